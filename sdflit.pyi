@@ -1,4 +1,5 @@
-from typing import Literal, Self, Tuple
+from os import PathLike
+from typing import Callable, Literal, Self, Sequence, Tuple
 
 import numpy as np
 import numpy.typing as npt
@@ -6,11 +7,17 @@ import numpy.typing as npt
 __all__ = [
     # fmt: off
     "SDF",
-    "FrustumCone", "RoundCone", "Sphere",
+    "FrustumCone", "FunctionSDF", "Metaballs", "RoundCone", "Sphere", "Spheres",
+    "SplineTube", "Tube",
     "merge", "intersect", "subtract",
-    "Material", "ColoredMaterial", "LinearGradientMaterial",
+    "repeat", "repeat_radial", "mirror", "round", "shell", "elongate",
+    "twist", "bend", "warp", "displace",
+    "Material", "ColoredMaterial", "LinearGradientMaterial", "NoiseMaterial",
+    "CheckerMaterial", "StripesMaterial", "ColormapMaterial",
+    "FalloffMaterial", "ChannelsMaterial", "OpacityMaterial",
+    "FunctionMaterial",
     "Object", "SDFObject",
-    "Scene", "ObjectsScene",
+    "Scene", "ObjectsScene", "ElasticScene",
     "RangeSampler", "UniformSampler"
     # fmt: on
 ]
@@ -23,6 +30,9 @@ class SDF:
     def distance(self, p: Vec3f) -> float: ...
     def inside(self, p: Vec3f) -> bool: ...
     def bounding_box(self) -> Tuple[Vec3f, Vec3f]: ...
+    def __or__(self, other: SDFLike) -> SDF: ...
+    def __and__(self, other: SDFLike) -> SDF: ...
+    def __sub__(self, other: SDFLike) -> SDF: ...
 
 class FrustumCone:
     def __new__(cls, a: Vec3f, b: Vec3f, ra: float, rb: float) -> FrustumCone: ...
@@ -30,6 +40,24 @@ class FrustumCone:
     def inside(self, p: Vec3f) -> bool: ...
     def bounding_box(self) -> Tuple[Vec3f, Vec3f]: ...
     def into(self) -> SDF: ...
+    def __or__(self, other: SDFLike) -> SDF: ...
+    def __and__(self, other: SDFLike) -> SDF: ...
+    def __sub__(self, other: SDFLike) -> SDF: ...
+
+class FunctionSDF:
+    def __new__(
+        cls,
+        func: Callable[..., float | npt.ArrayLike],
+        bounding_box: Tuple[Vec3f, Vec3f],
+        vectorized: bool = False,
+    ) -> FunctionSDF: ...
+    def distance(self, p: Vec3f) -> float: ...
+    def inside(self, p: Vec3f) -> bool: ...
+    def bounding_box(self) -> Tuple[Vec3f, Vec3f]: ...
+    def into(self) -> SDF: ...
+    def __or__(self, other: SDFLike) -> SDF: ...
+    def __and__(self, other: SDFLike) -> SDF: ...
+    def __sub__(self, other: SDFLike) -> SDF: ...
 
 class RoundCone:
     def __new__(cls, a: Vec3f, b: Vec3f, ra: float, rb: float) -> RoundCone: ...
@@ -37,6 +65,9 @@ class RoundCone:
     def inside(self, p: Vec3f) -> bool: ...
     def bounding_box(self) -> Tuple[Vec3f, Vec3f]: ...
     def into(self) -> SDF: ...
+    def __or__(self, other: SDFLike) -> SDF: ...
+    def __and__(self, other: SDFLike) -> SDF: ...
+    def __sub__(self, other: SDFLike) -> SDF: ...
 
 class Sphere:
     def __new__(cls, center: Vec3f, radius: float) -> Sphere: ...
@@ -44,49 +75,247 @@ class Sphere:
     def inside(self, p: Vec3f) -> bool: ...
     def bounding_box(self) -> Tuple[Vec3f, Vec3f]: ...
     def into(self) -> SDF: ...
+    def __or__(self, other: SDFLike) -> SDF: ...
+    def __and__(self, other: SDFLike) -> SDF: ...
+    def __sub__(self, other: SDFLike) -> SDF: ...
+
+class Spheres:
+    def __new__(cls, centers: npt.ArrayLike, radii: npt.ArrayLike) -> Spheres: ...
+    def distance(self, p: Vec3f) -> float: ...
+    def inside(self, p: Vec3f) -> bool: ...
+    def bounding_box(self) -> Tuple[Vec3f, Vec3f]: ...
+    def into(self) -> SDF: ...
+    def __or__(self, other: SDFLike) -> SDF: ...
+    def __and__(self, other: SDFLike) -> SDF: ...
+    def __sub__(self, other: SDFLike) -> SDF: ...
+
+class Metaballs:
+    def __new__(
+        cls, centers: npt.ArrayLike, radii: npt.ArrayLike, k: float
+    ) -> Metaballs: ...
+    def distance(self, p: Vec3f) -> float: ...
+    def inside(self, p: Vec3f) -> bool: ...
+    def bounding_box(self) -> Tuple[Vec3f, Vec3f]: ...
+    def into(self) -> SDF: ...
+    def __or__(self, other: SDFLike) -> SDF: ...
+    def __and__(self, other: SDFLike) -> SDF: ...
+    def __sub__(self, other: SDFLike) -> SDF: ...
+
+SplineKind = Literal["catmull_rom", "bezier"]
+
+class SplineTube:
+    def __new__(
+        cls,
+        points: npt.ArrayLike,
+        radii: npt.ArrayLike,
+        kind: SplineKind = "catmull_rom",
+    ) -> SplineTube: ...
+    def distance(self, p: Vec3f) -> float: ...
+    def inside(self, p: Vec3f) -> bool: ...
+    def bounding_box(self) -> Tuple[Vec3f, Vec3f]: ...
+    def into(self) -> SDF: ...
+    def __or__(self, other: SDFLike) -> SDF: ...
+    def __and__(self, other: SDFLike) -> SDF: ...
+    def __sub__(self, other: SDFLike) -> SDF: ...
+
+class Tube:
+    def __new__(
+        cls, nodes: npt.ArrayLike, radii: npt.ArrayLike
+    ) -> Tube: ...
+    def distance(self, p: Vec3f) -> float: ...
+    def inside(self, p: Vec3f) -> bool: ...
+    def bounding_box(self) -> Tuple[Vec3f, Vec3f]: ...
+    def into(self) -> SDF: ...
+    def __or__(self, other: SDFLike) -> SDF: ...
+    def __and__(self, other: SDFLike) -> SDF: ...
+    def __sub__(self, other: SDFLike) -> SDF: ...
 
 # Composition
 
-def merge(a: SDF, b: SDF) -> SDF: ...
-def intersect(a: SDF, b: SDF) -> SDF: ...
-def subtract(a: SDF, b: SDF) -> SDF: ...
+SDFLike = (
+    SDF
+    | FrustumCone
+    | FunctionSDF
+    | Metaballs
+    | RoundCone
+    | Sphere
+    | Spheres
+    | SplineTube
+    | Tube
+)
+
+def merge(a: SDFLike, b: SDFLike) -> SDF: ...
+def intersect(a: SDFLike, b: SDFLike) -> SDF: ...
+def subtract(a: SDFLike, b: SDFLike) -> SDF: ...
+
+# Modifier
+
+def repeat(
+    sdf: SDFLike, spacing: Vec3f, counts: Tuple[int, int, int] | None = None
+) -> SDF: ...
+def repeat_radial(
+    sdf: SDFLike, n: int, axis: Vec3f = (0, 0, 1), center: Vec3f = (0, 0, 0)
+) -> SDF: ...
+def mirror(sdf: SDFLike, normal: Vec3f, origin: Vec3f = (0, 0, 0)) -> SDF: ...
+def round(sdf: SDFLike, r: float) -> SDF: ...
+def shell(sdf: SDFLike, thickness: float) -> SDF: ...
+def elongate(sdf: SDFLike, h: Vec3f) -> SDF: ...
+def twist(
+    sdf: SDFLike, k: float, axis: Vec3f = (0, 0, 1), origin: Vec3f = (0, 0, 0)
+) -> SDF: ...
+def bend(
+    sdf: SDFLike,
+    k: float,
+    axis: Vec3f = (1, 0, 0),
+    normal: Vec3f = (0, 1, 0),
+    origin: Vec3f = (0, 0, 0),
+) -> SDF: ...
+def warp(sdf: SDFLike, field: npt.ArrayLike, min: Vec3f, max: Vec3f) -> SDF: ...
+
+NoiseKind = Literal["perlin", "simplex"]
+
+def displace(
+    sdf: SDFLike,
+    amplitude: float,
+    frequency: float = 1,
+    octaves: int = 1,
+    seed: int = 0,
+    kind: NoiseKind = "perlin",
+) -> SDF: ...
 
 # Material
 
-class Material: ...
+class Material:
+    def channels(self) -> int: ...
 
 class ColoredMaterial:
     def __new__(cls, color: Vec3f) -> ColoredMaterial: ...
     def into(self) -> Material: ...
 
-Axis = Literal["x", "y", "z"]
+Axis = Literal["u", "v", "w", "distance"]
 
 class LinearGradientMaterial:
     def __new__(cls, c1: Vec3f, c2: Vec3f, axis: Axis) -> LinearGradientMaterial: ...
     def into(self) -> Material: ...
 
+class NoiseMaterial:
+    def __new__(
+        cls,
+        c1: Vec3f,
+        c2: Vec3f,
+        frequency: float = 1,
+        octaves: int = 1,
+        seed: int = 0,
+        kind: NoiseKind = "perlin",
+        threshold: float | None = None,
+    ) -> NoiseMaterial: ...
+    def into(self) -> Material: ...
+
+class CheckerMaterial:
+    def __new__(cls, c1: Vec3f, c2: Vec3f, size: Vec3f) -> CheckerMaterial: ...
+    def into(self) -> Material: ...
+
+WorldAxis = Literal["u", "v", "w", "distance"]
+
+class StripesMaterial:
+    def __new__(
+        cls, c1: Vec3f, c2: Vec3f, width: float, axis: WorldAxis = "x"
+    ) -> StripesMaterial: ...
+    def into(self) -> Material: ...
+
+Colormap = Literal["viridis", "magma", "turbo"]
+
+class ColormapMaterial:
+    def __new__(
+        cls,
+        colormap: Colormap | npt.ArrayLike,
+        axis: Axis = "u",
+        vmin: float = 0,
+        vmax: float = 1,
+        stops: npt.ArrayLike | None = None,
+    ) -> ColormapMaterial: ...
+    def into(self) -> Material: ...
+
+FalloffProfile = Literal["linear", "gaussian", "exponential"]
+FalloffOrigin = Literal["surface", "centerline"]
+
+class FalloffMaterial:
+    def __new__(
+        cls,
+        color: Vec3f,
+        scale: float,
+        profile: FalloffProfile = "linear",
+        origin: FalloffOrigin = "surface",
+        radius: float | None = None,
+        background: Vec3f = (0, 0, 0),
+    ) -> FalloffMaterial: ...
+    def into(self) -> Material: ...
+
+class ChannelsMaterial:
+    def __new__(
+        cls, values: npt.ArrayLike, intensity: Material | None = None
+    ) -> ChannelsMaterial: ...
+    def into(self) -> Material: ...
+
+class OpacityMaterial:
+    def __new__(
+        cls, material: Material, opacity: float = 1, intensity: float = 1
+    ) -> OpacityMaterial: ...
+    def into(self) -> Material: ...
+
+class FunctionMaterial:
+    def __new__(
+        cls,
+        func: Callable[..., Sequence[float] | npt.ArrayLike],
+        channels: int = 3,
+        vectorized: bool = False,
+    ) -> FunctionMaterial: ...
+    def into(self) -> Material: ...
+
 # Object
 
 class Object: ...
 
 class SDFObject:
-    def __new__(cls, sdf: SDF, material: Material) -> SDFObject: ...
+    def __new__(cls, sdf: SDFLike, material: Material) -> SDFObject: ...
     def into(self) -> Object: ...
 
 # Scene
 
 class Scene:
-    def hit(self, p: Vec3f) -> Vec3f: ...
+    def hit(self, p: Vec3f) -> Tuple[float, ...]: ...
+    def channels(self) -> int: ...
     def bounding_box(self) -> Tuple[Vec3f, Vec3f] | None: ...
+    def save(self, path: str | PathLike[str]) -> None: ...
+
+Blend = Literal["first", "max", "additive", "alpha_over"]
+BVHMethod = Literal["median", "sah"]
 
 class ObjectsScene:
-    def __new__(cls) -> ObjectsScene: ...
-    def add_object(self, object: Object) -> None: ...
-    def build_bvh(self) -> None: ...
-    def set_background(self, background: Vec3f) -> None: ...
+    def __new__(cls, channels: int = 3) -> ObjectsScene: ...
+    def add_object(self, object: Object, priority: int = 0) -> None: ...
+    def build_bvh(
+        self,
+        method: BVHMethod = "median",
+        leaf_size: int = 1,
+        threads: int | None = None,
+    ) -> None: ...
+    def bvh_cost(self) -> float | None: ...
+    def set_background(self, background: Sequence[float]) -> None: ...
+    def set_blend(self, blend: Blend) -> None: ...
+    def channels(self) -> int: ...
     def bounding_box(self) -> Tuple[Vec3f, Vec3f]: ...
     def into(self) -> Scene: ...
 
+class ElasticScene:
+    def __new__(
+        cls, scene: Scene, spacing: Vec3f, sigma: float, seed: int = 0
+    ) -> ElasticScene: ...
+    def bounding_box(self) -> Tuple[Vec3f, Vec3f] | None: ...
+    def into(self) -> Scene: ...
+
+def load_scene(path: str | PathLike[str]) -> Scene: ...
+
 # Sampler
 
 class RangeSampler:
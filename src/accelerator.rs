@@ -2,87 +2,509 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+use std::panic;
+use std::thread::{self, ScopedJoinHandle};
 use std::{cmp::Ordering, sync::Arc};
 
+use serde::{Deserialize, Serialize};
+
 use crate::object::Object;
-use crate::vec3::{self, Vec3f};
+use crate::sdf::SDFHitInfo;
+use crate::vec3::{self, Float, Vec3f};
 
+/// Spatial index answering which objects of a scene contain a point.
 pub trait Accelerator: Send + Sync {
-    fn hit(&self, p: Vec3f) -> Option<Vec3f>;
+    /// Write the channels of the object hit at `p` with the lowest index into
+    /// `out`, returns its opacity or `None` on a miss.
+    fn hit(&self, p: Vec3f, out: &mut [f32]) -> Option<f32>;
+    /// Call `f` with the index and the object of every object that may
+    /// contain `p`.
+    fn visit(&self, p: Vec3f, f: &mut dyn FnMut(usize, &dyn Object));
+    /// The object of `index`.
+    fn object(&self, index: usize) -> Option<&dyn Object>;
+    /// All objects, in index order.
+    fn objects(&self) -> &[Arc<dyn Object>];
+    /// Bounds of all objects, `None` when there are none.
     fn bounding_box(&self) -> Option<(Vec3f, Vec3f)>;
+    /// Expected cost of a query within the bounds, in units of evaluating an
+    /// object, to compare the builds of an index.
+    fn cost(&self) -> Float;
+}
+
+/// How a BVH chooses the split of each node.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BVHMethod {
+    /// Halve the objects at the median center along the longest axis.
+    #[default]
+    Median,
+    /// Binned surface area heuristic, minimising the expected traversal cost.
+    Sah,
+}
+
+impl BVHMethod {
+    #[cfg(feature = "python")]
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            BVHMethod::Median => "median",
+            BVHMethod::Sah => "sah",
+        }
+    }
 }
 
+impl TryFrom<&str> for BVHMethod {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "median" | "Median" => Ok(Self::Median),
+            "sah" | "SAH" | "Sah" => Ok(Self::Sah),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Cost of testing a point against the bounding box of a node, relative to
+/// evaluating an object.
+const TRAVERSAL_COST: Float = 0.125;
+
+/// Cost of evaluating an object.
+const INTERSECTION_COST: Float = 1.0;
+
+/// Number of bins along the split axis of the SAH builder.
+const SAH_BINS: usize = 16;
+
+/// Depth after which the SAH builder falls back to median splits, leaving room
+/// for their `log2(n)` levels in the traversal stack.
+const MAX_SAH_DEPTH: usize = 24;
+
+/// Fewest objects of a node to build on several threads.
+const PARALLEL_MIN_OBJECTS: usize = 1 << 14;
+
+/// Nodes pending in the traversal stack of a `FlatBVH`, at least its depth.
+const STACK_SIZE: usize = 64;
+
 /**
  * Bounding Volume Hierarchy
  */
 #[allow(clippy::upper_case_acronyms)]
+#[allow(missing_debug_implementations)]
 pub struct BVH {
     root: Option<BVHNode>,
+    unbounded: Vec<usize>, // objects of infinite bounds, tested at every point
     objects: Vec<Arc<dyn Object>>,
 }
 
 impl BVH {
+    /// Build the hierarchy over the bounding boxes of `objects`.
+    #[must_use]
     pub fn new(objects: Vec<Arc<dyn Object>>) -> Self {
+        Self::with(objects, BVHMethod::Median, 1, 1)
+    }
+
+    /// Build the hierarchy with `method`, keeping up to `leaf_size` objects in
+    /// a leaf, with up to `threads` threads. The hierarchy does not depend on
+    /// the number of threads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `leaf_size` is zero.
+    pub fn build(
+        objects: Vec<Arc<dyn Object>>,
+        method: BVHMethod,
+        leaf_size: usize,
+        threads: usize,
+    ) -> Result<Self, &'static str> {
+        if leaf_size == 0 {
+            return Err("leaf size should be positive");
+        }
+
+        Ok(Self::with(objects, method, leaf_size, threads))
+    }
+
+    fn with(
+        objects: Vec<Arc<dyn Object>>,
+        method: BVHMethod,
+        leaf_size: usize,
+        threads: usize,
+    ) -> Self {
+        // infinite bounds have no center to split at
+        let (bounded, unbounded): (Vec<_>, Vec<_>) = objects
+            .iter()
+            .enumerate()
+            .map(|(i, o)| BVHBuildInfo::new(i, o.bounding_box()))
+            .partition(|o| is_finite(o.bounding_box));
+
         Self {
-            root: match objects.len() {
+            root: match bounded.len() {
                 0 => None,
-                _ => Some(BVHNode::new(&objects)),
+                _ => Some(BVHNode::build_from(bounded, method, leaf_size, threads)),
             },
+            unbounded: unbounded.into_iter().map(|o| o.index).collect(),
             objects,
         }
     }
 }
 
 impl Accelerator for BVH {
-    fn hit(&self, p: Vec3f) -> Option<Vec3f> {
-        match &self.root {
-            Some(r) => r
-                .hit(p)
-                .find_map(|idx| self.objects.get(idx).and_then(|obj| obj.hit(p))),
-            None => None,
+    fn hit(&self, p: Vec3f, out: &mut [f32]) -> Option<f32> {
+        // locate the lowest hit first, then shade only that one
+        let mut hit_info = |idx: usize| self.objects.get(idx)?.hit_info(p);
+        let mut best = None;
+        if let Some(root) = &self.root {
+            root.first_hit(p, &mut best, &mut hit_info);
         }
+
+        for &idx in &self.unbounded {
+            if best.as_ref().is_some_and(|(i, _)| *i <= idx) {
+                break;
+            }
+
+            if let Some(info) = hit_info(idx) {
+                best = Some((idx, info));
+                break;
+            }
+        }
+
+        let (idx, info) = best?;
+        Some(self.objects.get(idx)?.shade(p, info, out))
+    }
+
+    fn visit(&self, p: Vec3f, f: &mut dyn FnMut(usize, &dyn Object)) {
+        let candidates = self.root.iter().flat_map(|r| r.hit(p));
+        for idx in candidates.chain(self.unbounded.iter().copied()) {
+            if let Some(obj) = self.objects.get(idx) {
+                f(idx, obj.as_ref());
+            }
+        }
+    }
+
+    fn object(&self, index: usize) -> Option<&dyn Object> {
+        self.objects.get(index).map(AsRef::as_ref)
+    }
+
+    fn objects(&self) -> &[Arc<dyn Object>] {
+        &self.objects
     }
 
     fn bounding_box(&self) -> Option<(Vec3f, Vec3f)> {
-        self.root.as_ref().map(|r| r.bounding_box)
+        let root = self.root.as_ref().map(|r| r.bounding_box);
+        bounds(root, &self.unbounded, &self.objects)
+    }
+
+    fn cost(&self) -> Float {
+        let root = self.root.as_ref().map_or(0.0, BVHNode::cost);
+        root + unbounded_cost(&self.unbounded)
     }
 }
 
-struct BVHNode {
+/**
+ * Flattened Bounding Volume Hierarchy
+ *
+ * The nodes of a BVH in a single array in depth-first order, the left child of
+ * an inner node next to it, traversed with a fixed-size stack. Queries visit
+ * the objects in the same order as `BVH`.
+ */
+#[allow(clippy::upper_case_acronyms)]
+#[allow(missing_debug_implementations)]
+pub struct FlatBVH {
+    nodes: Vec<FlatNode>,
+    indices: Vec<u32>,     // objects of the leaves, leaf by leaf
+    unbounded: Vec<usize>, // objects of infinite bounds, tested at every point
+    objects: Vec<Arc<dyn Object>>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct FlatNode {
+    bounding_box: (Vec3f, Vec3f),
+    offset: u32, // first object in `indices` of a leaf, right child otherwise
+    count: u32,  // number of objects, 0 when inner
+    first: u32,  // lowest object index in the subtree
+}
+
+impl FlatBVH {
+    /// Build the hierarchy with `method`, keeping up to `leaf_size` objects in
+    /// a leaf, with up to `threads` threads. The hierarchy does not depend on
+    /// the number of threads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `leaf_size` is zero, or the objects are too many
+    /// for the layout.
+    pub fn build(
+        objects: Vec<Arc<dyn Object>>,
+        method: BVHMethod,
+        leaf_size: usize,
+        threads: usize,
+    ) -> Result<Self, &'static str> {
+        let bvh = BVH::build(objects, method, leaf_size, threads)?;
+        let mut flat = Self {
+            nodes: Vec::new(),
+            indices: Vec::new(),
+            unbounded: bvh.unbounded,
+            objects: bvh.objects,
+        };
+
+        if let Some(root) = &bvh.root {
+            if flat.flatten(root)? >= STACK_SIZE {
+                return Err("BVH is too deep");
+            }
+        }
+        Ok(flat)
+    }
+
+    /// Append `node` and its subtrees in depth-first order, returns its depth.
+    fn flatten(&mut self, node: &BVHNode) -> Result<usize, &'static str> {
+        const TOO_MANY: &str = "too many objects for a flattened BVH";
+        let i = self.nodes.len();
+        self.nodes.push(FlatNode {
+            bounding_box: node.bounding_box,
+            offset: u32::try_from(self.indices.len()).map_err(|_| TOO_MANY)?,
+            count: u32::try_from(node.indices.len()).map_err(|_| TOO_MANY)?,
+            first: u32::try_from(node.first).map_err(|_| TOO_MANY)?,
+        });
+
+        for &index in &node.indices {
+            self.indices
+                .push(u32::try_from(index).map_err(|_| TOO_MANY)?);
+        }
+
+        let Some((left, right)) = &node.children else {
+            return Ok(0);
+        };
+
+        let depth = self.flatten(left)?;
+        let offset = u32::try_from(self.nodes.len()).map_err(|_| TOO_MANY)?;
+        if let Some(node) = self.nodes.get_mut(i) {
+            node.offset = offset;
+        }
+        Ok(depth.max(self.flatten(right)?) + 1)
+    }
+
+    /// Call `f` with the index of every object in the leaves containing `p`,
+    /// then of the unbounded objects.
+    fn traverse(&self, p: Vec3f, mut f: impl FnMut(usize)) {
+        let mut stack = [0; STACK_SIZE];
+        let mut len = usize::from(!self.nodes.is_empty());
+        while let Some(i) = len.checked_sub(1).and_then(|k| stack.get(k).copied()) {
+            len -= 1;
+            let Some(node) = self.nodes.get(i as usize) else {
+                continue;
+            };
+
+            let (min, max) = node.bounding_box;
+            let isin = p.x >= min.x
+                && p.y >= min.y
+                && p.z >= min.z
+                && p.x <= max.x
+                && p.y <= max.y
+                && p.z <= max.z;
+            if !isin {
+                continue;
+            }
+
+            if node.count == 0 {
+                // the right child is visited first, as in `BVH`
+                for child in [i + 1, node.offset] {
+                    if let Some(slot) = stack.get_mut(len) {
+                        *slot = child;
+                        len += 1;
+                    }
+                }
+            } else {
+                let (start, count) = (node.offset as usize, node.count as usize);
+                for &index in self.indices.iter().skip(start).take(count) {
+                    f(index as usize);
+                }
+            }
+        }
+
+        self.unbounded.iter().copied().for_each(f);
+    }
+
+    /// Locate the object of the lowest index in the leaves containing `p`,
+    /// skipping the subtrees that cannot beat the best hit so far.
+    fn first_hit(&self, p: Vec3f) -> Option<(usize, SDFHitInfo)> {
+        let mut best: Option<(usize, SDFHitInfo)> = None;
+        let mut stack = [0; STACK_SIZE];
+        let mut len = usize::from(!self.nodes.is_empty());
+        while let Some(i) = len.checked_sub(1).and_then(|k| stack.get(k).copied()) {
+            len -= 1;
+            let Some(node) = self.nodes.get(i as usize) else {
+                continue;
+            };
+
+            if best.is_some_and(|(b, _)| b <= node.first as usize) {
+                continue;
+            }
+
+            let (min, max) = node.bounding_box;
+            let isin = p.x >= min.x
+                && p.y >= min.y
+                && p.z >= min.z
+                && p.x <= max.x
+                && p.y <= max.y
+                && p.z <= max.z;
+            if !isin {
+                continue;
+            }
+
+            if node.count == 0 {
+                // the child holding the lower indices on top, so that more get skipped
+                let (left, right) = (i + 1, node.offset);
+                let first = |j: u32| self.nodes.get(j as usize).map_or(u32::MAX, |n| n.first);
+                let children = if first(left) <= first(right) {
+                    [right, left]
+                } else {
+                    [left, right]
+                };
+                for child in children {
+                    if let Some(slot) = stack.get_mut(len) {
+                        *slot = child;
+                        len += 1;
+                    }
+                }
+            } else {
+                let (start, count) = (node.offset as usize, node.count as usize);
+                for &index in self.indices.iter().skip(start).take(count) {
+                    let index = index as usize;
+                    if best.is_some_and(|(b, _)| b <= index) {
+                        break;
+                    }
+
+                    if let Some(info) = self.objects.get(index).and_then(|obj| obj.hit_info(p)) {
+                        best = Some((index, info));
+                        break;
+                    }
+                }
+            }
+        }
+        best
+    }
+}
+
+impl Accelerator for FlatBVH {
+    fn hit(&self, p: Vec3f, out: &mut [f32]) -> Option<f32> {
+        // locate the lowest hit first, then shade only that one
+        let mut best = self.first_hit(p);
+        for &idx in &self.unbounded {
+            if best.is_some_and(|(i, _)| i <= idx) {
+                break;
+            }
+
+            if let Some(info) = self.objects.get(idx).and_then(|obj| obj.hit_info(p)) {
+                best = Some((idx, info));
+                break;
+            }
+        }
+
+        let (idx, info) = best?;
+        Some(self.objects.get(idx)?.shade(p, info, out))
+    }
+
+    fn visit(&self, p: Vec3f, f: &mut dyn FnMut(usize, &dyn Object)) {
+        self.traverse(p, |idx| {
+            if let Some(obj) = self.objects.get(idx) {
+                f(idx, obj.as_ref());
+            }
+        });
+    }
+
+    fn object(&self, index: usize) -> Option<&dyn Object> {
+        self.objects.get(index).map(AsRef::as_ref)
+    }
+
+    fn objects(&self) -> &[Arc<dyn Object>] {
+        &self.objects
+    }
+
+    fn bounding_box(&self) -> Option<(Vec3f, Vec3f)> {
+        let root = self.nodes.first().map(|r| r.bounding_box);
+        bounds(root, &self.unbounded, &self.objects)
+    }
+
+    fn cost(&self) -> Float {
+        let root = self.nodes.first().map_or(0.0, |r| area(r.bounding_box));
+        let nodes: Float = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let chance = if root > 0.0 {
+                    area(node.bounding_box) / root
+                } else {
+                    1.0
+                };
+
+                #[allow(clippy::cast_precision_loss, clippy::cast_lossless)]
+                match node.count {
+                    0 => chance * TRAVERSAL_COST,
+                    n => chance * INTERSECTION_COST * n as Float,
+                }
+            })
+            .sum();
+        nodes + unbounded_cost(&self.unbounded)
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub(crate) struct BVHNode {
     children: Option<(Box<BVHNode>, Box<BVHNode>)>,
     bounding_box: (Vec3f, Vec3f),
-    n: usize, // number of objects, 1 when leaf
-    index: usize,
+    indices: Vec<usize>, // objects of a leaf in ascending order, empty otherwise
+    first: usize,        // lowest object index in the subtree
 }
 
 impl BVHNode {
-    fn new(objects: &Vec<Arc<dyn Object>>) -> BVHNode {
-        let mut objects = objects
+    /// Build a tree over `bounding_boxes`, leaves refer to their index in the slice.
+    pub(crate) fn new(bounding_boxes: &[(Vec3f, Vec3f)]) -> BVHNode {
+        Self::build(bounding_boxes, BVHMethod::Median, 1, 1)
+    }
+
+    /// Build a tree with `method`, leaves hold at most `leaf_size` objects.
+    ///
+    /// Subtrees are built on up to `threads` threads, the tree does not depend
+    /// on the number of threads.
+    pub(crate) fn build(
+        bounding_boxes: &[(Vec3f, Vec3f)],
+        method: BVHMethod,
+        leaf_size: usize,
+        threads: usize,
+    ) -> BVHNode {
+        let objects = bounding_boxes
             .iter()
             .enumerate()
-            .map(|(i, x)| BVHBuildInfo::new(i, x))
+            .map(|(i, x)| BVHBuildInfo::new(i, *x))
             .collect::<Vec<_>>();
 
-        Self::new_(&mut objects)
+        Self::build_from(objects, method, leaf_size, threads)
     }
 
-    fn new_(objects: &mut [BVHBuildInfo]) -> BVHNode {
-        if objects.len() == 1 {
-            return BVHNode {
-                children: None,
-                bounding_box: objects.first().map(|o| o.bounding_box).unwrap(),
-                n: 1,
-                index: objects.first().map(|o| o.index).unwrap(),
-            };
+    fn build_from(
+        mut objects: Vec<BVHBuildInfo>,
+        method: BVHMethod,
+        leaf_size: usize,
+        threads: usize,
+    ) -> BVHNode {
+        match method {
+            BVHMethod::Median => Self::median(&mut objects, leaf_size, threads),
+            BVHMethod::Sah => Self::sah(&mut objects, leaf_size, 0, threads),
         }
+    }
 
-        let bounding_box = objects
-            .iter()
-            .map(|a| a.bounding_box)
-            .reduce(|(min, max), (emin, emax)| (vec3::minimum(min, emin), vec3::maximum(max, emax)))
-            .unwrap();
+    fn median(objects: &mut [BVHBuildInfo], leaf_size: usize, threads: usize) -> BVHNode {
+        if objects.len() <= leaf_size {
+            return Self::leaf(objects);
+        }
 
-        let shape = bounding_box.1 - bounding_box.0;
+        let threads = parallelism(objects, threads);
+
+        let (min, max) = par_fold(
+            objects,
+            threads,
+            |chunk| union(chunk.iter().map(|o| o.bounding_box)),
+            |a, b| union([a, b]),
+        );
+        let shape = max - min;
         let cmp = if shape.x >= shape.y && shape.x >= shape.z {
             BVHBuildInfo::cmp_x
         } else if shape.y >= shape.z {
@@ -91,28 +513,321 @@ impl BVHNode {
             BVHBuildInfo::cmp_z
         };
 
-        objects.sort_unstable_by(cmp);
-        let (left, right) = objects.split_at_mut(objects.len() / 2);
+        let mid = objects.len() / 2;
+        objects.select_nth_unstable_by(mid, cmp);
+        let (left, right) = objects.split_at_mut(mid);
+        Self::join(
+            threads,
+            (left.len(), |threads| Self::median(left, leaf_size, threads)),
+            (right.len(), |threads| {
+                Self::median(right, leaf_size, threads)
+            }),
+        )
+    }
+
+    fn sah(
+        objects: &mut [BVHBuildInfo],
+        leaf_size: usize,
+        depth: usize,
+        threads: usize,
+    ) -> BVHNode {
+        let n = objects.len();
+        if n == 1 {
+            return Self::leaf(objects);
+        }
+
+        // balance the deepest subtrees, keeping the depth within the traversal stack
+        if depth >= MAX_SAH_DEPTH {
+            return Self::median(objects, leaf_size, threads);
+        }
+
+        let threads = parallelism(objects, threads);
+
+        // bin the centers along the axis they spread the most
+        let (bounding_box, (min, max)) = par_fold(
+            objects,
+            threads,
+            |chunk| {
+                (
+                    union(chunk.iter().map(|o| o.bounding_box)),
+                    union(chunk.iter().map(|o| (o.center, o.center))),
+                )
+            },
+            |a, b| (union([a.0, b.0]), union([a.1, b.1])),
+        );
+        let shape = max - min;
+        let axis: fn(Vec3f) -> Float = if shape.x >= shape.y && shape.x >= shape.z {
+            |v| v.x
+        } else if shape.y >= shape.z {
+            |v| v.y
+        } else {
+            |v| v.z
+        };
+
+        let (lo, width) = (axis(min), axis(shape));
+        if width <= 0.0 {
+            // no split separates coincident centers
+            if n <= leaf_size {
+                return Self::leaf(objects);
+            }
+
+            let (left, right) = objects.split_at_mut(n / 2);
+            return Self::join(
+                threads,
+                (left.len(), |threads| {
+                    Self::sah(left, leaf_size, depth + 1, threads)
+                }),
+                (right.len(), |threads| {
+                    Self::sah(right, leaf_size, depth + 1, threads)
+                }),
+            );
+        }
+
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_precision_loss,
+            clippy::cast_sign_loss
+        )]
+        let bin = |o: &BVHBuildInfo| {
+            let k = (axis(o.center) - lo) / width * SAH_BINS as Float;
+            (k as usize).min(SAH_BINS - 1)
+        };
+
+        let bins = par_fold(
+            objects,
+            threads,
+            |chunk| {
+                let mut bins = [(0, None); SAH_BINS];
+                for o in chunk {
+                    if let Some(b) = bins.get_mut(bin(o)) {
+                        *b = merge_bin(*b, (1, Some(o.bounding_box)));
+                    }
+                }
+                bins
+            },
+            |mut a, b| {
+                for (a, b) in a.iter_mut().zip(b) {
+                    *a = merge_bin(*a, b);
+                }
+                a
+            },
+        );
+
+        let (k, weight) = best_split(&bins);
+
+        // compare with a leaf, both costs scaled by the area of the node
+        let node = area(bounding_box);
+        #[allow(clippy::cast_precision_loss)]
+        let leaf = n as Float * INTERSECTION_COST * node;
+        let split = TRAVERSAL_COST * node + INTERSECTION_COST * weight;
+        if n <= leaf_size && leaf <= split {
+            return Self::leaf(objects);
+        }
+
+        let mid = partition(objects, |o| bin(o) < k);
+        let (left, right) = objects.split_at_mut(mid);
+        Self::join(
+            threads,
+            (left.len(), |threads| {
+                Self::sah(left, leaf_size, depth + 1, threads)
+            }),
+            (right.len(), |threads| {
+                Self::sah(right, leaf_size, depth + 1, threads)
+            }),
+        )
+    }
+
+    /// Build the two subtrees of an inner node, sharing out the threads by
+    /// their number of objects.
+    ///
+    /// Only a node with several threads spawns one, for its left subtree, so
+    /// that a build spawns fewer than `threads` threads to build subtrees, and
+    /// every node below a single thread is built serially.
+    fn join<A, B>(threads: usize, (nl, left): (usize, A), (nr, right): (usize, B)) -> BVHNode
+    where
+        A: FnOnce(usize) -> BVHNode + Send,
+        B: FnOnce(usize) -> BVHNode,
+    {
+        if threads <= 1 {
+            return Self::inner(left(1), right(1));
+        }
+
+        let n = (nl + nr).max(1);
+        let tl = ((threads * nl + n / 2) / n).clamp(1, threads - 1);
+        thread::scope(|s| {
+            let handle = s.spawn(|| left(tl));
+            let right = right(threads - tl);
+            Self::inner(join_thread(handle), right)
+        })
+    }
+
+    fn leaf(objects: &[BVHBuildInfo]) -> BVHNode {
+        let mut indices = objects.iter().map(|o| o.index).collect::<Vec<_>>();
+        indices.sort_unstable();
         BVHNode {
-            children: Some((Box::new(Self::new_(left)), Box::new(Self::new_(right)))),
-            bounding_box,
-            n: objects.len(),
-            index: objects.first().map(|o| o.index).unwrap(),
+            children: None,
+            bounding_box: union(objects.iter().map(|o| o.bounding_box)),
+            first: indices.first().copied().unwrap_or_default(),
+            indices,
+        }
+    }
+
+    fn inner(left: BVHNode, right: BVHNode) -> BVHNode {
+        BVHNode {
+            bounding_box: union([left.bounding_box, right.bounding_box]),
+            first: left.first.min(right.first),
+            children: Some((Box::new(left), Box::new(right))),
+            indices: Vec::new(),
+        }
+    }
+
+    pub(crate) fn hit(&self, p: Vec3f) -> BVHHitIter<'_> {
+        BVHHitIter {
+            s: vec![&self],
+            leaf: [].iter(),
+            p,
+        }
+    }
+
+    /// Expected cost of a query in the bounding box, each node weighted by the
+    /// ratio of its surface area to the root as the chance of visiting it.
+    pub(crate) fn cost(&self) -> Float {
+        let root = area(self.bounding_box);
+        let mut cost = 0.0;
+        let mut s = vec![self];
+        while let Some(node) = s.pop() {
+            let chance = if root > 0.0 {
+                area(node.bounding_box) / root
+            } else {
+                1.0
+            };
+
+            #[allow(clippy::cast_precision_loss)]
+            match &node.children {
+                None => cost += chance * INTERSECTION_COST * node.indices.len() as Float,
+                Some((left, right)) => {
+                    cost += chance * TRAVERSAL_COST;
+                    s.push(left);
+                    s.push(right);
+                }
+            }
+        }
+        cost
+    }
+
+    /// Find the lowest index of the objects containing `p` for which `f` is
+    /// some, skipping the subtrees that cannot beat `best`.
+    fn first_hit<T>(
+        &self,
+        p: Vec3f,
+        best: &mut Option<(usize, T)>,
+        f: &mut impl FnMut(usize) -> Option<T>,
+    ) {
+        if best.as_ref().is_some_and(|(i, _)| *i <= self.first) || !self.isin(p) {
+            return;
+        }
+
+        match &self.children {
+            None => {
+                for &index in &self.indices {
+                    if best.as_ref().is_some_and(|(i, _)| *i <= index) {
+                        break;
+                    }
+
+                    if let Some(x) = f(index) {
+                        *best = Some((index, x));
+                        break;
+                    }
+                }
+            }
+            // the child holding the lower indices first, so that more get skipped
+            Some((left, right)) => {
+                let (a, b) = if left.first <= right.first {
+                    (left, right)
+                } else {
+                    (right, left)
+                };
+                a.first_hit(p, best, f);
+                b.first_hit(p, best, f);
+            }
         }
     }
 
-    fn hit(&self, p: Vec3f) -> BVHHitIter<'_> {
-        BVHHitIter { s: vec![&self], p }
+    /// Find the leaf minimising `f`, where `f(index)` must never be less than the
+    /// distance from `p` to the bounding box of that leaf whenever `p` lies outside.
+    pub(crate) fn nearest<F>(&self, p: Vec3f, mut f: F) -> (usize, Float)
+    where
+        F: FnMut(usize) -> Float,
+    {
+        let mut best = (self.first, Float::INFINITY);
+        let mut s = vec![self];
+        while let Some(node) = s.pop() {
+            let d = node.distance_to_box(p);
+            if d > 0.0 && d >= best.1 {
+                continue;
+            }
+
+            match &node.children {
+                None => {
+                    for &index in &node.indices {
+                        let sd = f(index);
+                        if sd < best.1 {
+                            best = (index, sd);
+                        }
+                    }
+                }
+                // visit the closer child first, so that more subtrees get pruned
+                Some((left, right)) if left.distance_to_box(p) < right.distance_to_box(p) => {
+                    s.push(right);
+                    s.push(left);
+                }
+                Some((left, right)) => {
+                    s.push(left);
+                    s.push(right);
+                }
+            }
+        }
+        best
+    }
+
+    /// Visit every leaf whose bounding box is not farther than `radius` from `p`,
+    /// leaves containing `p` are always visited.
+    pub(crate) fn within<F>(&self, p: Vec3f, radius: Float, mut f: F)
+    where
+        F: FnMut(usize),
+    {
+        let mut s = vec![self];
+        while let Some(node) = s.pop() {
+            let d = node.distance_to_box(p);
+            if d > 0.0 && d > radius {
+                continue;
+            }
+
+            match &node.children {
+                None => node.indices.iter().for_each(|&index| f(index)),
+                Some((left, right)) => {
+                    s.push(left);
+                    s.push(right);
+                }
+            }
+        }
     }
 
     fn isin(&self, p: Vec3f) -> bool {
         let (min, max) = self.bounding_box;
         p.x >= min.x && p.y >= min.y && p.z >= min.z && p.x <= max.x && p.y <= max.y && p.z <= max.z
     }
+
+    fn distance_to_box(&self, p: Vec3f) -> Float {
+        let (min, max) = self.bounding_box;
+        let zero = Vec3f::new(0.0, 0.0, 0.0);
+        vec3::maximum(vec3::maximum(min - p, p - max), zero).norm()
+    }
 }
 
-struct BVHHitIter<'a> {
+pub(crate) struct BVHHitIter<'a> {
     s: Vec<&'a BVHNode>,
+    leaf: std::slice::Iter<'a, usize>,
     p: Vec3f,
 }
 
@@ -120,20 +835,22 @@ impl Iterator for BVHHitIter<'_> {
     type Item = usize;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(node) = self.s.pop() {
-            match node.isin(self.p) {
-                true if node.n == 1 => return Some(node.index),
-                true => {
-                    if let Some((left, right)) = &node.children {
+        loop {
+            if let Some(&index) = self.leaf.next() {
+                return Some(index);
+            }
+
+            let node = self.s.pop()?;
+            if node.isin(self.p) {
+                match &node.children {
+                    None => self.leaf = node.indices.iter(),
+                    Some((left, right)) => {
                         self.s.push(left);
                         self.s.push(right);
                     }
                 }
-                _ => (),
             }
         }
-
-        None
     }
 }
 
@@ -144,8 +861,7 @@ struct BVHBuildInfo {
 }
 
 impl BVHBuildInfo {
-    fn new(index: usize, object: &Arc<dyn Object>) -> BVHBuildInfo {
-        let (min, max) = object.bounding_box();
+    fn new(index: usize, (min, max): (Vec3f, Vec3f)) -> BVHBuildInfo {
         BVHBuildInfo {
             index,
             center: (min + max) / 2.0,
@@ -165,3 +881,381 @@ impl BVHBuildInfo {
         a.center.z.total_cmp(&b.center.z)
     }
 }
+
+/// Bounds of `boxes`, which should not be empty.
+fn union(boxes: impl IntoIterator<Item = (Vec3f, Vec3f)>) -> (Vec3f, Vec3f) {
+    let zero = Vec3f::new(0.0, 0.0, 0.0);
+    boxes
+        .into_iter()
+        .reduce(|(min, max), (emin, emax)| (vec3::minimum(min, emin), vec3::maximum(max, emax)))
+        .unwrap_or((zero, zero))
+}
+
+fn area((min, max): (Vec3f, Vec3f)) -> Float {
+    let d = max - min;
+    2.0 * (d.x * d.y + d.y * d.z + d.z * d.x)
+}
+
+/// Whether every corner of `bounding_box` is finite.
+fn is_finite((min, max): (Vec3f, Vec3f)) -> bool {
+    [min.x, min.y, min.z, max.x, max.y, max.z]
+        .iter()
+        .all(|x| x.is_finite())
+}
+
+/// Bounds of the `root` of a tree and of the `unbounded` objects out of it.
+fn bounds(
+    root: Option<(Vec3f, Vec3f)>,
+    unbounded: &[usize],
+    objects: &[Arc<dyn Object>],
+) -> Option<(Vec3f, Vec3f)> {
+    let unbounded = unbounded
+        .iter()
+        .filter_map(|&i| objects.get(i).map(|o| o.bounding_box()));
+    root.into_iter()
+        .chain(unbounded)
+        .reduce(|a, b| union([a, b]))
+}
+
+/// The unbounded objects are tested at every point.
+#[allow(clippy::cast_precision_loss)]
+fn unbounded_cost(unbounded: &[usize]) -> Float {
+    INTERSECTION_COST * unbounded.len() as Float
+}
+
+/// Threads to build with when not given, the available parallelism.
+pub(crate) fn available_threads() -> usize {
+    thread::available_parallelism().map_or(1, usize::from)
+}
+
+/// Number of objects and bounds of a bin of the SAH builder.
+type Bin = (usize, Option<(Vec3f, Vec3f)>);
+
+fn merge_bin(a: Bin, b: Bin) -> Bin {
+    let bounding_box = match (a.1, b.1) {
+        (Some(a), Some(b)) => Some(union([a, b])),
+        (a, b) => a.or(b),
+    };
+    (a.0 + b.0, bounding_box)
+}
+
+/// Split between the bins `k - 1` and `k`, the first of the cheapest, with the
+/// area times count of both sides, infinite when no split separates objects.
+fn best_split(bins: &[Bin]) -> (usize, Float) {
+    // number of objects and their area times count, in the bins up to each one
+    #[allow(clippy::cast_precision_loss)]
+    let sweep = |acc: &mut Bin, &b: &Bin| {
+        *acc = merge_bin(*acc, b);
+        Some((acc.0, acc.0 as Float * acc.1.map_or(0.0, area)))
+    };
+    let left = bins.iter().scan((0, None), sweep).collect::<Vec<_>>();
+    let mut right = bins.iter().rev().scan((0, None), sweep).collect::<Vec<_>>();
+    right.reverse();
+
+    left.iter()
+        .zip(right.iter().skip(1))
+        .enumerate()
+        .filter(|(_, ((nl, _), (nr, _)))| *nl > 0 && *nr > 0)
+        .map(|(i, ((_, wl), (_, wr)))| (i + 1, wl + wr))
+        .reduce(|best, x| if x.1 < best.1 { x } else { best })
+        .unwrap_or((SAH_BINS / 2, Float::INFINITY))
+}
+
+/// Threads worth using for `objects`, small nodes are built on a single one.
+fn parallelism(objects: &[BVHBuildInfo], threads: usize) -> usize {
+    if objects.len() < PARALLEL_MIN_OBJECTS {
+        1
+    } else {
+        threads
+    }
+}
+
+/// Fold the chunks of `objects` on up to `threads` threads, the first one on
+/// the calling thread, then merge the results in order, so that the result
+/// does not depend on the threads.
+fn par_fold<T, F, M>(objects: &[BVHBuildInfo], threads: usize, fold: F, merge: M) -> T
+where
+    T: Send,
+    F: Fn(&[BVHBuildInfo]) -> T + Sync,
+    M: Fn(T, T) -> T,
+{
+    if threads <= 1 {
+        return fold(objects);
+    }
+
+    let (head, tail) = objects.split_at(objects.len().div_ceil(threads));
+    let fold = &fold;
+    thread::scope(|s| {
+        let handles = tail
+            .chunks(head.len().max(1))
+            .map(|chunk| s.spawn(move || fold(chunk)))
+            .collect::<Vec<_>>();
+        let head = fold(head);
+        handles.into_iter().map(join_thread).fold(head, merge)
+    })
+}
+
+fn join_thread<T>(handle: ScopedJoinHandle<'_, T>) -> T {
+    handle
+        .join()
+        .unwrap_or_else(|err| panic::resume_unwind(err))
+}
+
+/// Move the objects satisfying `pred` to the front, returns how many they are.
+fn partition(objects: &mut [BVHBuildInfo], pred: impl Fn(&BVHBuildInfo) -> bool) -> usize {
+    let mut mid = 0;
+    for i in 0..objects.len() {
+        if objects.get(i).is_some_and(&pred) {
+            objects.swap(i, mid);
+            mid += 1;
+        }
+    }
+    mid
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{self, AtomicUsize};
+
+    use super::*;
+    use crate::material::{ChannelsMaterial, DynMaterial};
+    use crate::modifier::Repetition;
+    use crate::object::SDFObject;
+    use crate::primitive::{RoundCone, Sphere};
+    use crate::sdf::DynSDF;
+
+    /// Boxes of the segments of a long thin neurite, a few long ones among many
+    /// short ones.
+    fn neurite() -> Vec<(Vec3f, Vec3f)> {
+        let mut x = 0.0;
+        (0..200_u16)
+            .map(|i| {
+                let len = if i % 25 == 0 {
+                    40.0
+                } else {
+                    0.5 + Float::from(i % 7) * 0.1
+                };
+                let (a, b) = (Vec3f::new(x, 0.0, 0.0), Vec3f::new(x + len, 0.1 * len, 0.0));
+                x += len;
+                (vec3::minimum(a, b) - 0.2, vec3::maximum(a, b) + 0.2)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_build() {
+        let boxes = neurite();
+        let contains = |(min, max): (Vec3f, Vec3f), p: Vec3f| {
+            p.x >= min.x
+                && p.y >= min.y
+                && p.z >= min.z
+                && p.x <= max.x
+                && p.y <= max.y
+                && p.z <= max.z
+        };
+
+        for method in [BVHMethod::Median, BVHMethod::Sah] {
+            for leaf_size in [1, 4] {
+                let bvh = BVHNode::build(&boxes, method, leaf_size, 1);
+                for i in 0..2000_u16 {
+                    let p =
+                        Vec3f::new(Float::from(i) * 0.31, Float::from(i % 13) * 0.25 - 0.5, 0.0);
+                    let mut hits = bvh.hit(p).collect::<Vec<_>>();
+                    hits.sort_unstable();
+                    let expected = (0..boxes.len())
+                        .filter(|&j| boxes.get(j).is_some_and(|b| contains(*b, p)))
+                        .collect::<Vec<_>>();
+                    // larger leaves also yield their other objects as candidates
+                    match leaf_size {
+                        1 => assert_eq!(hits, expected, "{method:?} at {p:?}"),
+                        _ => assert!(
+                            expected.iter().all(|j| hits.contains(j)),
+                            "{method:?} with {leaf_size} at {p:?}"
+                        ),
+                    }
+                }
+            }
+        }
+
+        let median = BVHNode::build(&boxes, BVHMethod::Median, 1, 1).cost();
+        let sah = BVHNode::build(&boxes, BVHMethod::Sah, 1, 1).cost();
+        assert!(sah < median, "{sah} >= {median}");
+    }
+
+    /// Round cones of one channel along the neurite, valued by their index.
+    fn objects() -> Vec<Arc<dyn Object>> {
+        neurite()
+            .into_iter()
+            .zip(0_u16..)
+            .map(|((min, max), i)| {
+                let sdf = RoundCone::new(min + 0.2, max - 0.2, 0.2, 0.2);
+                let material = ChannelsMaterial::new(vec![f32::from(i)], None).unwrap();
+                let object = SDFObject::new(DynSDF::new(sdf), DynMaterial::new(material));
+                let object: Arc<dyn Object> = Arc::new(object);
+                object
+            })
+            .collect()
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)] // the very same hits
+    fn test_flat() {
+        for method in [BVHMethod::Median, BVHMethod::Sah] {
+            for leaf_size in [1, 4] {
+                let bvh = BVH::build(objects(), method, leaf_size, 1).unwrap();
+                let flat = FlatBVH::build(objects(), method, leaf_size, 1).unwrap();
+                assert_eq!(flat.bounding_box(), bvh.bounding_box());
+                assert!((flat.cost() - bvh.cost()).abs() < 1e-4);
+
+                let visited = |acc: &dyn Accelerator, p| {
+                    let mut indices = Vec::new();
+                    acc.visit(p, &mut |i, _| indices.push(i));
+                    indices
+                };
+                for i in 0..2000_u16 {
+                    let p = Vec3f::new(Float::from(i) * 0.31, Float::from(i % 13) * 0.05, 0.0);
+                    let (mut a, mut b) = ([-1.0], [-1.0]);
+                    assert_eq!(flat.hit(p, &mut a), bvh.hit(p, &mut b));
+                    assert_eq!(a, b, "{method:?} with {leaf_size} at {p:?}");
+                    assert_eq!(visited(&flat, p), visited(&bvh, p));
+                }
+            }
+        }
+
+        assert!(FlatBVH::build(objects(), BVHMethod::Sah, 0, 1).is_err());
+        let empty = FlatBVH::build(Vec::new(), BVHMethod::Sah, 1, 1).unwrap();
+        assert!(empty.hit(Vec3f::new(0.0, 0.0, 0.0), &mut [0.0]).is_none());
+        assert!(empty.bounding_box().is_none());
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)] // the very same hits
+    fn test_unbounded() {
+        // a grid of spheres repeated forever along x and y, among the segments
+        let sphere = DynSDF::new(Sphere::new(Vec3f::new(0.0, 0.0, 0.0), 0.3));
+        let grid = Repetition::new(sphere, Vec3f::new(5.0, 5.0, 0.0), None).unwrap();
+        let material = ChannelsMaterial::new(vec![-2.0], None).unwrap();
+        let grid = SDFObject::new(DynSDF::new(grid), DynMaterial::new(material));
+        let mut objects = objects();
+        objects.insert(100, Arc::new(grid));
+
+        let (min, max) = objects.get(100).unwrap().bounding_box();
+        assert!(min.x.is_infinite() && max.y.is_infinite());
+        let flat = FlatBVH::build(objects.clone(), BVHMethod::Sah, 4, 1).unwrap();
+        assert!(flat.cost().is_finite());
+        assert_eq!(
+            flat.bounding_box().map(|(min, _)| min.x),
+            Some(Float::NEG_INFINITY)
+        );
+
+        for i in 0..2000_u16 {
+            let p = Vec3f::new(Float::from(i) * 0.31, Float::from(i % 13) * 0.5, 0.0);
+            let (mut a, mut b) = ([-1.0], [-1.0]);
+            let expected = objects.iter().find_map(|o| o.hit(p, &mut b));
+            assert_eq!(flat.hit(p, &mut a), expected);
+            assert_eq!(a, b, "at {p:?}");
+        }
+    }
+
+    /// An object counting its evaluations.
+    struct Counted {
+        object: SDFObject,
+        located: AtomicUsize,
+        shaded: AtomicUsize,
+    }
+
+    impl Object for Counted {
+        fn hit_info(&self, p: Vec3f) -> Option<SDFHitInfo> {
+            self.located.fetch_add(1, atomic::Ordering::Relaxed);
+            self.object.hit_info(p)
+        }
+
+        fn shade(&self, p: Vec3f, info: SDFHitInfo, out: &mut [f32]) -> f32 {
+            self.shaded.fetch_add(1, atomic::Ordering::Relaxed);
+            self.object.shade(p, info, out)
+        }
+
+        fn channels(&self) -> usize {
+            self.object.channels()
+        }
+
+        fn bounding_box(&self) -> (Vec3f, Vec3f) {
+            self.object.bounding_box()
+        }
+    }
+
+    #[test]
+    fn test_first_hit() {
+        // nested spheres all containing the center, only the first is evaluated
+        let spheres = (1..=64_u16)
+            .map(|i| {
+                let sdf = Sphere::new(Vec3f::new(0.0, 0.0, 0.0), Float::from(i));
+                let material = ChannelsMaterial::new(vec![f32::from(i)], None).unwrap();
+                Arc::new(Counted {
+                    object: SDFObject::new(DynSDF::new(sdf), DynMaterial::new(material)),
+                    located: AtomicUsize::new(0),
+                    shaded: AtomicUsize::new(0),
+                })
+            })
+            .collect::<Vec<_>>();
+        let objects = spheres
+            .iter()
+            .map(|o| {
+                let object: Arc<dyn Object> = o.clone();
+                object
+            })
+            .collect::<Vec<_>>();
+
+        for leaf_size in [1, 4] {
+            let bvh = BVH::build(objects.clone(), BVHMethod::Sah, leaf_size, 1).unwrap();
+            let flat = FlatBVH::build(objects.clone(), BVHMethod::Sah, leaf_size, 1).unwrap();
+            let accelerators: [&dyn Accelerator; 2] = [&bvh, &flat];
+            for acc in accelerators {
+                let mut out = [0.0];
+                assert!(acc.hit(Vec3f::new(0.5, 0.0, 0.0), &mut out).is_some());
+                assert!((out[0] - 1.0).abs() < f32::EPSILON);
+
+                let count = |o: &Counted| {
+                    (
+                        o.located.swap(0, atomic::Ordering::Relaxed),
+                        o.shaded.swap(0, atomic::Ordering::Relaxed),
+                    )
+                };
+                let counts = spheres.iter().map(|o| count(o)).collect::<Vec<_>>();
+                assert_eq!(counts.first(), Some(&(1, 1)));
+                assert!(counts.iter().skip(1).all(|&c| c == (0, 0)), "{counts:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_parallel() {
+        // enough boxes for the top nodes to be built on several threads
+        let mut seed = 1_u32;
+        let mut random = || {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            Float::from(u16::try_from(seed >> 16).unwrap()) / 64.0
+        };
+        let boxes = (0..PARALLEL_MIN_OBJECTS * 3)
+            .map(|_| {
+                let min = Vec3f::new(random(), random(), random());
+                (min, min + Vec3f::new(random(), random(), random()) / 256.0)
+            })
+            .collect::<Vec<_>>();
+
+        for method in [BVHMethod::Median, BVHMethod::Sah] {
+            let expected = BVHNode::build(&boxes, method, 4, 1);
+            for threads in [2, 3, 8] {
+                let bvh = BVHNode::build(&boxes, method, 4, threads);
+                assert!(bvh == expected, "{method:?} on {threads} threads");
+            }
+        }
+    }
+
+    #[test]
+    fn test_coincident() {
+        let b = (Vec3f::new(0.0, 0.0, 0.0), Vec3f::new(1.0, 1.0, 1.0));
+        let bvh = BVHNode::build(&[b; 9], BVHMethod::Sah, 2, 1);
+        assert_eq!(bvh.hit(Vec3f::new(0.5, 0.5, 0.5)).count(), 9);
+    }
+}
@@ -0,0 +1,280 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Command line renderer of sdflit scenes
+//!
+//! Samples a scene file or a SWC morphology into a volume, a maximum intensity
+//! projection or a surface mesh, without a Python environment.
+
+#![warn(
+    trivial_casts,
+    trivial_numeric_casts,
+    missing_copy_implementations,
+    missing_debug_implementations,
+    missing_docs,
+    unsafe_code,
+    unstable_features,
+    unused_import_braces,
+    unused_qualifications,
+    clippy::dbg_macro,
+    clippy::indexing_slicing,
+    clippy::pedantic
+)]
+
+mod output;
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::thread;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use sdflit::render::{self, Grid, Volume};
+use sdflit::{Float, Scene, Vec3f};
+
+use crate::output::{DType, MeshFormat, VolumeFormat};
+
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Sample the scene into a volume (.tif, .nrrd or .raw)
+    Volume {
+        #[command(flatten)]
+        sampling: Sampling,
+
+        /// Type of the written samples, integers scale [0, 1] to their range
+        #[arg(long, value_enum, default_value_t = DType::F32)]
+        dtype: DType,
+    },
+
+    /// Maximum intensity projection of the volume along an axis (.tif, .nrrd
+    /// or .raw)
+    Projection {
+        #[command(flatten)]
+        sampling: Sampling,
+
+        /// Axis to project along
+        #[arg(long, value_enum, default_value_t = Axis::Z)]
+        axis: Axis,
+
+        /// Type of the written samples, integers scale [0, 1] to their range
+        #[arg(long, value_enum, default_value_t = DType::F32)]
+        dtype: DType,
+    },
+
+    /// Mesh the surface of a channel of the volume (.obj or .ply)
+    Mesh {
+        #[command(flatten)]
+        sampling: Sampling,
+
+        /// Channel to mesh
+        #[arg(long, default_value_t = 0)]
+        channel: usize,
+
+        /// Value of the surface, samples above it are inside
+        #[arg(long, default_value_t = 0.5)]
+        iso: f32,
+    },
+}
+
+#[derive(Debug, Args)]
+struct Sampling {
+    /// Scene file (.json or .toml) or SWC morphology (.swc)
+    input: PathBuf,
+
+    /// Output file, its format chosen by the extension
+    output: PathBuf,
+
+    /// Lower corner of the sampled region, defaults to the bounding box of the
+    /// scene padded by a stride
+    #[arg(long, num_args = 3, value_names = ["X", "Y", "Z"], allow_negative_numbers = true)]
+    min: Option<Vec<Float>>,
+
+    /// Upper corner of the sampled region, excluded, defaults to the bounding
+    /// box of the scene padded by a stride
+    #[arg(long, num_args = 3, value_names = ["X", "Y", "Z"], allow_negative_numbers = true)]
+    max: Option<Vec<Float>>,
+
+    /// Distance between samples, one value for all axes or one per axis
+    #[arg(long, num_args = 1..=3, default_values_t = [1.0])]
+    stride: Vec<Float>,
+
+    /// Average N×N×N points in each voxel
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    supersampling: usize,
+
+    /// Number of threads, defaults to the available parallelism
+    #[arg(long)]
+    threads: Option<usize>,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("sdflit: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), String> {
+    match cli.command {
+        // the format is checked before the sampling, which may take long
+        Command::Volume { sampling, dtype } => {
+            let format = VolumeFormat::from_path(&sampling.output)?;
+            let (volume, grid) = sampling.sample()?;
+            output::write_volume(&sampling.output, format, &volume, &grid, dtype)
+        }
+        Command::Projection {
+            sampling,
+            axis,
+            dtype,
+        } => {
+            let format = VolumeFormat::from_path(&sampling.output)?;
+            let (volume, grid) = sampling.sample()?;
+            let projection = volume.project(axis as usize)?;
+            output::write_volume(&sampling.output, format, &projection, &grid, dtype)
+        }
+        Command::Mesh {
+            sampling,
+            channel,
+            iso,
+        } => {
+            let format = MeshFormat::from_path(&sampling.output)?;
+            let (volume, grid) = sampling.sample()?;
+            let mesh = render::surface_nets(&volume, &grid, channel, iso)?;
+            output::write_mesh(&sampling.output, format, &mesh)
+        }
+    }
+}
+
+impl Sampling {
+    fn sample(&self) -> Result<(Volume, Grid), String> {
+        let scene = load(&self.input)?;
+        let stride = match *self.stride.as_slice() {
+            [s] => Vec3f::new(s, s, s),
+            [x, y, z] => Vec3f::new(x, y, z),
+            _ => return Err("stride takes one or three values".to_owned()),
+        };
+
+        let corner = |v: &Option<Vec<Float>>| v.clone().map(Vec3f::try_from).transpose();
+        let (min, max) = match (corner(&self.min)?, corner(&self.max)?) {
+            (Some(min), Some(max)) => (min, max),
+            (min, max) => {
+                let (lower, upper) = scene
+                    .bounding_box()
+                    .ok_or("the scene is empty, set --min and --max")?;
+                (min.unwrap_or(lower - stride), max.unwrap_or(upper + stride))
+            }
+        };
+
+        let grid = Grid::new(min, max, stride)?;
+        let threads = self
+            .threads
+            .unwrap_or_else(|| thread::available_parallelism().map_or(1, usize::from));
+        let volume = render::sample(scene.as_ref(), &grid, self.supersampling, threads);
+        Ok((volume, grid))
+    }
+}
+
+/// Load a scene file or a SWC morphology, by the extension of `path`.
+fn load(path: &Path) -> Result<Arc<dyn Scene>, String> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("swc" | "SWC") => sdflit::load_swc(path),
+        _ => sdflit::load_scene(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(["sdflit"].iter().chain(args))
+    }
+
+    #[test]
+    fn test_cli() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_args() {
+        let cli = parse(&[
+            "projection",
+            "neuron.swc",
+            "mip.nrrd",
+            "--min",
+            "-1",
+            "-2.5",
+            "0",
+            "--stride",
+            "0.5",
+            "--axis",
+            "x",
+            "--dtype",
+            "u16",
+        ])
+        .unwrap();
+        let Command::Projection {
+            sampling,
+            axis,
+            dtype,
+        } = cli.command
+        else {
+            panic!("expected a projection");
+        };
+        assert_eq!(sampling.min, Some(vec![-1.0, -2.5, 0.0]));
+        assert_eq!(sampling.max, None);
+        assert_eq!(sampling.stride, [0.5]);
+        assert_eq!(sampling.supersampling, 1);
+        assert!(matches!((axis, dtype), (Axis::X, DType::U16)));
+
+        let cli = parse(&["mesh", "scene.toml", "out.ply", "--stride", "1", "2", "3"]).unwrap();
+        let Command::Mesh {
+            sampling,
+            channel,
+            iso,
+        } = cli.command
+        else {
+            panic!("expected a mesh");
+        };
+        assert_eq!(sampling.stride, [1.0, 2.0, 3.0]);
+        assert_eq!((channel, iso), (0, 0.5));
+
+        assert!(parse(&["volume", "scene.toml"]).is_err());
+        assert!(parse(&["volume", "a.toml", "b.tif", "--min", "1", "2"]).is_err());
+        assert!(parse(&["volume", "a.toml", "b.tif", "--stride", "1", "2", "3", "4"]).is_err());
+        assert!(parse(&["volume", "a.toml", "b.tif", "--dtype", "f16"]).is_err());
+    }
+
+    #[test]
+    fn test_unknown_format() {
+        // rejected before loading the missing scene, without creating a file
+        let output = std::env::temp_dir().join("sdflit-test-unknown-format.png");
+        let output = output.to_str().unwrap();
+        for command in ["volume", "projection", "mesh"] {
+            let cli = parse(&[command, "missing.toml", output]).unwrap();
+            let err = run(cli).unwrap_err();
+            assert!(err.contains("unknown"), "{command}: {err}");
+        }
+        assert!(!Path::new(output).exists());
+    }
+}
@@ -0,0 +1,544 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs::File;
+use std::io::{self, BufWriter, ErrorKind, Write};
+use std::path::Path;
+
+use clap::ValueEnum;
+use sdflit::render::{Grid, Mesh, Volume};
+use sdflit::Float;
+
+/// Type of the written samples.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum DType {
+    F32,
+    U16,
+    U8,
+}
+
+impl DType {
+    fn bits(self) -> u16 {
+        match self {
+            DType::F32 => 32,
+            DType::U16 => 16,
+            DType::U8 => 8,
+        }
+    }
+
+    fn nrrd(self) -> &'static str {
+        match self {
+            DType::F32 => "float",
+            DType::U16 => "uint16",
+            DType::U8 => "uint8",
+        }
+    }
+
+    /// Append `values` as little-endian samples, integers scale [0, 1] to
+    /// their range.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn encode(self, values: &[f32], out: &mut Vec<u8>) {
+        for &v in values {
+            match self {
+                DType::F32 => out.extend(v.to_le_bytes()),
+                DType::U16 => {
+                    let v = (v.clamp(0.0, 1.0) * f32::from(u16::MAX)).round() as u16;
+                    out.extend(v.to_le_bytes());
+                }
+                DType::U8 => out.push((v.clamp(0.0, 1.0) * f32::from(u8::MAX)).round() as u8),
+            }
+        }
+    }
+}
+
+/// Format of a written volume.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VolumeFormat {
+    Tiff,
+    Nrrd,
+    Raw,
+}
+
+impl VolumeFormat {
+    /// Format by the extension of `path`, checked before sampling.
+    pub fn from_path(path: &Path) -> Result<Self, String> {
+        match extension(path).as_str() {
+            "tif" | "tiff" => Ok(Self::Tiff),
+            "nrrd" => Ok(Self::Nrrd),
+            "raw" => Ok(Self::Raw),
+            _ => Err(format!(
+                "unknown volume format of {}, expected .tif, .nrrd or .raw",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// Format of a written mesh.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MeshFormat {
+    Obj,
+    Ply,
+}
+
+impl MeshFormat {
+    /// Format by the extension of `path`, checked before sampling.
+    pub fn from_path(path: &Path) -> Result<Self, String> {
+        match extension(path).as_str() {
+            "obj" => Ok(Self::Obj),
+            "ply" => Ok(Self::Ply),
+            _ => Err(format!(
+                "unknown mesh format of {}, expected .obj or .ply",
+                path.display()
+            )),
+        }
+    }
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidInput, msg)
+}
+
+fn create(path: &Path) -> Result<BufWriter<File>, String> {
+    File::create(path)
+        .map(BufWriter::new)
+        .map_err(|e| format!("failed to create {}: {e}", path.display()))
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+/// Write a volume to `path` as TIFF, NRRD or raw samples.
+pub fn write_volume(
+    path: &Path,
+    format: VolumeFormat,
+    volume: &Volume,
+    grid: &Grid,
+    dtype: DType,
+) -> Result<(), String> {
+    let mut file = create(path)?;
+    let written = match format {
+        VolumeFormat::Tiff => write_tiff(&mut file, volume, dtype),
+        VolumeFormat::Nrrd => write_nrrd(&mut file, volume, grid, dtype),
+        VolumeFormat::Raw => write_raw(&mut file, volume, dtype),
+    };
+    written
+        .and_then(|()| file.flush())
+        .map_err(|e| format!("failed to write {}: {e}", path.display()))
+}
+
+fn write_raw(w: &mut impl Write, volume: &Volume, dtype: DType) -> io::Result<()> {
+    let slice = (volume.shape[1] * volume.shape[2] * volume.channels).max(1);
+    let mut buf = Vec::new();
+    for chunk in volume.data.chunks(slice) {
+        buf.clear();
+        dtype.encode(chunk, &mut buf);
+        w.write_all(&buf)?;
+    }
+    Ok(())
+}
+
+/// NRRD lists the axes fastest first, so the channels, then z, y and x.
+fn write_nrrd(w: &mut impl Write, volume: &Volume, grid: &Grid, dtype: DType) -> io::Result<()> {
+    let [nx, ny, nz] = volume.shape;
+    let (o, s) = (grid.min(), grid.stride());
+    let (c, dimension, sizes, kinds) = if volume.channels > 1 {
+        (format!("{} ", volume.channels), 4, "none ", "vector ")
+    } else {
+        (String::new(), 3, "", "")
+    };
+
+    writeln!(w, "NRRD0004")?;
+    writeln!(w, "type: {}", dtype.nrrd())?;
+    writeln!(w, "dimension: {dimension}")?;
+    writeln!(w, "space dimension: 3")?;
+    writeln!(w, "sizes: {c}{nz} {ny} {nx}")?;
+    writeln!(
+        w,
+        "space directions: {sizes}(0,0,{}) (0,{},0) ({},0,0)",
+        s.z, s.y, s.x
+    )?;
+    writeln!(w, "kinds: {kinds}domain domain domain")?;
+    writeln!(w, "endian: little")?;
+    writeln!(w, "encoding: raw")?;
+    writeln!(w, "space origin: ({},{},{})", o.x, o.y, o.z)?;
+    writeln!(w)?;
+    write_raw(w, volume, dtype)
+}
+
+/// Value of a TIFF tag.
+#[derive(Clone, Debug)]
+enum Field {
+    Short(Vec<u16>),
+    Long(u32),
+}
+
+impl Field {
+    fn encode(&self) -> (u16, u32, Vec<u8>) {
+        match self {
+            Field::Short(v) => {
+                let bytes = v.iter().flat_map(|x| x.to_le_bytes()).collect();
+                (3, u32::try_from(v.len()).unwrap_or_default(), bytes)
+            }
+            Field::Long(x) => (4, 1, x.to_le_bytes().to_vec()),
+        }
+    }
+}
+
+/// Baseline little-endian TIFF with a page per x, each `shape[1]` rows of
+/// `shape[2]` pixels holding all the channels, as `tifffile` writes an array
+/// sampled by `RangeSampler`.
+fn write_tiff(w: &mut impl Write, volume: &Volume, dtype: DType) -> io::Result<()> {
+    let [pages, height, width] = volume.shape;
+    if pages == 0 || height == 0 || width == 0 {
+        return Err(invalid("cannot write an empty volume as TIFF"));
+    }
+
+    let spp = u16::try_from(volume.channels).map_err(|_| invalid("too many channels for TIFF"))?;
+    let (photometric, extras) = if spp >= 3 { (2, spp - 3) } else { (1, spp - 1) };
+    let sample_format = match dtype {
+        DType::F32 => 3,
+        DType::U16 | DType::U8 => 1,
+    };
+
+    let long = |n: usize| {
+        u32::try_from(n).map_err(|_| invalid("volume too large for TIFF, use NRRD or raw"))
+    };
+    let shorts = |v: u16, n: u16| Field::Short(vec![v; usize::from(n)]);
+    let pixels = height * width * volume.channels;
+    let page_len = pixels * usize::from(dtype.bits() / 8);
+    let page_len = page_len + page_len % 2;
+
+    // each page is its samples followed by its directory, values that do not
+    // fit in an entry are stored after the entries
+    let mut offset = 8;
+    let mut ifds = Vec::with_capacity(pages);
+    for page in 0..pages {
+        let data = offset;
+        let ifd = data + page_len;
+
+        let mut fields = vec![
+            (256, Field::Long(long(width)?)),
+            (257, Field::Long(long(height)?)),
+            (258, shorts(dtype.bits(), spp)),
+            (259, Field::Short(vec![1])),
+            (262, Field::Short(vec![photometric])),
+            (273, Field::Long(long(data)?)),
+            (277, Field::Short(vec![spp])),
+            (278, Field::Long(long(height)?)),
+            (279, Field::Long(long(page_len)?)),
+            (284, Field::Short(vec![1])),
+        ];
+        if extras > 0 {
+            fields.push((338, shorts(0, extras)));
+        }
+        fields.push((339, shorts(sample_format, spp)));
+
+        let overflow_at = ifd + 2 + 12 * fields.len() + 4;
+        let mut table = Vec::new();
+        let mut overflow = Vec::new();
+        table.extend(
+            u16::try_from(fields.len())
+                .unwrap_or_default()
+                .to_le_bytes(),
+        );
+        for (tag, field) in fields {
+            let (kind, count, mut bytes) = field.encode();
+            table.extend(u16::to_le_bytes(tag));
+            table.extend(kind.to_le_bytes());
+            table.extend(count.to_le_bytes());
+            if bytes.len() <= 4 {
+                bytes.resize(4, 0);
+                table.extend(bytes);
+            } else {
+                table.extend(long(overflow_at + overflow.len())?.to_le_bytes());
+                overflow.extend(bytes);
+            }
+        }
+
+        offset = overflow_at + overflow.len() + overflow.len() % 2;
+        let next = if page + 1 == pages {
+            0
+        } else {
+            offset + page_len
+        };
+        table.extend(long(next)?.to_le_bytes());
+        table.extend(overflow);
+        table.resize(offset - ifd, 0);
+        ifds.push((ifd, table));
+    }
+
+    let first = ifds.first().map_or(0, |(ifd, _)| *ifd);
+    w.write_all(b"II")?;
+    w.write_all(&42_u16.to_le_bytes())?;
+    w.write_all(&long(first)?.to_le_bytes())?;
+
+    let mut buf = Vec::with_capacity(page_len);
+    for ((_, table), values) in ifds.iter().zip(volume.data.chunks(pixels)) {
+        buf.clear();
+        dtype.encode(values, &mut buf);
+        buf.resize(page_len, 0);
+        w.write_all(&buf)?;
+        w.write_all(table)?;
+    }
+    Ok(())
+}
+
+/// Write a mesh to `path` as OBJ or binary PLY.
+pub fn write_mesh(path: &Path, format: MeshFormat, mesh: &Mesh) -> Result<(), String> {
+    let mut file = create(path)?;
+    let written = match format {
+        MeshFormat::Obj => write_obj(&mut file, mesh),
+        MeshFormat::Ply => write_ply(&mut file, mesh),
+    };
+    written
+        .and_then(|()| file.flush())
+        .map_err(|e| format!("failed to write {}: {e}", path.display()))
+}
+
+fn write_obj(w: &mut impl Write, mesh: &Mesh) -> io::Result<()> {
+    for v in &mesh.vertices {
+        writeln!(w, "v {} {} {}", v.x, v.y, v.z)?;
+    }
+
+    for [a, b, c] in &mesh.triangles {
+        writeln!(w, "f {} {} {}", a + 1, b + 1, c + 1)?;
+    }
+    Ok(())
+}
+
+fn write_ply(w: &mut impl Write, mesh: &Mesh) -> io::Result<()> {
+    if u32::try_from(mesh.vertices.len()).is_err() {
+        return Err(invalid("too many vertices for PLY"));
+    }
+
+    writeln!(w, "ply")?;
+    writeln!(w, "format binary_little_endian 1.0")?;
+    writeln!(w, "element vertex {}", mesh.vertices.len())?;
+    // vertices are written at the precision of the geometry
+    let float = if size_of::<Float>() == 8 {
+        "double"
+    } else {
+        "float"
+    };
+    for axis in ["x", "y", "z"] {
+        writeln!(w, "property {float} {axis}")?;
+    }
+    writeln!(w, "element face {}", mesh.triangles.len())?;
+    writeln!(w, "property list uchar uint vertex_indices")?;
+    writeln!(w, "end_header")?;
+
+    for v in &mesh.vertices {
+        for x in [v.x, v.y, v.z] {
+            w.write_all(&x.to_le_bytes())?;
+        }
+    }
+
+    for triangle in &mesh.triangles {
+        w.write_all(&[3])?;
+        for &i in triangle {
+            let i = u32::try_from(i).unwrap_or_default();
+            w.write_all(&i.to_le_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sdflit::Vec3f;
+
+    use super::*;
+
+    /// A volume of `shape` and `channels`, each value its index in the data.
+    #[allow(clippy::cast_precision_loss)]
+    fn volume(shape: [usize; 3], channels: usize) -> Volume {
+        let n = shape.iter().product::<usize>() * channels;
+        Volume {
+            shape,
+            channels,
+            data: (0..n).map(|i| i as f32).collect(),
+        }
+    }
+
+    fn grid() -> Grid {
+        let min = Vec3f::new(1.0, 2.0, 3.0);
+        let stride = Vec3f::new(0.5, 1.0, 2.0);
+        Grid::new(min, min + Vec3f::new(1.0, 3.0, 8.0), stride).unwrap()
+    }
+
+    fn floats(bytes: &[u8]) -> Vec<f32> {
+        bytes
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes(b.try_into().unwrap()))
+            .collect()
+    }
+
+    /// Split a written file after the line `end`.
+    fn split<'a>(bytes: &'a [u8], end: &str) -> (String, &'a [u8]) {
+        let at = bytes
+            .windows(end.len())
+            .position(|w| w == end.as_bytes())
+            .unwrap()
+            + end.len();
+        let (header, body) = bytes.split_at(at);
+        (String::from_utf8(header.to_vec()).unwrap(), body)
+    }
+
+    #[test]
+    fn test_formats() {
+        let volume = |p: &str| VolumeFormat::from_path(Path::new(p));
+        assert_eq!(volume("a/b.TIF"), Ok(VolumeFormat::Tiff));
+        assert_eq!(volume("b.tiff"), Ok(VolumeFormat::Tiff));
+        assert_eq!(volume("b.nrrd"), Ok(VolumeFormat::Nrrd));
+        assert_eq!(volume("b.raw"), Ok(VolumeFormat::Raw));
+        assert!(volume("b.png").is_err());
+        assert!(volume("b").is_err());
+
+        let mesh = |p: &str| MeshFormat::from_path(Path::new(p));
+        assert_eq!(mesh("b.obj"), Ok(MeshFormat::Obj));
+        assert_eq!(mesh("b.PLY"), Ok(MeshFormat::Ply));
+        assert!(mesh("b.stl").is_err());
+    }
+
+    #[test]
+    fn test_encode() {
+        let mut out = Vec::new();
+        DType::U8.encode(&[0.0, 0.5, 1.0, 2.0, -1.0], &mut out);
+        assert_eq!(out, [0, 128, 255, 255, 0]);
+
+        out.clear();
+        DType::U16.encode(&[1.0, 0.0], &mut out);
+        assert_eq!(out, [255, 255, 0, 0]);
+    }
+
+    #[test]
+    fn test_nrrd() {
+        let volume = volume([2, 3, 4], 1);
+        let mut out = Vec::new();
+        write_nrrd(&mut out, &volume, &grid(), DType::F32).unwrap();
+
+        let (header, body) = split(&out, "\n\n");
+        let expected = "NRRD0004\n\
+            type: float\n\
+            dimension: 3\n\
+            space dimension: 3\n\
+            sizes: 4 3 2\n\
+            space directions: (0,0,2) (0,1,0) (0.5,0,0)\n\
+            kinds: domain domain domain\n\
+            endian: little\n\
+            encoding: raw\n\
+            space origin: (1,2,3)\n\n";
+        assert_eq!(header, expected);
+        // z is the fastest axis and x the slowest, as `RangeSampler` samples
+        assert_eq!(floats(body), volume.data);
+
+        let volume = self::volume([2, 3, 4], 2);
+        let mut out = Vec::new();
+        write_nrrd(&mut out, &volume, &grid(), DType::U8).unwrap();
+        let (header, body) = split(&out, "\n\n");
+        assert!(header.contains("type: uint8\n"));
+        assert!(header.contains("dimension: 4\n"));
+        assert!(header.contains("sizes: 2 4 3 2\n"));
+        assert!(header.contains("space directions: none (0,0,2)"));
+        assert!(header.contains("kinds: vector domain domain domain\n"));
+        assert_eq!(body.len(), volume.data.len());
+    }
+
+    #[test]
+    fn test_raw() {
+        let volume = volume([3, 2, 5], 3);
+        let mut out = Vec::new();
+        write_raw(&mut out, &volume, DType::F32).unwrap();
+        assert_eq!(floats(&out), volume.data);
+    }
+
+    #[test]
+    fn test_tiff() {
+        let volume = volume([2, 3, 4], 1);
+        let mut out = Vec::new();
+        write_tiff(&mut out, &volume, DType::F32).unwrap();
+
+        let u16_at = |i: usize| u16::from_le_bytes(out.get(i..i + 2).unwrap().try_into().unwrap());
+        let u32_at = |i: usize| u32::from_le_bytes(out.get(i..i + 4).unwrap().try_into().unwrap());
+        assert_eq!(out.get(..4), Some(&b"II\x2a\x00"[..]));
+
+        // a page per x, each with the samples of its rows
+        let mut pages = Vec::new();
+        let mut ifd = u32_at(4) as usize;
+        while ifd != 0 {
+            let count = usize::from(u16_at(ifd));
+            let tag = |tag: u16| {
+                (0..count)
+                    .map(|k| ifd + 2 + 12 * k)
+                    .find(|&e| u16_at(e) == tag)
+                    .map(|e| u32_at(e + 8))
+                    .unwrap()
+            };
+            let (width, height) = (tag(256), tag(257));
+            let (offset, len) = (tag(273) as usize, tag(279) as usize);
+            pages.push((
+                width,
+                height,
+                floats(out.get(offset..offset + len).unwrap()),
+            ));
+            ifd = u32_at(ifd + 2 + 12 * count) as usize;
+        }
+
+        assert_eq!(pages.len(), 2);
+        for ((width, height, data), expected) in pages.into_iter().zip(volume.data.chunks(12)) {
+            assert_eq!((width, height), (4, 3));
+            assert_eq!(data, expected);
+        }
+
+        assert!(write_tiff(&mut Vec::new(), &self::volume([0, 3, 4], 1), DType::U8).is_err());
+    }
+
+    fn mesh() -> Mesh {
+        let v = |x, y, z| Vec3f::new(x, y, z);
+        Mesh {
+            vertices: vec![
+                v(0.0, 0.0, 0.0),
+                v(1.0, 0.0, 0.0),
+                v(0.0, 1.0, 0.0),
+                v(0.0, 0.0, 1.0),
+            ],
+            triangles: vec![[0, 1, 2], [0, 3, 1]],
+        }
+    }
+
+    #[test]
+    fn test_obj() {
+        let mut out = Vec::new();
+        write_obj(&mut out, &mesh()).unwrap();
+        let obj = String::from_utf8(out).unwrap();
+        let count = |prefix: &str| obj.lines().filter(|l| l.starts_with(prefix)).count();
+        assert_eq!((count("v "), count("f ")), (4, 2));
+        // indices start at one
+        assert!(obj.lines().any(|l| l == "f 1 4 2"));
+    }
+
+    #[test]
+    fn test_ply() {
+        let mut out = Vec::new();
+        write_ply(&mut out, &mesh()).unwrap();
+        let (header, body) = split(&out, "end_header\n");
+        assert!(header.starts_with("ply\nformat binary_little_endian 1.0\n"));
+        assert!(header.contains("element vertex 4\n"));
+        assert!(header.contains("element face 2\n"));
+        assert_eq!(body.len(), 4 * 3 * size_of::<Float>() + 2 * (1 + 3 * 4));
+
+        let faces = body.get(4 * 3 * size_of::<Float>()..).unwrap();
+        assert_eq!(faces.first(), Some(&3));
+        let second = faces.get(13..26).unwrap();
+        assert_eq!(second.first(), Some(&3));
+        let indices = second.get(1..).unwrap().chunks_exact(4);
+        let indices = indices.map(|b| u32::from_le_bytes(b.try_into().unwrap()));
+        assert_eq!(indices.collect::<Vec<_>>(), [0, 3, 1]);
+    }
+}
@@ -0,0 +1,30 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::cell::RefCell;
+
+use pyo3::prelude::*;
+
+thread_local! {
+    /// The error of the first Python callback failed on this thread, pending
+    /// until the query calling it returns to Python.
+    static PENDING: RefCell<Option<PyErr>> = const { RefCell::new(None) };
+}
+
+/// Call the Python callback `f` from within a query, which cannot return its
+/// error. The error is kept for `check` to raise, and later callbacks are
+/// skipped until then. Returns `None` when the callback fails or is skipped.
+pub fn run<T>(f: impl FnOnce() -> PyResult<T>) -> Option<T> {
+    if PENDING.with_borrow(Option::is_some) {
+        return None;
+    }
+
+    f().map_err(|err| PENDING.set(Some(err))).ok()
+}
+
+/// Raise the error of the first callback failed during a query, to be called
+/// by every Python method running a query.
+pub fn check() -> PyResult<()> {
+    PENDING.take().map_or(Ok(()), Err)
+}
@@ -4,29 +4,41 @@
 
 use std::sync::Arc;
 
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
-use crate::sdf::{DynSDF, SDF};
-use crate::vec3::{self, Vec3f};
+use crate::description::SDFDesc;
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+use crate::sdf::{hit_batch_from_distance, DynSDF, SDFHitInfo, SDF};
+use crate::vec3::{self, Float, Vec3f};
 
-#[pyfunction]
+/// Union of `a` and `b`.
+#[cfg_attr(feature = "python", pyfunction)]
+#[must_use]
 pub fn merge(a: DynSDF, b: DynSDF) -> DynSDF {
     let union: Arc<dyn SDF> = Arc::new(Union::new(a, b));
     DynSDF::from(union)
 }
 
-#[pyfunction]
+/// Intersection of `a` and `b`.
+#[cfg_attr(feature = "python", pyfunction)]
+#[must_use]
 pub fn intersect(a: DynSDF, b: DynSDF) -> DynSDF {
     let intersection: Arc<dyn SDF> = Arc::new(Intersection::new(a, b));
     DynSDF::from(intersection)
 }
 
-#[pyfunction]
+/// `a` with `b` carved out of it.
+#[cfg_attr(feature = "python", pyfunction)]
+#[must_use]
 pub fn subtract(a: DynSDF, b: DynSDF) -> DynSDF {
     let difference: Arc<dyn SDF> = Arc::new(Difference::new(a, b));
     DynSDF::from(difference)
 }
 
+/// Union of two SDFs, the minimum of their distances.
+#[allow(missing_debug_implementations)]
 pub struct Union {
     a: DynSDF,
     b: DynSDF,
@@ -34,6 +46,8 @@ pub struct Union {
 }
 
 impl Union {
+    /// Combine `a` and `b`.
+    #[must_use]
     pub fn new(a: DynSDF, b: DynSDF) -> Union {
         let (min_a, max_a) = a.bounding_box();
         let (min_b, max_b) = b.bounding_box();
@@ -43,8 +57,21 @@ impl Union {
 }
 
 impl SDF for Union {
-    fn distance(&self, p: Vec3f) -> f32 {
-        f32::min(self.a.distance(p), self.b.distance(p))
+    fn distance(&self, p: Vec3f) -> Float {
+        Float::min(self.a.distance(p), self.b.distance(p))
+    }
+
+    fn distance_batch(&self, ps: &[Vec3f], out: &mut [Float]) {
+        let mut other = vec![0.0; ps.len()];
+        self.a.distance_batch(ps, out);
+        self.b.distance_batch(ps, &mut other);
+        for (d, e) in out.iter_mut().zip(other) {
+            *d = Float::min(*d, e);
+        }
+    }
+
+    fn hit_batch(&self, ps: &[Vec3f], out: &mut [Option<SDFHitInfo>]) {
+        hit_batch_from_distance(self, ps, out);
     }
 
     fn bounding_box(&self) -> (Vec3f, Vec3f) {
@@ -54,8 +81,22 @@ impl SDF for Union {
     fn inside(&self, p: Vec3f) -> bool {
         self.inside_bounding_box(p) && (self.a.inside(p) || self.b.inside(p))
     }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::function(py, "merge", (self.a.clone(), self.b.clone()))
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        Ok(SDFDesc::Union {
+            a: Box::new(self.a.describe()?),
+            b: Box::new(self.b.describe()?),
+        })
+    }
 }
 
+/// Intersection of two SDFs, the maximum of their distances.
+#[allow(missing_debug_implementations)]
 pub struct Intersection {
     a: DynSDF,
     b: DynSDF,
@@ -63,6 +104,8 @@ pub struct Intersection {
 }
 
 impl Intersection {
+    /// Combine `a` and `b`.
+    #[must_use]
     pub fn new(a: DynSDF, b: DynSDF) -> Intersection {
         let (min_a, max_a) = a.bounding_box();
         let (min_b, max_b) = b.bounding_box();
@@ -72,8 +115,21 @@ impl Intersection {
 }
 
 impl SDF for Intersection {
-    fn distance(&self, p: Vec3f) -> f32 {
-        f32::max(self.a.distance(p), self.b.distance(p))
+    fn distance(&self, p: Vec3f) -> Float {
+        Float::max(self.a.distance(p), self.b.distance(p))
+    }
+
+    fn distance_batch(&self, ps: &[Vec3f], out: &mut [Float]) {
+        let mut other = vec![0.0; ps.len()];
+        self.a.distance_batch(ps, out);
+        self.b.distance_batch(ps, &mut other);
+        for (d, e) in out.iter_mut().zip(other) {
+            *d = Float::max(*d, e);
+        }
+    }
+
+    fn hit_batch(&self, ps: &[Vec3f], out: &mut [Option<SDFHitInfo>]) {
+        hit_batch_from_distance(self, ps, out);
     }
 
     fn bounding_box(&self) -> (Vec3f, Vec3f) {
@@ -83,8 +139,22 @@ impl SDF for Intersection {
     fn inside(&self, p: Vec3f) -> bool {
         self.inside_bounding_box(p) && self.a.inside(p) && self.b.inside(p)
     }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::function(py, "intersect", (self.a.clone(), self.b.clone()))
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        Ok(SDFDesc::Intersection {
+            a: Box::new(self.a.describe()?),
+            b: Box::new(self.b.describe()?),
+        })
+    }
 }
 
+/// First SDF minus the second, bounded by the first.
+#[allow(missing_debug_implementations)]
 pub struct Difference {
     a: DynSDF,
     b: DynSDF,
@@ -92,6 +162,8 @@ pub struct Difference {
 }
 
 impl Difference {
+    /// Combine `a` and `b`.
+    #[must_use]
     pub fn new(a: DynSDF, b: DynSDF) -> Difference {
         let bounding_box = a.bounding_box();
         Self { a, b, bounding_box }
@@ -99,8 +171,21 @@ impl Difference {
 }
 
 impl SDF for Difference {
-    fn distance(&self, p: Vec3f) -> f32 {
-        f32::max(self.a.distance(p), -self.b.distance(p))
+    fn distance(&self, p: Vec3f) -> Float {
+        Float::max(self.a.distance(p), -self.b.distance(p))
+    }
+
+    fn distance_batch(&self, ps: &[Vec3f], out: &mut [Float]) {
+        let mut other = vec![0.0; ps.len()];
+        self.a.distance_batch(ps, out);
+        self.b.distance_batch(ps, &mut other);
+        for (d, e) in out.iter_mut().zip(other) {
+            *d = Float::max(*d, -e);
+        }
+    }
+
+    fn hit_batch(&self, ps: &[Vec3f], out: &mut [Option<SDFHitInfo>]) {
+        hit_batch_from_distance(self, ps, out);
     }
 
     fn bounding_box(&self) -> (Vec3f, Vec3f) {
@@ -110,4 +195,16 @@ impl SDF for Difference {
     fn inside(&self, p: Vec3f) -> bool {
         self.inside_bounding_box(p) && self.a.inside(p) && !self.b.inside(p)
     }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::function(py, "subtract", (self.a.clone(), self.b.clone()))
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        Ok(SDFDesc::Difference {
+            a: Box::new(self.a.describe()?),
+            b: Box::new(self.b.describe()?),
+        })
+    }
 }
@@ -0,0 +1,570 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs;
+use std::path::Path;
+#[cfg(feature = "python")]
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[cfg(feature = "python")]
+use pyo3::{exceptions::PyValueError, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::accelerator::{self, BVHMethod};
+use crate::composition::{Difference, Intersection, Union};
+use crate::material::{
+    Axis, ChannelsMaterial, CheckerMaterial, ColoredMaterial, ColormapMaterial, DynMaterial,
+    FalloffMaterial, FalloffOrigin, FalloffProfile, LinearGradientMaterial, Material,
+    NoiseMaterial, OpacityMaterial, StripesMaterial, WorldAxis,
+};
+use crate::modifier::{
+    Bend, Displacement, DisplacementField, Elongation, Mirror, RadialRepetition, Repetition, Round,
+    Shell, Twist, Warp,
+};
+use crate::noise::{Noise, NoiseKind};
+use crate::object::{Object, SDFObject};
+use crate::primitive::{
+    FrustumCone, Metaballs, RoundCone, Sphere, Spheres, SplineKind, SplineTube, Tube,
+};
+#[cfg(feature = "python")]
+use crate::scene::DynScene;
+use crate::scene::{Blend, ElasticScene, ObjectsScene, Scene};
+use crate::sdf::{DynSDF, SDF};
+use crate::vec3::{self, Float, Vec3f};
+
+/// Load a scene saved by `Scene.save`.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "load_scene")]
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn py_load_scene(path: PathBuf) -> PyResult<DynScene> {
+    load_scene(&path)
+        .map(DynScene::from)
+        .map_err(PyValueError::new_err)
+}
+
+/// Version of the scene file format, files of a newer version are rejected.
+pub const VERSION: u32 = 1;
+
+/**
+ * Scene file
+ *
+ * A scene described as plain data, saved to and loaded from JSON or TOML.
+ * Every node is a table tagged by its `type`, unknown keys are ignored so
+ * that older readers accept files with additional settings.
+ */
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SceneFile {
+    /// Format version the file was written with.
+    pub version: u32,
+    /// The root scene.
+    pub scene: SceneDesc,
+}
+
+#[derive(Deserialize)]
+struct Header {
+    version: u32,
+}
+
+/// An SDF, built from its constructor arguments.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[allow(missing_docs)] // fields are named after the constructor arguments
+pub enum SDFDesc {
+    /// `Sphere`.
+    Sphere { center: Vec3f, radius: Float },
+    /// `RoundCone`.
+    RoundCone {
+        a: Vec3f,
+        b: Vec3f,
+        ra: Float,
+        rb: Float,
+    },
+    /// `FrustumCone`.
+    FrustumCone {
+        a: Vec3f,
+        b: Vec3f,
+        ra: Float,
+        rb: Float,
+    },
+    /// `Spheres`.
+    Spheres {
+        centers: Vec<Vec3f>,
+        radii: Vec<Float>,
+    },
+    /// `Spheres::metaballs`, blended by `k`.
+    Metaballs {
+        centers: Vec<Vec3f>,
+        radii: Vec<Float>,
+        k: Float,
+    },
+    /// `Tube` through `nodes`.
+    Tube {
+        nodes: Vec<Vec3f>,
+        radii: Vec<Float>,
+    },
+    /// `SplineTube` through `points`.
+    SplineTube {
+        points: Vec<Vec3f>,
+        radii: Vec<Float>,
+        kind: SplineKind,
+    },
+    /// Union of `a` and `b`.
+    Union { a: Box<SDFDesc>, b: Box<SDFDesc> },
+    /// Intersection of `a` and `b`.
+    Intersection { a: Box<SDFDesc>, b: Box<SDFDesc> },
+    /// `a` minus `b`.
+    Difference { a: Box<SDFDesc>, b: Box<SDFDesc> },
+    /// `Round`.
+    Round { sdf: Box<SDFDesc>, r: Float },
+    /// `Shell`.
+    Shell { sdf: Box<SDFDesc>, thickness: Float },
+    /// `Elongation`.
+    Elongate { sdf: Box<SDFDesc>, h: Vec3f },
+    /// `Repetition`.
+    Repeat {
+        sdf: Box<SDFDesc>,
+        spacing: Vec3f,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        counts: Option<vec3::Vec3<u32>>,
+    },
+    /// `RadialRepetition`.
+    RepeatRadial {
+        sdf: Box<SDFDesc>,
+        n: u32,
+        axis: Vec3f,
+        center: Vec3f,
+    },
+    /// `Mirror`.
+    Mirror {
+        sdf: Box<SDFDesc>,
+        normal: Vec3f,
+        origin: Vec3f,
+    },
+    /// `Twist`.
+    Twist {
+        sdf: Box<SDFDesc>,
+        k: Float,
+        axis: Vec3f,
+        origin: Vec3f,
+    },
+    /// `Bend`.
+    Bend {
+        sdf: Box<SDFDesc>,
+        k: Float,
+        axis: Vec3f,
+        normal: Vec3f,
+        origin: Vec3f,
+    },
+    /// `Warp` by a sampled displacement field.
+    Warp {
+        sdf: Box<SDFDesc>,
+        shape: (usize, usize, usize),
+        field: Vec<Vec3f>, // in C order of (x, y, z)
+        min: Vec3f,
+        max: Vec3f,
+    },
+    /// `Displacement` by noise.
+    Displace {
+        sdf: Box<SDFDesc>,
+        amplitude: Float,
+        frequency: Float,
+        octaves: u32,
+        seed: u64,
+        kind: NoiseKind,
+    },
+}
+
+/// A material, built from its constructor arguments.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[allow(missing_docs)] // fields are named after the constructor arguments
+pub enum MaterialDesc {
+    /// `ColoredMaterial`.
+    Colored { color: Vec3f },
+    /// `LinearGradientMaterial`.
+    LinearGradient { c1: Vec3f, c2: Vec3f, axis: Axis },
+    /// `NoiseMaterial`.
+    Noise {
+        c1: Vec3f,
+        c2: Vec3f,
+        frequency: Float,
+        octaves: u32,
+        seed: u64,
+        kind: NoiseKind,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        threshold: Option<Float>,
+    },
+    /// `CheckerMaterial`.
+    Checker { c1: Vec3f, c2: Vec3f, size: Vec3f },
+    /// `StripesMaterial`.
+    Stripes {
+        c1: Vec3f,
+        c2: Vec3f,
+        width: Float,
+        axis: WorldAxis,
+    },
+    /// `ColormapMaterial`.
+    Colormap {
+        lut: Vec<Vec3f>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        stops: Option<Vec<Float>>,
+        axis: Axis,
+        vmin: Float,
+        vmax: Float,
+    },
+    /// `FalloffMaterial`.
+    Falloff {
+        color: Vec3f,
+        background: Vec3f,
+        scale: Float,
+        profile: FalloffProfile,
+        origin: FalloffOrigin,
+    },
+    /// `ChannelsMaterial`.
+    Channels {
+        values: Vec<f32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        intensity: Option<Box<MaterialDesc>>,
+    },
+    /// `OpacityMaterial`.
+    Opacity {
+        material: Box<MaterialDesc>,
+        opacity: f32,
+        intensity: f32,
+    },
+}
+
+/// An object, built from its constructor arguments.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[allow(missing_docs)] // fields are named after the constructor arguments
+pub enum ObjectDesc {
+    /// `SDFObject`.
+    Sdf {
+        sdf: SDFDesc,
+        material: MaterialDesc,
+    },
+}
+
+/// An object of an `ObjectsScene` with its priority.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ObjectEntry {
+    /// The object.
+    pub object: ObjectDesc,
+    /// Objects of higher priority are drawn over the others.
+    #[serde(default)]
+    pub priority: i32,
+}
+
+/// A scene, built from its constructor arguments.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[allow(missing_docs)] // fields are named after the constructor arguments
+pub enum SceneDesc {
+    /// `ObjectsScene`.
+    Objects {
+        channels: usize,
+        objects: Vec<ObjectEntry>,
+        background: Vec<f32>,
+        blend: Blend,
+        #[serde(default)]
+        bvh: bool,
+        #[serde(default)]
+        bvh_method: BVHMethod,
+        #[serde(default = "default_leaf_size")]
+        bvh_leaf_size: usize,
+    },
+    /// `ElasticScene` deforming `scene`.
+    Elastic {
+        scene: Box<SceneDesc>,
+        spacing: Vec3f,
+        sigma: Float,
+        seed: u64,
+    },
+}
+
+fn default_leaf_size() -> usize {
+    1
+}
+
+impl SDFDesc {
+    /// Construct the SDF described.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the arguments are rejected by a constructor.
+    pub fn build(self) -> Result<DynSDF, &'static str> {
+        let sdf: Arc<dyn SDF> = match self {
+            SDFDesc::Sphere { center, radius } => Arc::new(Sphere::new(center, radius)),
+            SDFDesc::RoundCone { a, b, ra, rb } => Arc::new(RoundCone::new(a, b, ra, rb)),
+            SDFDesc::FrustumCone { a, b, ra, rb } => Arc::new(FrustumCone::new(a, b, ra, rb)),
+            SDFDesc::Spheres { centers, radii } => Arc::new(Spheres::new(&centers, &radii)?),
+            SDFDesc::Metaballs { centers, radii, k } => {
+                Arc::new(Metaballs::new(&centers, &radii, k)?)
+            }
+            SDFDesc::Tube { nodes, radii } => Arc::new(Tube::new(&nodes, &radii)?),
+            SDFDesc::SplineTube {
+                points,
+                radii,
+                kind,
+            } => Arc::new(match kind {
+                SplineKind::CatmullRom => SplineTube::catmull_rom(&points, &radii)?,
+                SplineKind::Bezier => SplineTube::bezier(&points, &radii)?,
+            }),
+            SDFDesc::Union { a, b } => Arc::new(Union::new(a.build()?, b.build()?)),
+            SDFDesc::Intersection { a, b } => Arc::new(Intersection::new(a.build()?, b.build()?)),
+            SDFDesc::Difference { a, b } => Arc::new(Difference::new(a.build()?, b.build()?)),
+            SDFDesc::Round { sdf, r } => Arc::new(Round::new(sdf.build()?, r)?),
+            SDFDesc::Shell { sdf, thickness } => Arc::new(Shell::new(sdf.build()?, thickness)?),
+            SDFDesc::Elongate { sdf, h } => Arc::new(Elongation::new(sdf.build()?, h)?),
+            SDFDesc::Repeat {
+                sdf,
+                spacing,
+                counts,
+            } => Arc::new(Repetition::new(sdf.build()?, spacing, counts)?),
+            SDFDesc::RepeatRadial {
+                sdf,
+                n,
+                axis,
+                center,
+            } => Arc::new(RadialRepetition::new(sdf.build()?, n, axis, center)?),
+            SDFDesc::Mirror {
+                sdf,
+                normal,
+                origin,
+            } => Arc::new(Mirror::new(sdf.build()?, normal, origin)?),
+            SDFDesc::Twist {
+                sdf,
+                k,
+                axis,
+                origin,
+            } => Arc::new(Twist::new(sdf.build()?, k, axis, origin)?),
+            SDFDesc::Bend {
+                sdf,
+                k,
+                axis,
+                normal,
+                origin,
+            } => Arc::new(Bend::new(sdf.build()?, k, axis, normal, origin)?),
+            SDFDesc::Warp {
+                sdf,
+                shape,
+                field,
+                min,
+                max,
+            } => {
+                let field = DisplacementField::new(field, shape, min, max)?;
+                Arc::new(Warp::new(sdf.build()?, field))
+            }
+            SDFDesc::Displace {
+                sdf,
+                amplitude,
+                frequency,
+                octaves,
+                seed,
+                kind,
+            } => {
+                let noise = Noise::new(kind, seed);
+                Arc::new(Displacement::new(
+                    sdf.build()?,
+                    noise,
+                    amplitude,
+                    frequency,
+                    octaves,
+                )?)
+            }
+        };
+        Ok(DynSDF::from(sdf))
+    }
+}
+
+impl MaterialDesc {
+    /// Construct the material described.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the arguments are rejected by a constructor.
+    pub fn build(self) -> Result<Arc<dyn Material>, &'static str> {
+        Ok(match self {
+            MaterialDesc::Colored { color } => Arc::new(ColoredMaterial::new(color)),
+            MaterialDesc::LinearGradient { c1, c2, axis } => {
+                Arc::new(LinearGradientMaterial::new(c1, c2, axis))
+            }
+            MaterialDesc::Noise {
+                c1,
+                c2,
+                frequency,
+                octaves,
+                seed,
+                kind,
+                threshold,
+            } => {
+                let noise = Noise::new(kind, seed);
+                Arc::new(NoiseMaterial::new(
+                    c1, c2, noise, frequency, octaves, threshold,
+                )?)
+            }
+            MaterialDesc::Checker { c1, c2, size } => Arc::new(CheckerMaterial::new(c1, c2, size)?),
+            MaterialDesc::Stripes {
+                c1,
+                c2,
+                width,
+                axis,
+            } => Arc::new(StripesMaterial::new(c1, c2, width, axis)?),
+            MaterialDesc::Colormap {
+                lut,
+                stops,
+                axis,
+                vmin,
+                vmax,
+            } => Arc::new(ColormapMaterial::new(lut, stops, axis, vmin, vmax)?),
+            MaterialDesc::Falloff {
+                color,
+                background,
+                scale,
+                profile,
+                origin,
+            } => Arc::new(FalloffMaterial::new(
+                color, background, scale, profile, origin,
+            )?),
+            MaterialDesc::Channels { values, intensity } => {
+                let intensity = intensity.map(|m| m.build()).transpose()?;
+                Arc::new(ChannelsMaterial::new(values, intensity)?)
+            }
+            MaterialDesc::Opacity {
+                material,
+                opacity,
+                intensity,
+            } => Arc::new(OpacityMaterial::new(material.build()?, opacity, intensity)?),
+        })
+    }
+}
+
+impl ObjectDesc {
+    /// Construct the object described.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the arguments are rejected by a constructor.
+    pub fn build(self) -> Result<Arc<dyn Object>, &'static str> {
+        Ok(match self {
+            ObjectDesc::Sdf { sdf, material } => Arc::new(SDFObject::new(
+                sdf.build()?,
+                DynMaterial::from(material.build()?),
+            )),
+        })
+    }
+}
+
+impl SceneDesc {
+    /// Construct the scene described.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the arguments are rejected by a constructor.
+    pub fn build(self) -> Result<Arc<dyn Scene>, &'static str> {
+        Ok(match self {
+            SceneDesc::Objects {
+                channels,
+                objects,
+                background,
+                blend,
+                bvh,
+                bvh_method,
+                bvh_leaf_size,
+            } => {
+                let mut scene = ObjectsScene::new(channels)?;
+                for ObjectEntry { object, priority } in objects {
+                    scene.add_object(object.build()?, priority)?;
+                }
+
+                scene.set_background(background)?;
+                scene.set_blend(blend);
+                if bvh {
+                    scene.build_bvh(bvh_method, bvh_leaf_size, accelerator::available_threads())?;
+                }
+                Arc::new(scene)
+            }
+            SceneDesc::Elastic {
+                scene,
+                spacing,
+                sigma,
+                seed,
+            } => Arc::new(ElasticScene::new(scene.build()?, spacing, sigma, seed)?),
+        })
+    }
+}
+
+/// File formats of a scene file, by extension.
+#[derive(Clone, Copy, Debug)]
+enum Format {
+    Json,
+    Toml,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Result<Self, String> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Ok(Self::Json),
+            Some("toml") => Ok(Self::Toml),
+            _ => Err(format!(
+                "unknown scene file format of {}, expected .json or .toml",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// Save a scene to a JSON or TOML file, by the extension of `path`.
+///
+/// # Errors
+///
+/// Returns an error when a part of the scene cannot be saved, the format is
+/// unknown or the file cannot be written.
+pub fn save_scene(scene: &dyn Scene, path: &Path) -> Result<(), String> {
+    let format = Format::from_path(path)?;
+    let file = SceneFile {
+        version: VERSION,
+        scene: scene.describe()?,
+    };
+    let text = match format {
+        Format::Json => serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?,
+        Format::Toml => toml::to_string(&file).map_err(|e| e.to_string())?,
+    };
+    fs::write(path, text).map_err(|e| format!("failed to write {}: {e}", path.display()))
+}
+
+/// Load a scene from a JSON or TOML file, by the extension of `path`.
+///
+/// # Errors
+///
+/// Returns an error when the file cannot be read, is of a newer version or
+/// does not describe a valid scene.
+pub fn load_scene(path: &Path) -> Result<Arc<dyn Scene>, String> {
+    let format = Format::from_path(path)?;
+    let text =
+        fs::read_to_string(path).map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+
+    // check the version first, a newer file may not parse at all
+    let header: Header = parse(format, &text)?;
+    if header.version > VERSION {
+        return Err(format!(
+            "scene file version {} is newer than the supported version {VERSION}",
+            header.version
+        ));
+    }
+
+    let file: SceneFile = parse(format, &text)?;
+    Ok(file.scene.build()?)
+}
+
+fn parse<T: for<'de> Deserialize<'de>>(format: Format, text: &str) -> Result<T, String> {
+    match format {
+        Format::Json => serde_json::from_str(text).map_err(|e| e.to_string()),
+        Format::Toml => toml::from_str(text).map_err(|e| e.to_string()),
+    }
+}
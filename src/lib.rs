@@ -6,6 +6,12 @@
 //!
 //! This library provides primitives, composition operations, and samplers
 //! for working with signed distance fields.
+//!
+//! The Python module is built with the `python` feature, without it the
+//! crate is a plain Rust library.
+//!
+//! From Rust, scenes are loaded from scene files or SWC morphologies and
+//! sampled with [`render`], which the `sdflit` command line tool builds on.
 
 #![warn(
     trivial_casts,
@@ -23,37 +29,95 @@
 )]
 // Allow certain clippy lints that are too restrictive for this crate
 #![allow(clippy::many_single_char_names)]
+// Block doc comments are set apart from the item by a blank line
+#![allow(clippy::empty_line_after_doc_comments)]
 
-mod accelerator;
-mod composition;
-mod material;
-mod object;
-mod primitive;
+/// Spatial indices over the objects of a scene.
+pub mod accelerator;
+#[cfg(feature = "python")]
+mod callback;
+/// Boolean operations combining SDFs.
+pub mod composition;
+/// Scene files, describing scenes as plain data.
+pub mod description;
+/// Materials shading the hits of SDFs.
+pub mod material;
+/// Modifiers offsetting, repeating and deforming SDFs.
+pub mod modifier;
+/// Seeded gradient noise.
+pub mod noise;
+/// Objects drawn in a scene.
+pub mod object;
+#[cfg(feature = "python")]
+mod pickle;
+/// Primitive shapes.
+pub mod primitive;
+pub mod render;
 mod sampler;
-mod scene;
-mod sdf;
+/// Scenes of objects, sampled at points.
+pub mod scene;
+/// The SDF trait and its shared wrapper.
+pub mod sdf;
 mod solid_geometry;
-mod vec3;
+mod swc;
+/// Three dimensional vectors.
+pub mod vec3;
 
+pub use accelerator::Accelerator;
+pub use description::{load_scene, save_scene};
+pub use material::Material;
+pub use object::Object;
+pub use scene::Scene;
+pub use sdf::SDF;
+pub use swc::load_swc;
+pub use vec3::{Float, Vec3, Vec3f};
+
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
+#[cfg(feature = "python")]
 #[pymodule]
 fn sdflit(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // SDF and Primitives
     m.add_class::<sdf::DynSDF>()?;
     m.add_class::<primitive::FrustumCone>()?;
+    m.add_class::<primitive::FunctionSDF>()?;
+    m.add_class::<primitive::Metaballs>()?;
     m.add_class::<primitive::RoundCone>()?;
     m.add_class::<primitive::Sphere>()?;
+    m.add_class::<primitive::Spheres>()?;
+    m.add_class::<primitive::SplineTube>()?;
+    m.add_class::<primitive::Tube>()?;
 
     // Composition
     m.add_function(wrap_pyfunction!(composition::merge, m)?)?;
     m.add_function(wrap_pyfunction!(composition::intersect, m)?)?;
     m.add_function(wrap_pyfunction!(composition::subtract, m)?)?;
 
+    // Modifier
+    m.add_function(wrap_pyfunction!(modifier::repeat, m)?)?;
+    m.add_function(wrap_pyfunction!(modifier::repeat_radial, m)?)?;
+    m.add_function(wrap_pyfunction!(modifier::mirror, m)?)?;
+    m.add_function(wrap_pyfunction!(modifier::round_, m)?)?;
+    m.add_function(wrap_pyfunction!(modifier::shell, m)?)?;
+    m.add_function(wrap_pyfunction!(modifier::elongate, m)?)?;
+    m.add_function(wrap_pyfunction!(modifier::twist, m)?)?;
+    m.add_function(wrap_pyfunction!(modifier::bend, m)?)?;
+    m.add_function(wrap_pyfunction!(modifier::warp, m)?)?;
+    m.add_function(wrap_pyfunction!(modifier::displace, m)?)?;
+
     // Material
     m.add_class::<material::DynMaterial>()?;
     m.add_class::<material::ColoredMaterial>()?;
     m.add_class::<material::LinearGradientMaterial>()?;
+    m.add_class::<material::NoiseMaterial>()?;
+    m.add_class::<material::CheckerMaterial>()?;
+    m.add_class::<material::StripesMaterial>()?;
+    m.add_class::<material::ColormapMaterial>()?;
+    m.add_class::<material::FalloffMaterial>()?;
+    m.add_class::<material::ChannelsMaterial>()?;
+    m.add_class::<material::OpacityMaterial>()?;
+    m.add_class::<material::FunctionMaterial>()?;
 
     // Object
     m.add_class::<object::DynObject>()?;
@@ -62,6 +126,8 @@ fn sdflit(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Scene
     m.add_class::<scene::DynScene>()?;
     m.add_class::<scene::ObjectsScene>()?;
+    m.add_class::<scene::ElasticScene>()?;
+    m.add_function(wrap_pyfunction!(description::py_load_scene, m)?)?;
 
     // Sampler
     m.add_class::<sampler::UniformSampler>()?;
@@ -4,28 +4,146 @@
 
 use std::sync::Arc;
 
-use pyo3::{exceptions::PyValueError, prelude::*};
+#[cfg(feature = "python")]
+use pyo3::{
+    exceptions::{PyTypeError, PyValueError},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
 
+use crate::description::MaterialDesc;
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
 use crate::sdf::SDFHitInfo;
-use crate::vec3::{self, Vec3f};
+use crate::vec3::{self, Float, Vec3f};
 
+mod channels;
+mod colormap;
+mod falloff;
+#[cfg(feature = "python")]
+mod function;
+mod opacity;
+mod texture;
+
+pub use channels::ChannelsMaterial;
+pub use colormap::ColormapMaterial;
+pub use falloff::{FalloffMaterial, FalloffOrigin, FalloffProfile};
+#[cfg(feature = "python")]
+pub use function::FunctionMaterial;
+pub use opacity::OpacityMaterial;
+pub use texture::{CheckerMaterial, NoiseMaterial, StripesMaterial, WorldAxis};
+
+/// Colors and channels of the points hitting an SDF.
 pub trait Material: Send + Sync {
-    fn hit(&self, hit: SDFHitInfo) -> Vec3f;
+    /// Color at the world-space point `p` of a hit.
+    fn hit(&self, p: Vec3f, hit: SDFHitInfo) -> Vec3f;
+
+    /// Opacity in `[0, 1]` at the world-space point `p` of a hit.
+    fn opacity(&self, _p: Vec3f, _hit: SDFHitInfo) -> f32 {
+        1.0
+    }
+
+    /// Number of channels written by `hit_channels`.
+    fn channels(&self) -> usize {
+        3
+    }
+
+    /// Write the channels at the world-space point `p` of a hit into `out`,
+    /// which holds exactly `channels` values. Defaults to the RGB color.
+    fn hit_channels(&self, p: Vec3f, hit: SDFHitInfo, out: &mut [f32]) {
+        let color: [f32; 3] = self.hit(p, hit).map(vec3::to_f32).into();
+        for (o, c) in out.iter_mut().zip(color) {
+            *o = c;
+        }
+    }
+
+    /// Write the channels of a batch of hits into `out`, `channels` values per
+    /// hit.
+    fn hit_batch(&self, ps: &[Vec3f], hits: &[SDFHitInfo], out: &mut [f32]) {
+        let chunks = out.chunks_exact_mut(self.channels());
+        for ((p, hit), out) in ps.iter().zip(hits).zip(chunks) {
+            self.hit_channels(*p, *hit, out);
+        }
+    }
+
+    /// Recipe rebuilding the material from Python, for pickling.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the material cannot be pickled, the default for
+    /// materials defined outside of sdflit.
+    #[cfg(feature = "python")]
+    fn reduce(&self, _py: Python<'_>) -> PyResult<Reduce> {
+        Err(PyTypeError::new_err("material cannot be pickled"))
+    }
+
+    /// Plain data describing the material, for scene files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the material cannot be saved, the default for
+    /// materials defined outside of sdflit.
+    fn describe(&self) -> Result<MaterialDesc, &'static str> {
+        Err("material cannot be saved to a scene file")
+    }
 }
 
 /**
  * Wrapper for material
  */
 
-#[pyclass]
-#[pyo3(name = "Material")]
+#[cfg_attr(feature = "python", pyclass(module = "sdflit", name = "Material"))]
 #[derive(Clone)]
 #[allow(missing_debug_implementations)]
 pub struct DynMaterial(Arc<dyn Material>);
 
+#[cfg(feature = "python")]
+#[pymethods]
+impl DynMaterial {
+    fn channels(&self) -> usize {
+        self.0.channels()
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        self.0.reduce(py)
+    }
+}
+
 impl Material for DynMaterial {
-    fn hit(&self, hit: SDFHitInfo) -> Vec3f {
-        self.0.hit(hit)
+    fn hit(&self, p: Vec3f, hit: SDFHitInfo) -> Vec3f {
+        self.0.hit(p, hit)
+    }
+
+    fn opacity(&self, p: Vec3f, hit: SDFHitInfo) -> f32 {
+        self.0.opacity(p, hit)
+    }
+
+    fn channels(&self) -> usize {
+        self.0.channels()
+    }
+
+    fn hit_channels(&self, p: Vec3f, hit: SDFHitInfo, out: &mut [f32]) {
+        self.0.hit_channels(p, hit, out);
+    }
+
+    fn hit_batch(&self, ps: &[Vec3f], hits: &[SDFHitInfo], out: &mut [f32]) {
+        self.0.hit_batch(ps, hits, out);
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        self.0.reduce(py)
+    }
+
+    fn describe(&self) -> Result<MaterialDesc, &'static str> {
+        self.0.describe()
+    }
+}
+
+impl DynMaterial {
+    /// Wrap `material`, to be shared by objects.
+    pub fn new(material: impl Material + 'static) -> Self {
+        Self(Arc::new(material))
     }
 }
 
@@ -45,41 +163,85 @@ impl From<DynMaterial> for Arc<dyn Material> {
  * Colored material
  */
 
-#[pyclass]
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
 #[derive(Clone, Copy, Debug)]
 pub struct ColoredMaterial {
     color: Vec3f,
 }
 
+impl ColoredMaterial {
+    /// A single `color` everywhere.
+    #[must_use]
+    pub fn new(color: Vec3f) -> Self {
+        Self { color }
+    }
+}
+
+#[cfg(feature = "python")]
 #[pymethods]
 impl ColoredMaterial {
     #[new]
-    pub fn new(color: (f32, f32, f32)) -> Self {
-        Self {
-            color: color.into(),
-        }
+    fn __new__(color: (Float, Float, Float)) -> Self {
+        Self::new(color.into())
     }
 
-    pub fn into(&self) -> DynMaterial {
+    fn into(&self) -> DynMaterial {
         DynMaterial(Arc::new(*self))
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::new::<Self>(py, (self.color,))
+    }
 }
 
 impl Material for ColoredMaterial {
-    fn hit(&self, _hit: SDFHitInfo) -> Vec3f {
+    fn hit(&self, _p: Vec3f, _hit: SDFHitInfo) -> Vec3f {
         self.color
     }
-}
 
-/**
- * Linear Gradient Material
- */
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, *self)
+    }
 
-#[derive(Clone, Copy, Debug)]
+    fn describe(&self) -> Result<MaterialDesc, &'static str> {
+        Ok(MaterialDesc::Colored { color: self.color })
+    }
+}
+
+/// Coordinate of a hit driving a material.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Axis {
+    /// `SDFHitInfo::u`.
     U,
+    /// `SDFHitInfo::v`.
     V,
+    /// `SDFHitInfo::w`.
     W,
+    /// `SDFHitInfo::distance`.
+    Distance,
+}
+
+impl Axis {
+    #[cfg(feature = "python")]
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Axis::U => "u",
+            Axis::V => "v",
+            Axis::W => "w",
+            Axis::Distance => "distance",
+        }
+    }
+
+    fn select(self, hit: SDFHitInfo) -> Float {
+        match self {
+            Axis::U => hit.u,
+            Axis::V => hit.v,
+            Axis::W => hit.w,
+            Axis::Distance => hit.distance,
+        }
+    }
 }
 
 impl TryFrom<&str> for Axis {
@@ -90,12 +252,17 @@ impl TryFrom<&str> for Axis {
             "u" | "U" => Ok(Self::U),
             "v" | "V" => Ok(Self::V),
             "w" | "W" => Ok(Self::W),
+            "distance" | "Distance" => Ok(Self::Distance),
             _ => Err(()),
         }
     }
 }
 
-#[pyclass]
+/**
+ * Linear Gradient Material
+ */
+
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
 #[derive(Clone, Copy, Debug)]
 pub struct LinearGradientMaterial {
     c1: Vec3f,
@@ -104,15 +271,18 @@ pub struct LinearGradientMaterial {
 }
 
 impl LinearGradientMaterial {
+    /// Blend from `c2` at 0 to `c1` at 1 of the hit coordinate `axis`.
+    #[must_use]
     pub fn new(c1: Vec3f, c2: Vec3f, axis: Axis) -> Self {
         Self { c1, c2, axis }
     }
 }
 
+#[cfg(feature = "python")]
 #[pymethods]
 impl LinearGradientMaterial {
     #[new]
-    pub fn __new__(c1: (f32, f32, f32), c2: (f32, f32, f32), axis: &str) -> PyResult<Self> {
+    fn __new__(c1: (Float, Float, Float), c2: (Float, Float, Float), axis: &str) -> PyResult<Self> {
         let axis: Axis = axis
             .try_into()
             .map_err(|()| PyValueError::new_err("Invalid axis"))?;
@@ -120,18 +290,32 @@ impl LinearGradientMaterial {
         Ok(Self::new(c1.into(), c2.into(), axis))
     }
 
-    pub fn into(&self) -> DynMaterial {
+    fn into(&self) -> DynMaterial {
         DynMaterial(Arc::new(*self))
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let args = (self.c1, self.c2, self.axis.as_str());
+        pickle::new::<Self>(py, args)
+    }
 }
 
 impl Material for LinearGradientMaterial {
-    fn hit(&self, hit: SDFHitInfo) -> Vec3f {
-        let axis = match self.axis {
-            Axis::U => hit.u,
-            Axis::V => hit.v,
-            Axis::W => hit.w,
-        };
+    fn hit(&self, _p: Vec3f, hit: SDFHitInfo) -> Vec3f {
+        let axis = self.axis.select(hit);
         vec3::interpolate(self.c1, self.c2, axis.clamp(0.0, 1.0))
     }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, *self)
+    }
+
+    fn describe(&self) -> Result<MaterialDesc, &'static str> {
+        Ok(MaterialDesc::LinearGradient {
+            c1: self.c1,
+            c2: self.c2,
+            axis: self.axis,
+        })
+    }
 }
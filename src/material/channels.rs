@@ -0,0 +1,118 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+#[cfg(feature = "python")]
+use numpy::{AllowTypeChange, IntoPyArray, PyArrayLike1};
+#[cfg(feature = "python")]
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::description::MaterialDesc;
+#[cfg(feature = "python")]
+use crate::material::DynMaterial;
+use crate::material::Material;
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+use crate::sdf::SDFHitInfo;
+use crate::vec3::{self, Float, Vec3f};
+
+/**
+ * Channels Material
+ *
+ * A fixed value per channel, e.g. the expression of each marker in a
+ * multiplexed image, optionally scaled by the mean RGB of an `intensity`
+ * material.
+ */
+
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
+#[derive(Clone)]
+#[allow(missing_debug_implementations)]
+pub struct ChannelsMaterial {
+    values: Vec<f32>,
+    intensity: Option<Arc<dyn Material>>,
+}
+
+impl ChannelsMaterial {
+    /// One `values` entry per channel, scaled by `intensity` when given.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `values` is empty.
+    pub fn new(
+        values: Vec<f32>,
+        intensity: Option<Arc<dyn Material>>,
+    ) -> Result<Self, &'static str> {
+        if values.is_empty() {
+            return Err("values should not be empty");
+        }
+
+        Ok(Self { values, intensity })
+    }
+
+    fn intensity(&self, p: Vec3f, hit: SDFHitInfo) -> Float {
+        self.intensity.as_ref().map_or(1.0, |m| {
+            let c = m.hit(p, hit);
+            (c.x + c.y + c.z) / 3.0
+        })
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl ChannelsMaterial {
+    #[new]
+    #[pyo3(signature = (values, intensity = None))]
+    #[allow(clippy::needless_pass_by_value)]
+    fn __new__(
+        values: PyArrayLike1<'_, f32, AllowTypeChange>,
+        intensity: Option<DynMaterial>,
+    ) -> PyResult<Self> {
+        let values = values.as_array().to_vec();
+        Self::new(values, intensity.map(Into::into)).map_err(PyValueError::new_err)
+    }
+
+    fn into(&self) -> DynMaterial {
+        DynMaterial(Arc::new(self.clone()))
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let intensity = self.intensity.clone().map(DynMaterial::from);
+        let args = (self.values.clone().into_pyarray(py), intensity);
+        pickle::new::<Self>(py, args)
+    }
+}
+
+impl Material for ChannelsMaterial {
+    /// The first three channels, padded with zeros.
+    fn hit(&self, p: Vec3f, hit: SDFHitInfo) -> Vec3f {
+        let k = self.intensity(p, hit);
+        let channel = |i: usize| self.values.get(i).map_or(0.0, |v| k * Float::from(*v));
+        Vec3f::new(channel(0), channel(1), channel(2))
+    }
+
+    fn channels(&self) -> usize {
+        self.values.len()
+    }
+
+    fn hit_channels(&self, p: Vec3f, hit: SDFHitInfo, out: &mut [f32]) {
+        let k = vec3::to_f32(self.intensity(p, hit));
+        for (o, v) in out.iter_mut().zip(&self.values) {
+            *o = k * v;
+        }
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, self.clone())
+    }
+
+    fn describe(&self) -> Result<MaterialDesc, &'static str> {
+        let intensity = self.intensity.as_ref().map(|m| m.describe());
+        Ok(MaterialDesc::Channels {
+            values: self.values.clone(),
+            intensity: intensity.transpose()?.map(Box::new),
+        })
+    }
+}
@@ -0,0 +1,265 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(feature = "python")]
+use std::sync::Arc;
+
+#[cfg(feature = "python")]
+use numpy::{ndarray::Array2, AllowTypeChange, IntoPyArray, PyArrayLike1, PyArrayLike2};
+#[cfg(feature = "python")]
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::description::MaterialDesc;
+#[cfg(feature = "python")]
+use crate::material::DynMaterial;
+use crate::material::{Axis, Material};
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+use crate::sdf::SDFHitInfo;
+use crate::vec3::{self, Float, Vec3f};
+
+/// Number of entries sampled from the built-in colormaps.
+const BUILTIN_SIZE: usize = 256;
+
+/**
+ * Colormap Material
+ *
+ * Maps a hit axis through a color ramp. The value is rescaled from
+ * `[vmin, vmax]` to `[0, 1]` and clamped, then linearly interpolated between
+ * the colors of the lookup table, placed at `stops` or evenly spaced.
+ */
+
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
+#[derive(Clone, Debug)]
+pub struct ColormapMaterial {
+    lut: Vec<Vec3f>,
+    stops: Vec<Float>,
+    axis: Axis,
+    vmin: Float,
+    vmax: Float,
+}
+
+impl ColormapMaterial {
+    /// Map `axis` from `[vmin, vmax]` through the colors of `lut`, placed at
+    /// `stops` or evenly spaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `lut` is empty, `stops` does not have one
+    /// non-decreasing entry per color or `vmin` equals `vmax`.
+    pub fn new(
+        lut: Vec<Vec3f>,
+        stops: Option<Vec<Float>>,
+        axis: Axis,
+        vmin: Float,
+        vmax: Float,
+    ) -> Result<Self, &'static str> {
+        if lut.is_empty() {
+            return Err("colormap should not be empty");
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let stops = match stops {
+            Some(stops) if stops.len() != lut.len() => {
+                return Err("stops should have one entry per color");
+            }
+            Some(stops) if stops.windows(2).any(|w| w.first() > w.get(1)) => {
+                return Err("stops should be non-decreasing");
+            }
+            Some(stops) => stops,
+            None if lut.len() == 1 => vec![0.0],
+            None => {
+                let n = (lut.len() - 1) as Float;
+                (0..lut.len()).map(|i| i as Float / n).collect()
+            }
+        };
+
+        if vmax - vmin == 0.0 {
+            return Err("vmin and vmax should not be equal");
+        }
+
+        Ok(Self {
+            lut,
+            stops,
+            axis,
+            vmin,
+            vmax,
+        })
+    }
+
+    /// A built-in colormap, `viridis`, `magma` or `turbo`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the colormap is unknown, or as `new`.
+    pub fn builtin(name: &str, axis: Axis, vmin: Float, vmax: Float) -> Result<Self, &'static str> {
+        let f = match name {
+            "viridis" => viridis,
+            "magma" => magma,
+            "turbo" => turbo,
+            _ => return Err("Invalid colormap"),
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let lut = (0..BUILTIN_SIZE)
+            .map(|i| f(i as Float / (BUILTIN_SIZE - 1) as Float))
+            .collect();
+        Self::new(lut, None, axis, vmin, vmax)
+    }
+
+    fn lookup(&self, t: Float) -> Vec3f {
+        let i = self.stops.partition_point(|&s| s <= t);
+        let lower = i
+            .checked_sub(1)
+            .and_then(|j| self.stops.get(j).zip(self.lut.get(j)));
+        let upper = self.stops.get(i).zip(self.lut.get(i));
+        match (lower, upper) {
+            (Some((&s1, &c1)), Some((&s2, &c2))) => vec3::interpolate(c2, c1, (t - s1) / (s2 - s1)),
+            (Some((_, &c)), None) | (None, Some((_, &c))) => c,
+            (None, None) => unreachable!("colormap should not be empty"),
+        }
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl ColormapMaterial {
+    #[new]
+    #[pyo3(signature = (colormap, axis = "u", vmin = 0.0, vmax = 1.0, stops = None))]
+    #[allow(clippy::needless_pass_by_value)]
+    fn __new__(
+        colormap: &Bound<'_, PyAny>,
+        axis: &str,
+        vmin: Float,
+        vmax: Float,
+        stops: Option<PyArrayLike1<'_, Float, AllowTypeChange>>,
+    ) -> PyResult<Self> {
+        let axis: Axis = axis
+            .try_into()
+            .map_err(|()| PyValueError::new_err("Invalid axis"))?;
+
+        if let Ok(name) = colormap.extract::<String>() {
+            if stops.is_some() {
+                return Err(PyValueError::new_err(
+                    "stops are not supported for built-in colormaps",
+                ));
+            }
+            return Self::builtin(&name, axis, vmin, vmax).map_err(PyValueError::new_err);
+        }
+
+        let lut = colormap.extract::<PyArrayLike2<'_, Float, AllowTypeChange>>()?;
+        let lut = lut.as_array();
+        if lut.ncols() != 3 {
+            return Err(PyValueError::new_err("colormap should be of shape (K, 3)"));
+        }
+
+        let lut = lut
+            .rows()
+            .into_iter()
+            .map(|row| Vec3f::try_from(row.to_vec()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(PyValueError::new_err)?;
+        let stops = stops.map(|s| s.as_array().to_vec());
+        Self::new(lut, stops, axis, vmin, vmax).map_err(PyValueError::new_err)
+    }
+
+    fn into(&self) -> DynMaterial {
+        DynMaterial(Arc::new(self.clone()))
+    }
+
+    /// Pickled as its lookup table, built-in colormaps included.
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let lut = self.lut.iter().flat_map(|c| [c.x, c.y, c.z]).collect();
+        let lut = Array2::from_shape_vec((self.lut.len(), 3), lut).unwrap();
+        let args = (
+            lut.into_pyarray(py),
+            self.axis.as_str(),
+            self.vmin,
+            self.vmax,
+            self.stops.clone().into_pyarray(py),
+        );
+        pickle::new::<Self>(py, args)
+    }
+}
+
+impl Material for ColormapMaterial {
+    fn hit(&self, _p: Vec3f, hit: SDFHitInfo) -> Vec3f {
+        let t = (self.axis.select(hit) - self.vmin) / (self.vmax - self.vmin);
+        self.lookup(t.clamp(0.0, 1.0))
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, self.clone())
+    }
+
+    fn describe(&self) -> Result<MaterialDesc, &'static str> {
+        Ok(MaterialDesc::Colormap {
+            lut: self.lut.clone(),
+            stops: Some(self.stops.clone()),
+            axis: self.axis,
+            vmin: self.vmin,
+            vmax: self.vmax,
+        })
+    }
+}
+
+/// Evaluate a polynomial with coefficients in increasing order.
+fn polynomial(coefficients: &[Vec3f], t: Float) -> Vec3f {
+    let c = coefficients
+        .iter()
+        .rev()
+        .fold(Vec3f::new(0.0, 0.0, 0.0), |acc, &c| acc * t + c);
+    vec3::minimum(
+        vec3::maximum(c, Vec3f::new(0.0, 0.0, 0.0)),
+        Vec3f::new(1.0, 1.0, 1.0),
+    )
+}
+
+/// Polynomial fit of matplotlib's viridis.
+fn viridis(t: Float) -> Vec3f {
+    polynomial(
+        &[
+            Vec3f::new(0.277_727_33, 0.005_407_344_5, 0.334_099_8),
+            Vec3f::new(0.105_093_04, 1.404_613_5, 1.384_590_2),
+            Vec3f::new(-0.330_861_83, 0.214_847_56, 0.095_095_16),
+            Vec3f::new(-4.634_230_6, -5.799_101, -19.332_441),
+            Vec3f::new(6.228_27, 14.179_933, 56.690_55),
+            Vec3f::new(4.776_385, -13.745_145, -65.353_03),
+            Vec3f::new(-5.435_456, 4.645_852_6, 26.312_435),
+        ],
+        t,
+    )
+}
+
+/// Polynomial fit of matplotlib's magma.
+fn magma(t: Float) -> Vec3f {
+    polynomial(
+        &[
+            Vec3f::new(-0.002_136_485, -0.000_749_655_05, -0.005_386_128),
+            Vec3f::new(0.251_660_54, 0.677_523_24, 2.494_026_6),
+            Vec3f::new(8.353_717, -3.577_719_4, 0.314_467_9),
+            Vec3f::new(-27.668_733, 14.264_731, -13.649_213),
+            Vec3f::new(52.176_14, -27.943_607, 12.944_169),
+            Vec3f::new(-50.768_524, 29.046_583, 4.234_153),
+            Vec3f::new(18.655_705, -11.489_774, -5.601_961_4),
+        ],
+        t,
+    )
+}
+
+/// Polynomial approximation of Google's turbo.
+fn turbo(t: Float) -> Vec3f {
+    polynomial(
+        &[
+            Vec3f::new(0.135_721_38, 0.091_402_61, 0.106_673_3),
+            Vec3f::new(4.615_392_6, 2.194_188_4, 12.641_946),
+            Vec3f::new(-42.660_324, 4.842_966_6, -60.582_047),
+            Vec3f::new(132.131_09, -14.185_033, 110.362_77),
+            Vec3f::new(-152.942_4, 4.277_299, -89.903_11),
+            Vec3f::new(59.286_38, 2.829_566, 27.348_25),
+        ],
+        t,
+    )
+}
@@ -0,0 +1,212 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(feature = "python")]
+use std::sync::Arc;
+
+#[cfg(feature = "python")]
+use pyo3::{exceptions::PyValueError, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::description::MaterialDesc;
+#[cfg(feature = "python")]
+use crate::material::DynMaterial;
+use crate::material::Material;
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+use crate::sdf::SDFHitInfo;
+use crate::vec3::{self, Float, Vec3f};
+
+/// Decay of the intensity with the distance to its origin.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FalloffProfile {
+    /// Linear down to zero at one scale unit.
+    Linear,
+    /// Gaussian of standard deviation one scale unit.
+    Gaussian,
+    /// Exponential decay by `e` per scale unit.
+    Exponential,
+}
+
+impl FalloffProfile {
+    #[cfg(feature = "python")]
+    fn as_str(self) -> &'static str {
+        match self {
+            FalloffProfile::Linear => "linear",
+            FalloffProfile::Gaussian => "gaussian",
+            FalloffProfile::Exponential => "exponential",
+        }
+    }
+
+    /// Intensity at `x` scale units away from the origin, 1 at the origin.
+    fn intensity(self, x: Float) -> Float {
+        match self {
+            FalloffProfile::Linear => (1.0 - x).max(0.0),
+            FalloffProfile::Gaussian => (-0.5 * x * x).exp(),
+            FalloffProfile::Exponential => (-x).exp(),
+        }
+    }
+}
+
+impl TryFrom<&str> for FalloffProfile {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "linear" | "Linear" => Ok(Self::Linear),
+            "gaussian" | "Gaussian" => Ok(Self::Gaussian),
+            "exponential" | "Exponential" => Ok(Self::Exponential),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Where the intensity peaks.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FalloffOrigin {
+    /// On the surface, decaying inward with the depth of the hit.
+    Surface,
+    /// On the centreline at depth `radius`, decaying outward to the surface.
+    Centerline {
+        /// Depth of the centreline below the surface.
+        radius: Float,
+    },
+}
+
+/**
+ * Falloff Material
+ *
+ * Blend from `color` to `background` with the distance of a hit to the
+ * surface or to the centreline, e.g. membrane or cytoplasmic labelling.
+ */
+
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
+#[derive(Clone, Copy, Debug)]
+pub struct FalloffMaterial {
+    color: Vec3f,
+    background: Vec3f,
+    scale: Float,
+    profile: FalloffProfile,
+    origin: FalloffOrigin,
+}
+
+impl FalloffMaterial {
+    /// Decay from `color` to `background` over `scale` units away from `origin`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `scale` or the centreline `radius` is not positive.
+    pub fn new(
+        color: Vec3f,
+        background: Vec3f,
+        scale: Float,
+        profile: FalloffProfile,
+        origin: FalloffOrigin,
+    ) -> Result<Self, &'static str> {
+        if scale <= 0.0 {
+            return Err("scale should be positive");
+        }
+
+        if let FalloffOrigin::Centerline { radius } = origin {
+            if radius <= 0.0 {
+                return Err("radius should be positive");
+            }
+        }
+
+        Ok(Self {
+            color,
+            background,
+            scale,
+            profile,
+            origin,
+        })
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl FalloffMaterial {
+    #[new]
+    #[pyo3(signature = (color, scale, profile = "linear", origin = "surface", radius = None, background = (0.0, 0.0, 0.0)))]
+    fn __new__(
+        color: (Float, Float, Float),
+        scale: Float,
+        profile: &str,
+        origin: &str,
+        radius: Option<Float>,
+        background: (Float, Float, Float),
+    ) -> PyResult<Self> {
+        let profile: FalloffProfile = profile
+            .try_into()
+            .map_err(|()| PyValueError::new_err("Invalid falloff profile"))?;
+
+        let origin = match (origin, radius) {
+            ("surface", None) => FalloffOrigin::Surface,
+            ("centerline", Some(radius)) => FalloffOrigin::Centerline { radius },
+            ("surface", Some(_)) => {
+                return Err(PyValueError::new_err(
+                    "radius is only used with the centerline origin",
+                ))
+            }
+            ("centerline", None) => {
+                return Err(PyValueError::new_err(
+                    "radius is required with the centerline origin",
+                ))
+            }
+            _ => return Err(PyValueError::new_err("Invalid falloff origin")),
+        };
+
+        Self::new(color.into(), background.into(), scale, profile, origin)
+            .map_err(PyValueError::new_err)
+    }
+
+    fn into(&self) -> DynMaterial {
+        DynMaterial(Arc::new(*self))
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let (origin, radius) = match self.origin {
+            FalloffOrigin::Surface => ("surface", None),
+            FalloffOrigin::Centerline { radius } => ("centerline", Some(radius)),
+        };
+        let args = (
+            self.color,
+            self.scale,
+            self.profile.as_str(),
+            origin,
+            radius,
+            self.background,
+        );
+        pickle::new::<Self>(py, args)
+    }
+}
+
+impl Material for FalloffMaterial {
+    fn hit(&self, _p: Vec3f, hit: SDFHitInfo) -> Vec3f {
+        let depth = (-hit.distance).max(0.0);
+        let x = match self.origin {
+            FalloffOrigin::Surface => depth,
+            FalloffOrigin::Centerline { radius } => (radius - depth).max(0.0),
+        };
+        let intensity = self.profile.intensity(x / self.scale);
+        vec3::interpolate(self.color, self.background, intensity)
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, *self)
+    }
+
+    fn describe(&self) -> Result<MaterialDesc, &'static str> {
+        Ok(MaterialDesc::Falloff {
+            color: self.color,
+            background: self.background,
+            scale: self.scale,
+            profile: self.profile,
+            origin: self.origin,
+        })
+    }
+}
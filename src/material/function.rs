@@ -0,0 +1,172 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+#[cfg(feature = "python")]
+use numpy::{ndarray::Array2, AllowTypeChange, IntoPyArray, PyArrayLike2};
+#[cfg(feature = "python")]
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::callback;
+use crate::description::MaterialDesc;
+use crate::material::{DynMaterial, Material};
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+use crate::sdf::SDFHitInfo;
+use crate::vec3::{Float, Vec3f};
+
+/**
+ * Function Material
+ *
+ * A material defined by a Python callable `f(distance, u, v, w, p)` returning
+ * the channels of a hit. When `vectorized`, it receives a batch of `N` hits
+ * as arrays of shape `(N,)` and `(N, 3)` for `p` instead, and returns an
+ * array of shape `(N, channels)`.
+ */
+
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
+#[derive(Clone, Debug)]
+pub struct FunctionMaterial {
+    func: Arc<Py<PyAny>>,
+    channels: usize,
+    vectorized: bool,
+}
+
+impl FunctionMaterial {
+    /// Call `func` for the `channels` of each hit, with arrays of hits when
+    /// `vectorized`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `channels` is zero.
+    pub fn new(func: Py<PyAny>, channels: usize, vectorized: bool) -> Result<Self, &'static str> {
+        if channels == 0 {
+            return Err("channels should be positive");
+        }
+
+        Ok(Self {
+            func: Arc::new(func),
+            channels,
+            vectorized,
+        })
+    }
+
+    fn call(&self, p: Vec3f, hit: SDFHitInfo, out: &mut [f32]) -> PyResult<()> {
+        Python::attach(|py| {
+            let args = (hit.distance, hit.u, hit.v, hit.w, (p.x, p.y, p.z));
+            let values: Vec<f32> = self.func.bind(py).call1(args)?.extract()?;
+            if values.len() != self.channels {
+                return Err(PyValueError::new_err(
+                    "material function should return one value per channel",
+                ));
+            }
+
+            out.copy_from_slice(&values);
+            Ok(())
+        })
+    }
+
+    fn call_batch(&self, ps: &[Vec3f], hits: &[SDFHitInfo], out: &mut [f32]) -> PyResult<()> {
+        Python::attach(|py| {
+            let field = |f: fn(&SDFHitInfo) -> Float| hits.iter().map(f).collect::<Vec<_>>();
+            let p = ps.iter().flat_map(|p| [p.x, p.y, p.z]).collect::<Vec<_>>();
+            let p = Array2::from_shape_vec((ps.len(), 3), p).unwrap();
+            let args = (
+                field(|h| h.distance).into_pyarray(py),
+                field(|h| h.u).into_pyarray(py),
+                field(|h| h.v).into_pyarray(py),
+                field(|h| h.w).into_pyarray(py),
+                p.into_pyarray(py),
+            );
+
+            let values = self.func.bind(py).call1(args)?;
+            let values = values.extract::<PyArrayLike2<'_, f32, AllowTypeChange>>()?;
+            let values = values.as_array();
+            if values.dim() != (ps.len(), self.channels) {
+                return Err(PyValueError::new_err(
+                    "material function should return an array of shape (N, channels)",
+                ));
+            }
+
+            for (o, v) in out.iter_mut().zip(values.iter()) {
+                *o = *v;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl FunctionMaterial {
+    #[new]
+    #[pyo3(signature = (func, channels = 3, vectorized = false))]
+    fn __new__(func: Py<PyAny>, channels: usize, vectorized: bool) -> PyResult<Self> {
+        Self::new(func, channels, vectorized).map_err(PyValueError::new_err)
+    }
+
+    fn into(&self) -> DynMaterial {
+        DynMaterial(Arc::new(self.clone()))
+    }
+
+    /// The function is pickled by Python, so it should be importable, e.g. not
+    /// a lambda.
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let args = (self.func.clone_ref(py), self.channels, self.vectorized);
+        pickle::new::<Self>(py, args)
+    }
+}
+
+impl Material for FunctionMaterial {
+    /// The first three channels, padded with zeros.
+    fn hit(&self, p: Vec3f, hit: SDFHitInfo) -> Vec3f {
+        let mut out = vec![0.0; self.channels];
+        self.hit_channels(p, hit, &mut out);
+        let channel = |i: usize| out.get(i).copied().map_or(0.0, Float::from);
+        Vec3f::new(channel(0), channel(1), channel(2))
+    }
+
+    fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Zeros when the function fails, its error is raised once the query
+    /// returns to Python.
+    fn hit_channels(&self, p: Vec3f, hit: SDFHitInfo, out: &mut [f32]) {
+        let result = callback::run(|| {
+            if self.vectorized {
+                self.call_batch(&[p], &[hit], out)
+            } else {
+                self.call(p, hit, out)
+            }
+        });
+        if result.is_none() {
+            out.fill(0.0);
+        }
+    }
+
+    fn hit_batch(&self, ps: &[Vec3f], hits: &[SDFHitInfo], out: &mut [f32]) {
+        if !self.vectorized {
+            let chunks = out.chunks_exact_mut(self.channels);
+            for ((p, hit), out) in ps.iter().zip(hits).zip(chunks) {
+                self.hit_channels(*p, *hit, out);
+            }
+            return;
+        }
+
+        if callback::run(|| self.call_batch(ps, hits, out)).is_none() {
+            out.fill(0.0);
+        }
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, self.clone())
+    }
+
+    fn describe(&self) -> Result<MaterialDesc, &'static str> {
+        Err("function materials cannot be saved to a scene file")
+    }
+}
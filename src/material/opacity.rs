@@ -0,0 +1,121 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+#[cfg(feature = "python")]
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::description::MaterialDesc;
+#[cfg(feature = "python")]
+use crate::material::DynMaterial;
+use crate::material::Material;
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+use crate::sdf::SDFHitInfo;
+use crate::vec3::{Float, Vec3f};
+
+/**
+ * Opacity Material
+ *
+ * Scale the channels of a material by `intensity` and give it an `opacity`,
+ * used when the scene blends overlapping objects.
+ */
+
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
+#[derive(Clone)]
+#[allow(missing_debug_implementations)]
+pub struct OpacityMaterial {
+    material: Arc<dyn Material>,
+    opacity: f32,
+    intensity: f32,
+}
+
+impl OpacityMaterial {
+    /// Scale `material` by `intensity`, drawn with `opacity`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `opacity` is out of `[0, 1]` or `intensity` is
+    /// negative.
+    pub fn new(
+        material: Arc<dyn Material>,
+        opacity: f32,
+        intensity: f32,
+    ) -> Result<Self, &'static str> {
+        if !(0.0..=1.0).contains(&opacity) {
+            return Err("opacity should be in [0, 1]");
+        }
+
+        if intensity < 0.0 {
+            return Err("intensity should not be negative");
+        }
+
+        Ok(Self {
+            material,
+            opacity,
+            intensity,
+        })
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl OpacityMaterial {
+    #[new]
+    #[pyo3(signature = (material, opacity = 1.0, intensity = 1.0))]
+    fn __new__(material: DynMaterial, opacity: f32, intensity: f32) -> PyResult<Self> {
+        Self::new(material.into(), opacity, intensity).map_err(PyValueError::new_err)
+    }
+
+    fn into(&self) -> DynMaterial {
+        DynMaterial(Arc::new(self.clone()))
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let material = DynMaterial::from(self.material.clone());
+        pickle::new::<Self>(py, (material, self.opacity, self.intensity))
+    }
+}
+
+impl Material for OpacityMaterial {
+    fn hit(&self, p: Vec3f, hit: SDFHitInfo) -> Vec3f {
+        self.material.hit(p, hit) * Float::from(self.intensity)
+    }
+
+    fn opacity(&self, p: Vec3f, hit: SDFHitInfo) -> f32 {
+        self.opacity * self.material.opacity(p, hit)
+    }
+
+    fn channels(&self) -> usize {
+        self.material.channels()
+    }
+
+    fn hit_channels(&self, p: Vec3f, hit: SDFHitInfo, out: &mut [f32]) {
+        self.material.hit_channels(p, hit, out);
+        for o in out.iter_mut() {
+            *o *= self.intensity;
+        }
+    }
+
+    fn hit_batch(&self, ps: &[Vec3f], hits: &[SDFHitInfo], out: &mut [f32]) {
+        self.material.hit_batch(ps, hits, out);
+        for o in out.iter_mut() {
+            *o *= self.intensity;
+        }
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, self.clone())
+    }
+
+    fn describe(&self) -> Result<MaterialDesc, &'static str> {
+        Ok(MaterialDesc::Opacity {
+            material: Box::new(self.material.describe()?),
+            opacity: self.opacity,
+            intensity: self.intensity,
+        })
+    }
+}
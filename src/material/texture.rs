@@ -0,0 +1,341 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(feature = "python")]
+use std::sync::Arc;
+
+#[cfg(feature = "python")]
+use pyo3::{exceptions::PyValueError, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::description::MaterialDesc;
+#[cfg(feature = "python")]
+use crate::material::DynMaterial;
+use crate::material::Material;
+#[cfg(feature = "python")]
+use crate::noise::NoiseKind;
+use crate::noise::{self, Noise};
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+use crate::sdf::SDFHitInfo;
+use crate::vec3::{self, Float, Vec3f};
+
+/**
+ * Noise Material
+ *
+ * World-space fBm noise mapped from `c1` to `c2`, or split into the two colors
+ * at `threshold` when given.
+ */
+
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
+#[derive(Clone, Debug)]
+pub struct NoiseMaterial {
+    c1: Vec3f,
+    c2: Vec3f,
+    noise: Noise,
+    frequency: Float,
+    octaves: u32,
+    threshold: Option<Float>,
+}
+
+impl NoiseMaterial {
+    /// Blend `c1` and `c2` by `noise` at `frequency`, summed over `octaves`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `frequency` is not positive, or `octaves` is not
+    /// within `1..=MAX_OCTAVES`.
+    pub fn new(
+        c1: Vec3f,
+        c2: Vec3f,
+        noise: Noise,
+        frequency: Float,
+        octaves: u32,
+        threshold: Option<Float>,
+    ) -> Result<Self, &'static str> {
+        if frequency <= 0.0 {
+            return Err("frequency should be positive");
+        }
+
+        noise::check_octaves(octaves)?;
+        Ok(Self {
+            c1,
+            c2,
+            noise,
+            frequency,
+            octaves,
+            threshold,
+        })
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl NoiseMaterial {
+    #[new]
+    #[pyo3(signature = (c1, c2, frequency = 1.0, octaves = 1, seed = 0, kind = "perlin", threshold = None))]
+    fn __new__(
+        c1: (Float, Float, Float),
+        c2: (Float, Float, Float),
+        frequency: Float,
+        octaves: u32,
+        seed: u64,
+        kind: &str,
+        threshold: Option<Float>,
+    ) -> PyResult<Self> {
+        let kind: NoiseKind = kind
+            .try_into()
+            .map_err(|()| PyValueError::new_err("Invalid noise kind"))?;
+
+        let noise = Noise::new(kind, seed);
+        Self::new(c1.into(), c2.into(), noise, frequency, octaves, threshold)
+            .map_err(PyValueError::new_err)
+    }
+
+    fn into(&self) -> DynMaterial {
+        DynMaterial(Arc::new(self.clone()))
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let args = (
+            self.c1,
+            self.c2,
+            self.frequency,
+            self.octaves,
+            self.noise.seed(),
+            self.noise.kind().as_str(),
+            self.threshold,
+        );
+        pickle::new::<Self>(py, args)
+    }
+}
+
+impl Material for NoiseMaterial {
+    fn hit(&self, p: Vec3f, _hit: SDFHitInfo) -> Vec3f {
+        let noise = self.noise.fbm(p * self.frequency, self.octaves);
+        let k = match self.threshold {
+            Some(threshold) if noise < threshold => 0.0,
+            Some(_) => 1.0,
+            None => Float::midpoint(noise, 1.0),
+        };
+        vec3::interpolate(self.c2, self.c1, k)
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, self.clone())
+    }
+
+    fn describe(&self) -> Result<MaterialDesc, &'static str> {
+        Ok(MaterialDesc::Noise {
+            c1: self.c1,
+            c2: self.c2,
+            frequency: self.frequency,
+            octaves: self.octaves,
+            seed: self.noise.seed(),
+            kind: self.noise.kind(),
+            threshold: self.threshold,
+        })
+    }
+}
+
+/**
+ * Checker Material
+ */
+
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
+#[derive(Clone, Copy, Debug)]
+pub struct CheckerMaterial {
+    c1: Vec3f,
+    c2: Vec3f,
+    size: Vec3f,
+}
+
+impl CheckerMaterial {
+    /// Alternate `c1` and `c2` in cells of `size`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `size` is not positive.
+    pub fn new(c1: Vec3f, c2: Vec3f, size: Vec3f) -> Result<Self, &'static str> {
+        if size.x <= 0.0 || size.y <= 0.0 || size.z <= 0.0 {
+            return Err("size should be positive");
+        }
+
+        Ok(Self { c1, c2, size })
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl CheckerMaterial {
+    #[new]
+    fn __new__(
+        c1: (Float, Float, Float),
+        c2: (Float, Float, Float),
+        size: (Float, Float, Float),
+    ) -> PyResult<Self> {
+        Self::new(c1.into(), c2.into(), size.into()).map_err(PyValueError::new_err)
+    }
+
+    fn into(&self) -> DynMaterial {
+        DynMaterial(Arc::new(*self))
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let args = (self.c1, self.c2, self.size);
+        pickle::new::<Self>(py, args)
+    }
+}
+
+impl Material for CheckerMaterial {
+    fn hit(&self, p: Vec3f, _hit: SDFHitInfo) -> Vec3f {
+        let q = p / self.size;
+        let parity = q.x.floor() + q.y.floor() + q.z.floor();
+        if parity.rem_euclid(2.0) < 1.0 {
+            self.c1
+        } else {
+            self.c2
+        }
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, *self)
+    }
+
+    fn describe(&self) -> Result<MaterialDesc, &'static str> {
+        Ok(MaterialDesc::Checker {
+            c1: self.c1,
+            c2: self.c2,
+            size: self.size,
+        })
+    }
+}
+
+/// World space axis.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorldAxis {
+    /// The x axis.
+    X,
+    /// The y axis.
+    Y,
+    /// The z axis.
+    Z,
+}
+
+impl TryFrom<&str> for WorldAxis {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "x" | "X" => Ok(Self::X),
+            "y" | "Y" => Ok(Self::Y),
+            "z" | "Z" => Ok(Self::Z),
+            _ => Err(()),
+        }
+    }
+}
+
+impl WorldAxis {
+    #[cfg(feature = "python")]
+    fn as_str(self) -> &'static str {
+        match self {
+            WorldAxis::X => "x",
+            WorldAxis::Y => "y",
+            WorldAxis::Z => "z",
+        }
+    }
+}
+
+/**
+ * Stripes Material
+ */
+
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
+#[derive(Clone, Copy, Debug)]
+pub struct StripesMaterial {
+    c1: Vec3f,
+    c2: Vec3f,
+    width: Float,
+    axis: WorldAxis,
+}
+
+impl StripesMaterial {
+    /// Alternate `c1` and `c2` in slabs of `width` along `axis`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `width` is not positive.
+    pub fn new(c1: Vec3f, c2: Vec3f, width: Float, axis: WorldAxis) -> Result<Self, &'static str> {
+        if width <= 0.0 {
+            return Err("width should be positive");
+        }
+
+        Ok(Self {
+            c1,
+            c2,
+            width,
+            axis,
+        })
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl StripesMaterial {
+    #[new]
+    #[pyo3(signature = (c1, c2, width, axis = "x"))]
+    fn __new__(
+        c1: (Float, Float, Float),
+        c2: (Float, Float, Float),
+        width: Float,
+        axis: &str,
+    ) -> PyResult<Self> {
+        let axis: WorldAxis = axis
+            .try_into()
+            .map_err(|()| PyValueError::new_err("Invalid axis"))?;
+
+        Self::new(c1.into(), c2.into(), width, axis).map_err(PyValueError::new_err)
+    }
+
+    fn into(&self) -> DynMaterial {
+        DynMaterial(Arc::new(*self))
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let args = (self.c1, self.c2, self.width, self.axis.as_str());
+        pickle::new::<Self>(py, args)
+    }
+}
+
+impl Material for StripesMaterial {
+    fn hit(&self, p: Vec3f, _hit: SDFHitInfo) -> Vec3f {
+        let x = match self.axis {
+            WorldAxis::X => p.x,
+            WorldAxis::Y => p.y,
+            WorldAxis::Z => p.z,
+        };
+        if (x / self.width).floor().rem_euclid(2.0) < 1.0 {
+            self.c1
+        } else {
+            self.c2
+        }
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, *self)
+    }
+
+    fn describe(&self) -> Result<MaterialDesc, &'static str> {
+        Ok(MaterialDesc::Stripes {
+            c1: self.c1,
+            c2: self.c2,
+            width: self.width,
+            axis: self.axis,
+        })
+    }
+}
@@ -0,0 +1,66 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::sdf::{SDFHitInfo, SDF};
+use crate::vec3::{self, Float, Vec3f};
+
+mod deformation;
+mod displacement;
+mod offset;
+mod repetition;
+
+pub use deformation::{Bend, DisplacementField, Twist, Warp};
+pub use displacement::Displacement;
+pub use offset::{Elongation, Round, Shell};
+pub use repetition::{Mirror, RadialRepetition, Repetition};
+
+#[cfg(feature = "python")]
+pub(crate) use deformation::{bend, twist, warp};
+#[cfg(feature = "python")]
+pub(crate) use displacement::displace;
+#[cfg(feature = "python")]
+pub(crate) use offset::{elongate, round_, shell};
+#[cfg(feature = "python")]
+pub(crate) use repetition::{mirror, repeat, repeat_radial};
+
+/// Hit info of a modified SDF, the surface coordinates are taken from the
+/// wrapped SDF whenever `p` is also inside it.
+fn hit_with_distance(sdf: &dyn SDF, p: Vec3f, distance: Float) -> Option<SDFHitInfo> {
+    if distance >= 0.0 {
+        return None;
+    }
+
+    let (u, v, w) = sdf.hit(p).map_or((0.0, 0.0, 0.0), |h| (h.u, h.v, h.w));
+    Some(SDFHitInfo { distance, u, v, w })
+}
+
+/// Bounding box of the eight corners of a box after mapping them by `f`.
+fn map_bounding_box<F>((min, max): (Vec3f, Vec3f), f: F) -> (Vec3f, Vec3f)
+where
+    F: Fn(Vec3f) -> Vec3f,
+{
+    [
+        Vec3f::new(min.x, min.y, min.z),
+        Vec3f::new(min.x, min.y, max.z),
+        Vec3f::new(min.x, max.y, min.z),
+        Vec3f::new(min.x, max.y, max.z),
+        Vec3f::new(max.x, min.y, min.z),
+        Vec3f::new(max.x, min.y, max.z),
+        Vec3f::new(max.x, max.y, min.z),
+        Vec3f::new(max.x, max.y, max.z),
+    ]
+    .into_iter()
+    .map(f)
+    .fold(
+        (
+            Vec3f::new(Float::INFINITY, Float::INFINITY, Float::INFINITY),
+            Vec3f::new(
+                Float::NEG_INFINITY,
+                Float::NEG_INFINITY,
+                Float::NEG_INFINITY,
+            ),
+        ),
+        |(min, max), p| (vec3::minimum(min, p), vec3::maximum(max, p)),
+    )
+}
@@ -0,0 +1,472 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(feature = "python")]
+use std::sync::Arc;
+
+#[cfg(feature = "python")]
+use numpy::{ndarray::Array4, AllowTypeChange, IntoPyArray, PyArray4, PyArrayLike4};
+#[cfg(feature = "python")]
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::description::SDFDesc;
+use crate::modifier::{hit_with_distance, map_bounding_box};
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+use crate::sdf::{DynSDF, SDFHitInfo, SDF};
+use crate::solid_geometry::proj_vector_on_plane;
+use crate::vec3::{self, Float, Vec3f};
+
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (sdf, k, axis = (0.0, 0.0, 1.0), origin = (0.0, 0.0, 0.0)))]
+pub fn twist(
+    sdf: DynSDF,
+    k: Float,
+    axis: (Float, Float, Float),
+    origin: (Float, Float, Float),
+) -> PyResult<DynSDF> {
+    let twist: Arc<dyn SDF> =
+        Arc::new(Twist::new(sdf, k, axis.into(), origin.into()).map_err(PyValueError::new_err)?);
+    Ok(DynSDF::from(twist))
+}
+
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (sdf, k, axis = (1.0, 0.0, 0.0), normal = (0.0, 1.0, 0.0), origin = (0.0, 0.0, 0.0)))]
+pub fn bend(
+    sdf: DynSDF,
+    k: Float,
+    axis: (Float, Float, Float),
+    normal: (Float, Float, Float),
+    origin: (Float, Float, Float),
+) -> PyResult<DynSDF> {
+    let bend: Arc<dyn SDF> = Arc::new(
+        Bend::new(sdf, k, axis.into(), normal.into(), origin.into())
+            .map_err(PyValueError::new_err)?,
+    );
+    Ok(DynSDF::from(bend))
+}
+
+#[cfg(feature = "python")]
+#[pyfunction]
+#[allow(clippy::needless_pass_by_value)]
+pub fn warp(
+    sdf: DynSDF,
+    field: PyArrayLike4<'_, Float, AllowTypeChange>,
+    min: (Float, Float, Float),
+    max: (Float, Float, Float),
+) -> PyResult<DynSDF> {
+    let field = field.as_array();
+    let (nx, ny, nz, c) = field.dim();
+    if c != 3 {
+        return Err(PyValueError::new_err(
+            "field should be of shape (X, Y, Z, 3)",
+        ));
+    }
+
+    let displacements = field
+        .rows()
+        .into_iter()
+        .map(|row| Vec3f::try_from(row.to_vec()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(PyValueError::new_err)?;
+    let field = DisplacementField::new(displacements, (nx, ny, nz), min.into(), max.into())
+        .map_err(PyValueError::new_err)?;
+    let warp: Arc<dyn SDF> = Arc::new(Warp::new(sdf, field));
+    Ok(DynSDF::from(warp))
+}
+
+/**
+ * Twist a SDF about an axis
+ *
+ * A point at height `t` along the axis is rotated by `k * t` radians. The
+ * mapping is a shear of `s = k * r` at distance `r` from the axis, so that
+ * the distance is divided by its Lipschitz constant `(s + sqrt(s^2 + 4)) / 2`
+ * with the largest `r`.
+ */
+#[allow(missing_debug_implementations)]
+pub struct Twist {
+    sdf: DynSDF,
+    k: Float,
+    axis: Vec3f,
+    origin: Vec3f,
+    lipschitz: Float,
+    bounding_box: (Vec3f, Vec3f),
+}
+
+impl Twist {
+    /// Twist `sdf` by `k` radians per unit along `axis` through `origin`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `axis` is zero.
+    pub fn new(sdf: DynSDF, k: Float, axis: Vec3f, origin: Vec3f) -> Result<Twist, &'static str> {
+        if axis.norm() == 0.0 {
+            return Err("axis should not be zero");
+        }
+
+        // twisting keeps both the height and the distance to the axis
+        let axis = axis.normalize();
+        let (t, r) = cylinder_extent(sdf.bounding_box(), origin, axis);
+        let s = k.abs() * r;
+        Ok(Self {
+            sdf,
+            k,
+            axis,
+            origin,
+            lipschitz: Float::midpoint(s, Float::sqrt(s * s + 4.0)),
+            bounding_box: cylinder_bounding_box(origin, axis, t, r),
+        })
+    }
+
+    fn untwist(&self, p: Vec3f) -> Vec3f {
+        let d = p - self.origin;
+        let t = vec3::dot(d, self.axis);
+        self.origin + vec3::rotate(d, self.axis, -self.k * t)
+    }
+}
+
+impl SDF for Twist {
+    fn distance(&self, p: Vec3f) -> Float {
+        self.sdf.distance(self.untwist(p)) / self.lipschitz
+    }
+
+    fn hit(&self, p: Vec3f) -> Option<SDFHitInfo> {
+        let q = self.untwist(p);
+        hit_with_distance(&self.sdf, q, self.sdf.distance(q) / self.lipschitz)
+    }
+
+    fn bounding_box(&self) -> (Vec3f, Vec3f) {
+        self.bounding_box
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::function(
+            py,
+            "twist",
+            (self.sdf.clone(), self.k, self.axis, self.origin),
+        )
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        Ok(SDFDesc::Twist {
+            sdf: Box::new(self.sdf.describe()?),
+            k: self.k,
+            axis: self.axis,
+            origin: self.origin,
+        })
+    }
+}
+
+/**
+ * Bend a SDF laid along `axis` toward `normal` with curvature `k`
+ *
+ * A point at `x` along the axis is rotated by `k * x` radians in the plane
+ * of `axis` and `normal`, about the line through `origin`. The distance is
+ * divided by the Lipschitz constant `1 + k * r`, where `r` is the largest
+ * distance to that line.
+ */
+#[allow(missing_debug_implementations)]
+pub struct Bend {
+    sdf: DynSDF,
+    k: Float,
+    axis: Vec3f,
+    normal: Vec3f,
+    origin: Vec3f,
+    lipschitz: Float,
+    bounding_box: (Vec3f, Vec3f),
+}
+
+impl Bend {
+    /// Bend `sdf` by `k` radians per unit along `axis`, towards `normal`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `axis` and `normal` are zero or parallel.
+    pub fn new(
+        sdf: DynSDF,
+        k: Float,
+        axis: Vec3f,
+        normal: Vec3f,
+        origin: Vec3f,
+    ) -> Result<Bend, &'static str> {
+        let axis = axis.normalize();
+        let normal = proj_vector_on_plane(normal, axis).normalize();
+        if axis.norm() == 0.0 || normal.norm() == 0.0 {
+            return Err("axis and normal should be non-zero and not parallel");
+        }
+
+        // bending keeps both the height and the distance to the binormal line
+        let binormal = vec3::cross(axis, normal);
+        let (t, r) = cylinder_extent(sdf.bounding_box(), origin, binormal);
+        Ok(Self {
+            sdf,
+            k,
+            axis,
+            normal,
+            origin,
+            lipschitz: 1.0 + k.abs() * r,
+            bounding_box: cylinder_bounding_box(origin, binormal, t, r),
+        })
+    }
+
+    fn unbend(&self, p: Vec3f) -> Vec3f {
+        let d = p - self.origin;
+        let (x, y) = (vec3::dot(d, self.axis), vec3::dot(d, self.normal));
+        let rest = d - self.axis * x - self.normal * y;
+        let (s, c) = (self.k * x).sin_cos();
+        self.origin + self.axis * (c * x - s * y) + self.normal * (s * x + c * y) + rest
+    }
+}
+
+impl SDF for Bend {
+    fn distance(&self, p: Vec3f) -> Float {
+        self.sdf.distance(self.unbend(p)) / self.lipschitz
+    }
+
+    fn hit(&self, p: Vec3f) -> Option<SDFHitInfo> {
+        let q = self.unbend(p);
+        hit_with_distance(&self.sdf, q, self.sdf.distance(q) / self.lipschitz)
+    }
+
+    fn bounding_box(&self) -> (Vec3f, Vec3f) {
+        self.bounding_box
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let args = (
+            self.sdf.clone(),
+            self.k,
+            self.axis,
+            self.normal,
+            self.origin,
+        );
+        pickle::function(py, "bend", args)
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        Ok(SDFDesc::Bend {
+            sdf: Box::new(self.sdf.describe()?),
+            k: self.k,
+            axis: self.axis,
+            normal: self.normal,
+            origin: self.origin,
+        })
+    }
+}
+
+/**
+ * Warp a SDF with a displacement field
+ *
+ * The SDF is evaluated at `p + D(p)`, where `D` is trilinearly interpolated
+ * from a regular grid and clamped outside of it. The distance is divided by
+ * an upper bound of the Lipschitz constant `1 + |grad D|`.
+ */
+#[allow(missing_debug_implementations)]
+pub struct Warp {
+    sdf: DynSDF,
+    field: DisplacementField,
+    lipschitz: Float,
+    bounding_box: (Vec3f, Vec3f),
+}
+
+impl Warp {
+    /// Warp `sdf` by `field`.
+    #[must_use]
+    pub fn new(sdf: DynSDF, field: DisplacementField) -> Warp {
+        let (min, max) = sdf.bounding_box();
+        let m = field.max_norm();
+        let lipschitz = 1.0 + field.max_gradient();
+        Self {
+            sdf,
+            field,
+            lipschitz,
+            bounding_box: (min - m, max + m),
+        }
+    }
+}
+
+impl SDF for Warp {
+    fn distance(&self, p: Vec3f) -> Float {
+        self.sdf.distance(p + self.field.at(p)) / self.lipschitz
+    }
+
+    fn hit(&self, p: Vec3f) -> Option<SDFHitInfo> {
+        let q = p + self.field.at(p);
+        hit_with_distance(&self.sdf, q, self.sdf.distance(q) / self.lipschitz)
+    }
+
+    fn bounding_box(&self) -> (Vec3f, Vec3f) {
+        self.bounding_box
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let field = self.field.to_array(py);
+        let args = (self.sdf.clone(), field, self.field.min, self.field.max);
+        pickle::function(py, "warp", args)
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        Ok(SDFDesc::Warp {
+            sdf: Box::new(self.sdf.describe()?),
+            shape: self.field.shape,
+            field: self.field.values.clone(),
+            min: self.field.min,
+            max: self.field.max,
+        })
+    }
+}
+
+/**
+ * Vector field sampled on a regular grid
+ */
+#[derive(Debug)]
+pub struct DisplacementField {
+    values: Vec<Vec3f>, // in C order of (x, y, z)
+    shape: (usize, usize, usize),
+    min: Vec3f,
+    max: Vec3f,
+    step: Vec3f,
+}
+
+impl DisplacementField {
+    /// Samples `values` in C order of `shape`, spanning `min` to `max`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when an axis has less than two samples, `values` does
+    /// not match `shape` or `max` is not greater than `min`.
+    pub fn new(
+        values: Vec<Vec3f>,
+        shape: (usize, usize, usize),
+        min: Vec3f,
+        max: Vec3f,
+    ) -> Result<DisplacementField, &'static str> {
+        let (nx, ny, nz) = shape;
+        if nx < 2 || ny < 2 || nz < 2 {
+            return Err("field requires at least two samples along each axis");
+        }
+
+        if values.len() != nx * ny * nz {
+            return Err("field values do not match its shape");
+        }
+
+        if max.x <= min.x || max.y <= min.y || max.z <= min.z {
+            return Err("field max should be greater than min");
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let step =
+            (max - min) / Vec3f::new((nx - 1) as Float, (ny - 1) as Float, (nz - 1) as Float);
+        Ok(Self {
+            values,
+            shape,
+            min,
+            max,
+            step,
+        })
+    }
+
+    /// The values as an array of shape `(X, Y, Z, 3)`.
+    #[cfg(feature = "python")]
+    fn to_array<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray4<Float>> {
+        let (nx, ny, nz) = self.shape;
+        let values = self.values.iter().flat_map(|v| [v.x, v.y, v.z]).collect();
+        Array4::from_shape_vec((nx, ny, nz, 3), values)
+            .unwrap()
+            .into_pyarray(py)
+    }
+
+    fn get(&self, i: usize, j: usize, k: usize) -> Vec3f {
+        let (_, ny, nz) = self.shape;
+        self.values
+            .get((i * ny + j) * nz + k)
+            .copied()
+            .unwrap_or(Vec3f::new(0.0, 0.0, 0.0))
+    }
+
+    /// Trilinear interpolation, clamped to the grid.
+    #[must_use]
+    pub fn at(&self, p: Vec3f) -> Vec3f {
+        let (nx, ny, nz) = self.shape;
+        let g = (p - self.min) / self.step;
+        #[allow(clippy::cast_precision_loss)]
+        let locate = |x: Float, n: usize| {
+            let x = x.clamp(0.0, (n - 1) as Float);
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let i = (x.floor() as usize).min(n - 2);
+            (i, x - i as Float)
+        };
+        let ((i, fx), (j, fy), (k, fz)) = (locate(g.x, nx), locate(g.y, ny), locate(g.z, nz));
+
+        let lerp = |a: Vec3f, b: Vec3f, t: Float| a * (1.0 - t) + b * t;
+        let c00 = lerp(self.get(i, j, k), self.get(i + 1, j, k), fx);
+        let c01 = lerp(self.get(i, j, k + 1), self.get(i + 1, j, k + 1), fx);
+        let c10 = lerp(self.get(i, j + 1, k), self.get(i + 1, j + 1, k), fx);
+        let c11 = lerp(self.get(i, j + 1, k + 1), self.get(i + 1, j + 1, k + 1), fx);
+        lerp(lerp(c00, c10, fy), lerp(c01, c11, fy), fz)
+    }
+
+    fn max_norm(&self) -> Float {
+        self.values.iter().map(|v| v.norm()).fold(0.0, Float::max)
+    }
+
+    /// Upper bound of the spectral norm of the jacobian, by the norms of the
+    /// largest finite differences along each axis.
+    fn max_gradient(&self) -> Float {
+        let (nx, ny, nz) = self.shape;
+        let (mut gx, mut gy, mut gz): (Float, Float, Float) = (0.0, 0.0, 0.0);
+        for i in 0..nx {
+            for j in 0..ny {
+                for k in 0..nz {
+                    let v = self.get(i, j, k);
+                    if i + 1 < nx {
+                        gx = gx.max((self.get(i + 1, j, k) - v).norm() / self.step.x);
+                    }
+                    if j + 1 < ny {
+                        gy = gy.max((self.get(i, j + 1, k) - v).norm() / self.step.y);
+                    }
+                    if k + 1 < nz {
+                        gz = gz.max((self.get(i, j, k + 1) - v).norm() / self.step.z);
+                    }
+                }
+            }
+        }
+        Float::sqrt(gx * gx + gy * gy + gz * gz)
+    }
+}
+
+/// Range of heights along `axis` and the largest distance to it over a box.
+fn cylinder_extent(
+    bounding_box: (Vec3f, Vec3f),
+    origin: Vec3f,
+    axis: Vec3f,
+) -> ((Float, Float), Float) {
+    let (min, max) = map_bounding_box(bounding_box, |p| {
+        let t = vec3::dot(p - origin, axis);
+        let r = proj_vector_on_plane(p - origin, axis).norm();
+        Vec3f::new(t, r, 0.0)
+    });
+    ((min.x, max.x), max.y)
+}
+
+/// Bounding box of a cylinder around `axis`, spanning the heights `t`.
+fn cylinder_bounding_box(
+    origin: Vec3f,
+    axis: Vec3f,
+    t: (Float, Float),
+    r: Float,
+) -> (Vec3f, Vec3f) {
+    let (a, b) = (origin + axis * t.0, origin + axis * t.1);
+    let e = Vec3f::new(
+        Float::sqrt(Float::max(1.0 - axis.x * axis.x, 0.0)),
+        Float::sqrt(Float::max(1.0 - axis.y * axis.y, 0.0)),
+        Float::sqrt(Float::max(1.0 - axis.z * axis.z, 0.0)),
+    ) * r;
+    (vec3::minimum(a, b) - e, vec3::maximum(a, b) + e)
+}
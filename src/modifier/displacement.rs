@@ -0,0 +1,134 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(feature = "python")]
+use std::sync::Arc;
+
+#[cfg(feature = "python")]
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::description::SDFDesc;
+use crate::modifier::hit_with_distance;
+#[cfg(feature = "python")]
+use crate::noise::NoiseKind;
+use crate::noise::{self, Noise};
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+use crate::sdf::{DynSDF, SDFHitInfo, SDF};
+use crate::vec3::{Float, Vec3f};
+
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (sdf, amplitude, frequency = 1.0, octaves = 1, seed = 0, kind = "perlin"))]
+pub fn displace(
+    sdf: DynSDF,
+    amplitude: Float,
+    frequency: Float,
+    octaves: u32,
+    seed: u64,
+    kind: &str,
+) -> PyResult<DynSDF> {
+    let kind: NoiseKind = kind
+        .try_into()
+        .map_err(|()| PyValueError::new_err("Invalid noise kind"))?;
+
+    let noise = Noise::new(kind, seed);
+    let displacement: Arc<dyn SDF> = Arc::new(
+        Displacement::new(sdf, noise, amplitude, frequency, octaves)
+            .map_err(PyValueError::new_err)?,
+    );
+    Ok(DynSDF::from(displacement))
+}
+
+/**
+ * Perturb the surface of a SDF with fBm noise
+ *
+ * The surface moves by at most `amplitude` in either direction. The result is
+ * no longer an exact distance, but keeps a valid bound as long as
+ * `amplitude * frequency` is small.
+ */
+#[allow(missing_debug_implementations)]
+pub struct Displacement {
+    sdf: DynSDF,
+    noise: Noise,
+    amplitude: Float,
+    frequency: Float,
+    octaves: u32,
+    bounding_box: (Vec3f, Vec3f),
+}
+
+impl Displacement {
+    /// Perturb `sdf` by `noise` at `frequency`, summed over `octaves`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `amplitude` is negative, `frequency` is not
+    /// positive, or `octaves` is not within `1..=MAX_OCTAVES`.
+    pub fn new(
+        sdf: DynSDF,
+        noise: Noise,
+        amplitude: Float,
+        frequency: Float,
+        octaves: u32,
+    ) -> Result<Displacement, &'static str> {
+        if amplitude < 0.0 {
+            return Err("amplitude should not be negative");
+        }
+
+        if frequency <= 0.0 {
+            return Err("frequency should be positive");
+        }
+
+        noise::check_octaves(octaves)?;
+
+        let (min, max) = sdf.bounding_box();
+        Ok(Self {
+            sdf,
+            noise,
+            amplitude,
+            frequency,
+            octaves,
+            bounding_box: (min - amplitude, max + amplitude),
+        })
+    }
+}
+
+impl SDF for Displacement {
+    fn distance(&self, p: Vec3f) -> Float {
+        let noise = self.noise.fbm(p * self.frequency, self.octaves);
+        self.sdf.distance(p) + self.amplitude * noise
+    }
+
+    fn hit(&self, p: Vec3f) -> Option<SDFHitInfo> {
+        hit_with_distance(&self.sdf, p, self.distance(p))
+    }
+
+    fn bounding_box(&self) -> (Vec3f, Vec3f) {
+        self.bounding_box
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let args = (
+            self.sdf.clone(),
+            self.amplitude,
+            self.frequency,
+            self.octaves,
+            self.noise.seed(),
+            self.noise.kind().as_str(),
+        );
+        pickle::function(py, "displace", args)
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        Ok(SDFDesc::Displace {
+            sdf: Box::new(self.sdf.describe()?),
+            amplitude: self.amplitude,
+            frequency: self.frequency,
+            octaves: self.octaves,
+            seed: self.noise.seed(),
+            kind: self.noise.kind(),
+        })
+    }
+}
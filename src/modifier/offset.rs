@@ -0,0 +1,217 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(feature = "python")]
+use std::sync::Arc;
+
+#[cfg(feature = "python")]
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::description::SDFDesc;
+use crate::modifier::hit_with_distance;
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+use crate::sdf::{DynSDF, SDFHitInfo, SDF};
+use crate::vec3::{self, Float, Vec3f};
+
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "round")]
+pub fn round_(sdf: DynSDF, r: Float) -> PyResult<DynSDF> {
+    let round: Arc<dyn SDF> = Arc::new(Round::new(sdf, r).map_err(PyValueError::new_err)?);
+    Ok(DynSDF::from(round))
+}
+
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn shell(sdf: DynSDF, thickness: Float) -> PyResult<DynSDF> {
+    let shell: Arc<dyn SDF> = Arc::new(Shell::new(sdf, thickness).map_err(PyValueError::new_err)?);
+    Ok(DynSDF::from(shell))
+}
+
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn elongate(sdf: DynSDF, h: (Float, Float, Float)) -> PyResult<DynSDF> {
+    let elongation: Arc<dyn SDF> =
+        Arc::new(Elongation::new(sdf, h.into()).map_err(PyValueError::new_err)?);
+    Ok(DynSDF::from(elongation))
+}
+
+/**
+ * Offset the surface of a SDF outward by `r`
+ */
+#[allow(missing_debug_implementations)]
+pub struct Round {
+    sdf: DynSDF,
+    r: Float,
+    bounding_box: (Vec3f, Vec3f),
+}
+
+impl Round {
+    /// Round `sdf` by `r`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `r` is negative.
+    pub fn new(sdf: DynSDF, r: Float) -> Result<Round, &'static str> {
+        if r < 0.0 {
+            return Err("radius should not be negative");
+        }
+
+        let (min, max) = sdf.bounding_box();
+        Ok(Self {
+            sdf,
+            r,
+            bounding_box: (min - r, max + r),
+        })
+    }
+}
+
+impl SDF for Round {
+    fn distance(&self, p: Vec3f) -> Float {
+        self.sdf.distance(p) - self.r
+    }
+
+    fn hit(&self, p: Vec3f) -> Option<SDFHitInfo> {
+        hit_with_distance(&self.sdf, p, self.distance(p))
+    }
+
+    fn bounding_box(&self) -> (Vec3f, Vec3f) {
+        self.bounding_box
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::function(py, "round", (self.sdf.clone(), self.r))
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        Ok(SDFDesc::Round {
+            sdf: Box::new(self.sdf.describe()?),
+            r: self.r,
+        })
+    }
+}
+
+/**
+ * Hollow a SDF into a shell of `thickness` centered on its surface
+ */
+#[allow(missing_debug_implementations)]
+pub struct Shell {
+    sdf: DynSDF,
+    thickness: Float,
+    bounding_box: (Vec3f, Vec3f),
+}
+
+impl Shell {
+    /// Hollow `sdf` into a shell of `thickness`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `thickness` is not positive.
+    pub fn new(sdf: DynSDF, thickness: Float) -> Result<Shell, &'static str> {
+        if thickness <= 0.0 {
+            return Err("thickness should be positive");
+        }
+
+        let (min, max) = sdf.bounding_box();
+        let half = thickness / 2.0;
+        Ok(Self {
+            sdf,
+            thickness,
+            bounding_box: (min - half, max + half),
+        })
+    }
+}
+
+impl SDF for Shell {
+    fn distance(&self, p: Vec3f) -> Float {
+        self.sdf.distance(p).abs() - self.thickness / 2.0
+    }
+
+    fn hit(&self, p: Vec3f) -> Option<SDFHitInfo> {
+        hit_with_distance(&self.sdf, p, self.distance(p))
+    }
+
+    fn bounding_box(&self) -> (Vec3f, Vec3f) {
+        self.bounding_box
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::function(py, "shell", (self.sdf.clone(), self.thickness))
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        Ok(SDFDesc::Shell {
+            sdf: Box::new(self.sdf.describe()?),
+            thickness: self.thickness,
+        })
+    }
+}
+
+/**
+ * Stretch a SDF by `2 * h` along each axis, splitting it at its center
+ */
+#[allow(missing_debug_implementations)]
+pub struct Elongation {
+    sdf: DynSDF,
+    h: Vec3f,
+    center: Vec3f,
+    bounding_box: (Vec3f, Vec3f),
+}
+
+impl Elongation {
+    /// Elongate `sdf` by `h` on each side of its center.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `h` is negative.
+    pub fn new(sdf: DynSDF, h: Vec3f) -> Result<Elongation, &'static str> {
+        if h.x < 0.0 || h.y < 0.0 || h.z < 0.0 {
+            return Err("elongation should not be negative");
+        }
+
+        let (min, max) = sdf.bounding_box();
+        Ok(Self {
+            sdf,
+            h,
+            center: (min + max) / 2.0,
+            bounding_box: (min - h, max + h),
+        })
+    }
+
+    fn shrink(&self, p: Vec3f) -> Vec3f {
+        let d = p - self.center;
+        let zero = Vec3f::new(0.0, 0.0, 0.0);
+        let clamped = vec3::minimum(vec3::maximum(d, zero - self.h), self.h);
+        p - clamped
+    }
+}
+
+impl SDF for Elongation {
+    fn distance(&self, p: Vec3f) -> Float {
+        self.sdf.distance(self.shrink(p))
+    }
+
+    fn hit(&self, p: Vec3f) -> Option<SDFHitInfo> {
+        self.sdf.hit(self.shrink(p))
+    }
+
+    fn bounding_box(&self) -> (Vec3f, Vec3f) {
+        self.bounding_box
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::function(py, "elongate", (self.sdf.clone(), self.h))
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        Ok(SDFDesc::Elongate {
+            sdf: Box::new(self.sdf.describe()?),
+            h: self.h,
+        })
+    }
+}
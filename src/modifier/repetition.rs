@@ -0,0 +1,370 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::vec3::consts::PI;
+#[cfg(feature = "python")]
+use std::sync::Arc;
+
+#[cfg(feature = "python")]
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::description::SDFDesc;
+use crate::modifier::map_bounding_box;
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+use crate::sdf::{DynSDF, SDFHitInfo, SDF};
+use crate::solid_geometry::proj_vector_on_plane;
+use crate::vec3::{self, Float, Vec3f};
+
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (sdf, spacing, counts = None))]
+pub fn repeat(
+    sdf: DynSDF,
+    spacing: (Float, Float, Float),
+    counts: Option<(u32, u32, u32)>,
+) -> PyResult<DynSDF> {
+    let repetition: Arc<dyn SDF> = Arc::new(
+        Repetition::new(sdf, spacing.into(), counts.map(Into::into))
+            .map_err(PyValueError::new_err)?,
+    );
+    Ok(DynSDF::from(repetition))
+}
+
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (sdf, n, axis = (0.0, 0.0, 1.0), center = (0.0, 0.0, 0.0)))]
+pub fn repeat_radial(
+    sdf: DynSDF,
+    n: u32,
+    axis: (Float, Float, Float),
+    center: (Float, Float, Float),
+) -> PyResult<DynSDF> {
+    let repetition: Arc<dyn SDF> = Arc::new(
+        RadialRepetition::new(sdf, n, axis.into(), center.into()).map_err(PyValueError::new_err)?,
+    );
+    Ok(DynSDF::from(repetition))
+}
+
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (sdf, normal, origin = (0.0, 0.0, 0.0)))]
+pub fn mirror(
+    sdf: DynSDF,
+    normal: (Float, Float, Float),
+    origin: (Float, Float, Float),
+) -> PyResult<DynSDF> {
+    let mirror: Arc<dyn SDF> =
+        Arc::new(Mirror::new(sdf, normal.into(), origin.into()).map_err(PyValueError::new_err)?);
+    Ok(DynSDF::from(mirror))
+}
+
+/**
+ * Repeat a SDF on a grid
+ *
+ * Copies are placed at `spacing * i` from the original one, where `i` is
+ * unbounded, or in `0..counts` for a finite repetition. An axis with zero
+ * spacing is not repeated. The SDF should fit in a cell around its center.
+ */
+#[allow(missing_debug_implementations)]
+pub struct Repetition {
+    sdf: DynSDF,
+    spacing: Vec3f,
+    counts: Option<vec3::Vec3<u32>>,
+    last: Option<Vec3f>, // index of the last copy along each axis
+    center: Vec3f,
+    bounding_box: (Vec3f, Vec3f),
+}
+
+impl Repetition {
+    /// Repeat `sdf` every `spacing`, `counts` times when given.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `spacing` is negative or `counts` is not positive.
+    pub fn new(
+        sdf: DynSDF,
+        spacing: Vec3f,
+        counts: Option<vec3::Vec3<u32>>,
+    ) -> Result<Repetition, &'static str> {
+        if spacing.x < 0.0 || spacing.y < 0.0 || spacing.z < 0.0 {
+            return Err("spacing should not be negative");
+        }
+
+        #[allow(clippy::cast_precision_loss, clippy::cast_lossless)]
+        let last = match counts {
+            Some(c) if c.x == 0 || c.y == 0 || c.z == 0 => {
+                return Err("counts should be positive");
+            }
+            Some(c) => Some(Vec3f::new(
+                (c.x - 1) as Float,
+                (c.y - 1) as Float,
+                (c.z - 1) as Float,
+            )),
+            None => None,
+        };
+
+        let (min, max) = sdf.bounding_box();
+        let extent = |s: Float, min: Float, max: Float, last: Option<Float>| match last {
+            _ if s == 0.0 => (min, max),
+            Some(last) => (min, max + s * last),
+            None => (Float::NEG_INFINITY, Float::INFINITY),
+        };
+        let (min_x, max_x) = extent(spacing.x, min.x, max.x, last.map(|l| l.x));
+        let (min_y, max_y) = extent(spacing.y, min.y, max.y, last.map(|l| l.y));
+        let (min_z, max_z) = extent(spacing.z, min.z, max.z, last.map(|l| l.z));
+
+        Ok(Self {
+            sdf,
+            spacing,
+            counts,
+            last,
+            center: (min + max) / 2.0,
+            bounding_box: (
+                Vec3f::new(min_x, min_y, min_z),
+                Vec3f::new(max_x, max_y, max_z),
+            ),
+        })
+    }
+
+    fn to_cell(&self, p: Vec3f) -> Vec3f {
+        let index = |p: Float, c: Float, s: Float, last: Option<Float>| {
+            if s == 0.0 {
+                return 0.0;
+            }
+
+            let i = ((p - c) / s).round();
+            last.map_or(i, |l| i.clamp(0.0, l))
+        };
+
+        let (c, s) = (self.center, self.spacing);
+        let i = Vec3f::new(
+            index(p.x, c.x, s.x, self.last.map(|l| l.x)),
+            index(p.y, c.y, s.y, self.last.map(|l| l.y)),
+            index(p.z, c.z, s.z, self.last.map(|l| l.z)),
+        );
+        p - s * i
+    }
+}
+
+impl SDF for Repetition {
+    fn distance(&self, p: Vec3f) -> Float {
+        self.sdf.distance(self.to_cell(p))
+    }
+
+    fn hit(&self, p: Vec3f) -> Option<SDFHitInfo> {
+        self.sdf.hit(self.to_cell(p))
+    }
+
+    fn bounding_box(&self) -> (Vec3f, Vec3f) {
+        self.bounding_box
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let counts = self.counts.map(|c| (c.x, c.y, c.z));
+        pickle::function(py, "repeat", (self.sdf.clone(), self.spacing, counts))
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        Ok(SDFDesc::Repeat {
+            sdf: Box::new(self.sdf.describe()?),
+            spacing: self.spacing,
+            counts: self.counts,
+        })
+    }
+}
+
+/**
+ * Repeat a SDF `n` times around an axis
+ */
+#[allow(missing_debug_implementations)]
+pub struct RadialRepetition {
+    sdf: DynSDF,
+    n: u32,
+    sector: Float,
+    axis: Vec3f,
+    center: Vec3f,
+    basis: (Vec3f, Vec3f), // the first copy lies along the first vector
+    bounding_box: (Vec3f, Vec3f),
+}
+
+impl RadialRepetition {
+    /// Repeat `sdf` `n` times around `axis` through `center`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `n` or `axis` is zero.
+    pub fn new(
+        sdf: DynSDF,
+        n: u32,
+        axis: Vec3f,
+        center: Vec3f,
+    ) -> Result<RadialRepetition, &'static str> {
+        if n == 0 {
+            return Err("number of copies should be positive");
+        }
+
+        if axis.norm() == 0.0 {
+            return Err("axis should not be zero");
+        }
+
+        let axis = axis.normalize();
+        let (min, max) = sdf.bounding_box();
+        let mut u = proj_vector_on_plane((min + max) / 2.0 - center, axis).normalize();
+        if u.norm() == 0.0 {
+            // the SDF is centered on the axis, pick any perpendicular vector
+            let x = if axis.x.abs() < 0.9 {
+                Vec3f::new(1.0, 0.0, 0.0)
+            } else {
+                Vec3f::new(0.0, 1.0, 0.0)
+            };
+            u = proj_vector_on_plane(x, axis).normalize();
+        }
+        let v = vec3::cross(axis, u);
+
+        #[allow(clippy::cast_precision_loss, clippy::cast_lossless)]
+        let sector = 2.0 * PI / n as Float;
+        let bounding_box = (0..n)
+            .map(|i| {
+                #[allow(clippy::cast_precision_loss, clippy::cast_lossless)]
+                let angle = sector * i as Float;
+                map_bounding_box((min, max), |p| {
+                    center + vec3::rotate(p - center, axis, angle)
+                })
+            })
+            .fold((min, max), |(min, max), (emin, emax)| {
+                (vec3::minimum(min, emin), vec3::maximum(max, emax))
+            });
+
+        Ok(Self {
+            sdf,
+            n,
+            sector,
+            axis,
+            center,
+            basis: (u, v),
+            bounding_box,
+        })
+    }
+
+    fn to_sector(&self, p: Vec3f) -> Vec3f {
+        let d = p - self.center;
+        let angle = Float::atan2(vec3::dot(d, self.basis.1), vec3::dot(d, self.basis.0));
+        let i = (angle / self.sector).round();
+        self.center + vec3::rotate(d, self.axis, -i * self.sector)
+    }
+}
+
+impl SDF for RadialRepetition {
+    fn distance(&self, p: Vec3f) -> Float {
+        self.sdf.distance(self.to_sector(p))
+    }
+
+    fn hit(&self, p: Vec3f) -> Option<SDFHitInfo> {
+        self.sdf.hit(self.to_sector(p))
+    }
+
+    fn bounding_box(&self) -> (Vec3f, Vec3f) {
+        self.bounding_box
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::function(
+            py,
+            "repeat_radial",
+            (self.sdf.clone(), self.n, self.axis, self.center),
+        )
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        Ok(SDFDesc::RepeatRadial {
+            sdf: Box::new(self.sdf.describe()?),
+            n: self.n,
+            axis: self.axis,
+            center: self.center,
+        })
+    }
+}
+
+/**
+ * Mirror a SDF across a plane
+ *
+ * The part on the side the normal points to is kept and reflected to the
+ * other side.
+ */
+#[allow(missing_debug_implementations)]
+pub struct Mirror {
+    sdf: DynSDF,
+    normal: Vec3f,
+    origin: Vec3f,
+    bounding_box: (Vec3f, Vec3f),
+}
+
+impl Mirror {
+    /// Mirror `sdf` across the plane of `normal` through `origin`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `normal` is zero.
+    pub fn new(sdf: DynSDF, normal: Vec3f, origin: Vec3f) -> Result<Mirror, &'static str> {
+        if normal.norm() == 0.0 {
+            return Err("normal should not be zero");
+        }
+
+        let normal = normal.normalize();
+        let (min, max) = sdf.bounding_box();
+        let (mirror_min, mirror_max) = map_bounding_box((min, max), |p| {
+            p - normal * (2.0 * vec3::dot(p - origin, normal))
+        });
+        let bounding_box = (
+            vec3::minimum(min, mirror_min),
+            vec3::maximum(max, mirror_max),
+        );
+
+        Ok(Self {
+            sdf,
+            normal,
+            origin,
+            bounding_box,
+        })
+    }
+
+    fn reflect(&self, p: Vec3f) -> Vec3f {
+        let d = vec3::dot(p - self.origin, self.normal);
+        if d < 0.0 {
+            p - self.normal * (2.0 * d)
+        } else {
+            p
+        }
+    }
+}
+
+impl SDF for Mirror {
+    fn distance(&self, p: Vec3f) -> Float {
+        self.sdf.distance(self.reflect(p))
+    }
+
+    fn hit(&self, p: Vec3f) -> Option<SDFHitInfo> {
+        self.sdf.hit(self.reflect(p))
+    }
+
+    fn bounding_box(&self) -> (Vec3f, Vec3f) {
+        self.bounding_box
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::function(py, "mirror", (self.sdf.clone(), self.normal, self.origin))
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        Ok(SDFDesc::Mirror {
+            sdf: Box::new(self.sdf.describe()?),
+            normal: self.normal,
+            origin: self.origin,
+        })
+    }
+}
@@ -0,0 +1,291 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use crate::vec3::{Float, Vec3f};
+
+/// Kind of gradient noise.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NoiseKind {
+    /// Perlin gradient noise on a cubic lattice.
+    Perlin,
+    /// Simplex noise on a tetrahedral lattice.
+    Simplex,
+}
+
+impl TryFrom<&str> for NoiseKind {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "perlin" | "Perlin" => Ok(Self::Perlin),
+            "simplex" | "Simplex" => Ok(Self::Simplex),
+            _ => Err(()),
+        }
+    }
+}
+
+impl NoiseKind {
+    /// Name of the kind in scene files and Python.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Perlin => "perlin",
+            Self::Simplex => "simplex",
+        }
+    }
+}
+
+/// Most layers summed by `Noise::fbm`, the frequency of the last one is
+/// `2^15` times the first, finer than a single precision coordinate resolves.
+pub const MAX_OCTAVES: u32 = 16;
+
+/// Check that `octaves` is within `1..=MAX_OCTAVES`, for the constructors
+/// taking a number of octaves.
+///
+/// # Errors
+///
+/// Returns an error when `octaves` is zero or above `MAX_OCTAVES`.
+pub fn check_octaves(octaves: u32) -> Result<(), &'static str> {
+    if (1..=MAX_OCTAVES).contains(&octaves) {
+        Ok(())
+    } else {
+        Err("octaves should be between 1 and 16")
+    }
+}
+
+/**
+ * Seeded gradient noise in `[-1, 1]`
+ *
+ * The noise repeats every 256 units along each axis.
+ */
+
+#[derive(Clone, Debug)]
+pub struct Noise {
+    kind: NoiseKind,
+    seed: u64,
+    perm: Vec<u8>,
+}
+
+impl Noise {
+    /// Noise of `kind`, shuffled by `seed`.
+    #[must_use]
+    pub fn new(kind: NoiseKind, seed: u64) -> Self {
+        let mut perm = (0..=255).collect::<Vec<u8>>();
+        perm.shuffle(&mut StdRng::seed_from_u64(seed));
+        Self { kind, seed, perm }
+    }
+
+    /// Kind of the noise.
+    #[must_use]
+    pub fn kind(&self) -> NoiseKind {
+        self.kind
+    }
+
+    /// Seed of the noise.
+    #[must_use]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Noise at `p`, with features about one unit apart.
+    #[must_use]
+    pub fn sample(&self, p: Vec3f) -> Float {
+        // both could slightly exceed the range in rare cases
+        let v = match self.kind {
+            NoiseKind::Perlin => self.perlin(p),
+            NoiseKind::Simplex => self.simplex(p),
+        };
+        v.clamp(-1.0, 1.0)
+    }
+
+    /// Fractal Brownian motion, sums `octaves` layers of noise with doubled
+    /// frequency and halved amplitude, normalized back to `[-1, 1]`. The
+    /// octaves are clamped to `1..=MAX_OCTAVES`.
+    #[must_use]
+    pub fn fbm(&self, p: Vec3f, octaves: u32) -> Float {
+        let (mut sum, mut total) = (0.0, 0.0);
+        let (mut amplitude, mut frequency) = (1.0, 1.0);
+        for _ in 0..octaves.clamp(1, MAX_OCTAVES) {
+            sum += amplitude * self.sample(p * frequency);
+            total += amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+        sum / total
+    }
+
+    fn hash(&self, i: i32) -> i32 {
+        #[allow(clippy::cast_sign_loss)]
+        let i = (i & 255) as usize;
+        self.perm.get(i).copied().map_or(0, i32::from)
+    }
+
+    /// Improved Perlin noise.
+    fn perlin(&self, p: Vec3f) -> Float {
+        let (fx, fy, fz) = (p.x.floor(), p.y.floor(), p.z.floor());
+        let (i, j, k) = (lattice(fx), lattice(fy), lattice(fz));
+        let (x, y, z) = (p.x - fx, p.y - fy, p.z - fz);
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+
+        let a = self.hash(i) + j;
+        let (aa, ab) = (self.hash(a) + k, self.hash(a + 1) + k);
+        let b = self.hash(i + 1) + j;
+        let (ba, bb) = (self.hash(b) + k, self.hash(b + 1) + k);
+
+        let g = |h: i32, x: Float, y: Float, z: Float| perlin_grad(self.hash(h), x, y, z);
+        lerp(
+            lerp(
+                lerp(g(aa, x, y, z), g(ba, x - 1.0, y, z), u),
+                lerp(g(ab, x, y - 1.0, z), g(bb, x - 1.0, y - 1.0, z), u),
+                v,
+            ),
+            lerp(
+                lerp(g(aa + 1, x, y, z - 1.0), g(ba + 1, x - 1.0, y, z - 1.0), u),
+                lerp(
+                    g(ab + 1, x, y - 1.0, z - 1.0),
+                    g(bb + 1, x - 1.0, y - 1.0, z - 1.0),
+                    u,
+                ),
+                v,
+            ),
+            w,
+        )
+    }
+
+    /// Simplex noise, in the formulation of Stefan Gustavson.
+    fn simplex(&self, p: Vec3f) -> Float {
+        const F3: Float = 1.0 / 3.0;
+        const G3: Float = 1.0 / 6.0;
+
+        let s = (p.x + p.y + p.z) * F3;
+        let (fi, fj, fk) = ((p.x + s).floor(), (p.y + s).floor(), (p.z + s).floor());
+        let t = (fi + fj + fk) * G3;
+        let x0 = Vec3f::new(p.x - fi + t, p.y - fj + t, p.z - fk + t);
+
+        // offsets of the second and third corners, in skewed coordinates
+        let ((i1, j1, k1), (i2, j2, k2)) = if x0.x >= x0.y {
+            if x0.y >= x0.z {
+                ((1, 0, 0), (1, 1, 0))
+            } else if x0.x >= x0.z {
+                ((1, 0, 0), (1, 0, 1))
+            } else {
+                ((0, 0, 1), (1, 0, 1))
+            }
+        } else if x0.y < x0.z {
+            ((0, 0, 1), (0, 1, 1))
+        } else if x0.x < x0.z {
+            ((0, 1, 0), (0, 1, 1))
+        } else {
+            ((0, 1, 0), (1, 1, 0))
+        };
+
+        let (i, j, k) = (lattice(fi), lattice(fj), lattice(fk));
+        let corner = |di: i32, dj: i32, dk: i32, offset: Float| {
+            #[allow(clippy::cast_precision_loss, clippy::cast_lossless)]
+            let x = x0 - Vec3f::new(di as Float, dj as Float, dk as Float) + offset;
+            let t = 0.6 - x.dot2();
+            if t < 0.0 {
+                0.0
+            } else {
+                let h = self.hash(i + di + self.hash(j + dj + self.hash(k + dk)));
+                t * t * t * t * simplex_grad(h, x)
+            }
+        };
+
+        32.0 * (corner(0, 0, 0, 0.0)
+            + corner(i1, j1, k1, G3)
+            + corner(i2, j2, k2, 2.0 * G3)
+            + corner(1, 1, 1, 3.0 * G3))
+    }
+}
+
+/// Index of the lattice cell at `floor`, wrapped into the period of the
+/// permutation so that the hashes of neighbouring cells cannot overflow.
+fn lattice(floor: Float) -> i32 {
+    #[allow(clippy::cast_possible_truncation)]
+    let i = floor.rem_euclid(256.0) as i32;
+    i
+}
+
+fn fade(t: Float) -> Float {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: Float, b: Float, t: Float) -> Float {
+    a + t * (b - a)
+}
+
+fn perlin_grad(hash: i32, x: Float, y: Float, z: Float) -> Float {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = match h {
+        0..=3 => y,
+        12 | 14 => x,
+        _ => z,
+    };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+fn simplex_grad(hash: i32, p: Vec3f) -> Float {
+    match hash % 12 {
+        0 => p.x + p.y,
+        1 => -p.x + p.y,
+        2 => p.x - p.y,
+        3 => -p.x - p.y,
+        4 => p.x + p.z,
+        5 => -p.x + p.z,
+        6 => p.x - p.z,
+        7 => -p.x - p.z,
+        8 => p.y + p.z,
+        9 => -p.y + p.z,
+        10 => p.y - p.z,
+        _ => -p.y - p.z,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_large() {
+        // far coordinates and fine octaves once overflowed the lattice indices
+        for kind in [NoiseKind::Perlin, NoiseKind::Simplex] {
+            let noise = Noise::new(kind, 0);
+            for p in [
+                Vec3f::new(3e9, 0.5, 0.5),
+                Vec3f::new(-3e9, 1e12, 0.5),
+                Vec3f::new(0.25, -1e30, 2.5),
+            ] {
+                assert!((-1.0..=1.0).contains(&noise.sample(p)), "{kind:?} at {p:?}");
+            }
+
+            let p = Vec3f::new(2.0, 0.5, 0.5);
+            assert!((-1.0..=1.0).contains(&noise.fbm(p, 40)));
+            assert!((noise.fbm(p, 40) - noise.fbm(p, MAX_OCTAVES)).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_period() {
+        let noise = Noise::new(NoiseKind::Perlin, 1);
+        let p = Vec3f::new(-3.3, 1.7, 12.1);
+        let q = p + Vec3f::new(256.0, -512.0, 0.0);
+        assert!((noise.sample(p) - noise.sample(q)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_octaves() {
+        assert!(check_octaves(0).is_err());
+        assert!(check_octaves(1).is_ok());
+        assert!(check_octaves(MAX_OCTAVES).is_ok());
+        assert!(check_octaves(MAX_OCTAVES + 1).is_err());
+    }
+}
@@ -4,27 +4,105 @@
 
 use std::sync::Arc;
 
-use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::{exceptions::PyTypeError, prelude::*};
 
+use crate::description::ObjectDesc;
 use crate::material::{DynMaterial, Material};
-use crate::sdf::{DynSDF, SDF};
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+use crate::sdf::{DynSDF, SDFHitInfo, SDF};
 use crate::vec3::Vec3f;
 
+/// Something drawn in a scene, locating points and shading them.
 pub trait Object: Send + Sync {
-    fn hit(&self, p: Vec3f) -> Option<Vec3f>;
+    /// Locate `p` on the object without shading it, `None` on a miss.
+    fn hit_info(&self, p: Vec3f) -> Option<SDFHitInfo>;
+
+    /// Locate each of `ps` on the object into `out`.
+    fn hit_info_batch(&self, ps: &[Vec3f], out: &mut [Option<SDFHitInfo>]) {
+        for (p, out) in ps.iter().zip(out) {
+            *out = self.hit_info(*p);
+        }
+    }
+
+    /// Write the channels of a hit into `out`, returns its opacity.
+    fn shade(&self, p: Vec3f, info: SDFHitInfo, out: &mut [f32]) -> f32;
+
+    /// Write the channels of a batch of hits into `out`, `channels` values
+    /// per hit.
+    fn shade_batch(&self, ps: &[Vec3f], infos: &[SDFHitInfo], out: &mut [f32]) {
+        let chunks = out.chunks_exact_mut(self.channels());
+        for ((p, info), out) in ps.iter().zip(infos).zip(chunks) {
+            self.shade(*p, *info, out);
+        }
+    }
+
+    /// Write the channels of a hit at `p` into `out`, returns the opacity of
+    /// the hit or `None` on a miss.
+    fn hit(&self, p: Vec3f, out: &mut [f32]) -> Option<f32> {
+        let info = self.hit_info(p)?;
+        Some(self.shade(p, info, out))
+    }
+
+    /// Number of channels written by `shade`.
+    fn channels(&self) -> usize;
+
+    /// Corners of a box containing the whole object.
     fn bounding_box(&self) -> (Vec3f, Vec3f);
+
+    /// Recipe rebuilding the object from Python, for pickling.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the object cannot be pickled, the default for
+    /// objects defined outside of sdflit.
+    #[cfg(feature = "python")]
+    fn reduce(&self, _py: Python<'_>) -> PyResult<Reduce> {
+        Err(PyTypeError::new_err("object cannot be pickled"))
+    }
+
+    /// Plain data describing the object, for scene files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the object cannot be saved, the default for
+    /// objects defined outside of sdflit.
+    fn describe(&self) -> Result<ObjectDesc, &'static str> {
+        Err("object cannot be saved to a scene file")
+    }
 }
 
 /**
  * Wrapper for object
  */
 
-#[pyclass]
-#[pyo3[name="Object"]]
+#[cfg_attr(feature = "python", pyclass(module = "sdflit", name = "Object"))]
 #[derive(Clone)]
 #[allow(missing_debug_implementations)]
 pub struct DynObject(Arc<dyn Object>);
 
+#[cfg(feature = "python")]
+#[pymethods]
+impl DynObject {
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        self.0.reduce(py)
+    }
+}
+
+impl DynObject {
+    /// Wrap `object`, to be added to scenes.
+    pub fn new(object: impl Object + 'static) -> Self {
+        Self(Arc::new(object))
+    }
+}
+
+impl From<Arc<dyn Object>> for DynObject {
+    fn from(x: Arc<dyn Object>) -> Self {
+        Self(x)
+    }
+}
+
 impl From<DynObject> for Arc<dyn Object> {
     fn from(val: DynObject) -> Self {
         val.0
@@ -32,20 +110,49 @@ impl From<DynObject> for Arc<dyn Object> {
 }
 
 impl Object for DynObject {
-    fn hit(&self, p: Vec3f) -> Option<Vec3f> {
-        self.0.hit(p)
+    fn hit_info(&self, p: Vec3f) -> Option<SDFHitInfo> {
+        self.0.hit_info(p)
+    }
+
+    fn hit_info_batch(&self, ps: &[Vec3f], out: &mut [Option<SDFHitInfo>]) {
+        self.0.hit_info_batch(ps, out);
+    }
+
+    fn shade(&self, p: Vec3f, info: SDFHitInfo, out: &mut [f32]) -> f32 {
+        self.0.shade(p, info, out)
+    }
+
+    fn shade_batch(&self, ps: &[Vec3f], infos: &[SDFHitInfo], out: &mut [f32]) {
+        self.0.shade_batch(ps, infos, out);
+    }
+
+    fn hit(&self, p: Vec3f, out: &mut [f32]) -> Option<f32> {
+        self.0.hit(p, out)
+    }
+
+    fn channels(&self) -> usize {
+        self.0.channels()
     }
 
     fn bounding_box(&self) -> (Vec3f, Vec3f) {
         self.0.bounding_box()
     }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        self.0.reduce(py)
+    }
+
+    fn describe(&self) -> Result<ObjectDesc, &'static str> {
+        self.0.describe()
+    }
 }
 
 /**
  * Wrap a SDF into an Object
  */
 
-#[pyclass]
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
 #[derive(Clone)]
 #[allow(missing_debug_implementations)]
 pub struct SDFObject {
@@ -53,27 +160,70 @@ pub struct SDFObject {
     material: Arc<dyn Material>,
 }
 
-#[pymethods]
 impl SDFObject {
-    #[new]
+    /// Draw the inside of `sdf`, shaded by `material`.
+    #[must_use]
     pub fn new(sdf: DynSDF, material: DynMaterial) -> Self {
         Self {
             sdf,
             material: material.into(),
         }
     }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl SDFObject {
+    #[new]
+    fn __new__(sdf: DynSDF, material: DynMaterial) -> Self {
+        Self::new(sdf, material)
+    }
 
-    pub fn into(&self) -> DynObject {
+    fn into(&self) -> DynObject {
         DynObject(Arc::new(self.clone()))
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let material = DynMaterial::from(self.material.clone());
+        pickle::new::<Self>(py, (self.sdf.clone(), material))
+    }
 }
 
 impl Object for SDFObject {
-    fn hit(&self, p: Vec3f) -> Option<Vec3f> {
-        self.sdf.hit(p).map(|info| self.material.hit(info))
+    fn hit_info(&self, p: Vec3f) -> Option<SDFHitInfo> {
+        self.sdf.hit(p)
+    }
+
+    fn hit_info_batch(&self, ps: &[Vec3f], out: &mut [Option<SDFHitInfo>]) {
+        self.sdf.hit_batch(ps, out);
+    }
+
+    fn shade(&self, p: Vec3f, info: SDFHitInfo, out: &mut [f32]) -> f32 {
+        self.material.hit_channels(p, info, out);
+        self.material.opacity(p, info)
+    }
+
+    fn shade_batch(&self, ps: &[Vec3f], infos: &[SDFHitInfo], out: &mut [f32]) {
+        self.material.hit_batch(ps, infos, out);
+    }
+
+    fn channels(&self) -> usize {
+        self.material.channels()
     }
 
     fn bounding_box(&self) -> (Vec3f, Vec3f) {
         self.sdf.bounding_box()
     }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, self.clone())
+    }
+
+    fn describe(&self) -> Result<ObjectDesc, &'static str> {
+        Ok(ObjectDesc::Sdf {
+            sdf: self.sdf.describe()?,
+            material: self.material.describe()?,
+        })
+    }
 }
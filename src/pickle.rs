@@ -0,0 +1,42 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use pyo3::{prelude::*, types::PyTuple, BoundObject, PyClass};
+
+/// A `(callable, args)` pair as returned by `__reduce__`, rebuilding an object
+/// when unpickled.
+pub type Reduce = (Py<PyAny>, Py<PyTuple>);
+
+/// Rebuild by calling the constructor of the pyclass `T` with `args`.
+pub fn new<'py, T: PyClass>(
+    py: Python<'py>,
+    args: impl IntoPyObject<'py, Target = PyTuple>,
+) -> PyResult<Reduce> {
+    let args = args.into_pyobject(py).map_err(Into::into)?;
+    Ok((
+        py.get_type::<T>().into_any().unbind(),
+        args.into_bound().unbind(),
+    ))
+}
+
+/// Rebuild by calling the function `name` of the module with `args`, e.g. the
+/// composition and modifier functions.
+pub fn function<'py, A>(py: Python<'py>, name: &str, args: A) -> PyResult<Reduce>
+where
+    A: IntoPyObject<'py, Target = PyTuple>,
+{
+    let func = py.import("sdflit")?.getattr(name)?;
+    let args = args.into_pyobject(py).map_err(Into::into)?;
+    Ok((func.unbind(), args.into_bound().unbind()))
+}
+
+/// Rebuild a wrapper by calling `into()` on the pyclass `value`, which is
+/// pickled through its own `__reduce__`.
+pub fn into<T>(py: Python<'_>, value: T) -> PyResult<Reduce>
+where
+    T: PyClass + Into<PyClassInitializer<T>>,
+{
+    let into = Bound::new(py, value)?.into_any().getattr("into")?;
+    Ok((into.unbind(), PyTuple::empty(py).unbind()))
+}
@@ -2,10 +2,53 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "python")]
+use numpy::{ndarray::Array2, AllowTypeChange, IntoPyArray, PyArray2, PyArrayLike2};
+#[cfg(feature = "python")]
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+#[cfg(feature = "python")]
+use crate::vec3::{Float, Vec3f};
+
 mod frustum_cone;
+#[cfg(feature = "python")]
+mod function;
 mod round_cone;
 mod sphere;
+mod spheres;
+mod spline_tube;
+mod tube;
 
 pub use frustum_cone::FrustumCone;
+#[cfg(feature = "python")]
+pub use function::FunctionSDF;
 pub use round_cone::RoundCone;
 pub use sphere::Sphere;
+pub use spheres::{Metaballs, Spheres};
+pub use spline_tube::{SplineKind, SplineTube};
+pub use tube::Tube;
+
+/// Read an `(N, 3)` array of points passed from Python.
+#[cfg(feature = "python")]
+fn points_from_array(points: &PyArrayLike2<'_, Float, AllowTypeChange>) -> PyResult<Vec<Vec3f>> {
+    let points = points.as_array();
+    if points.ncols() != 3 {
+        return Err(PyValueError::new_err("points should be of shape (N, 3)"));
+    }
+
+    points
+        .rows()
+        .into_iter()
+        .map(|row| Vec3f::try_from(row.to_vec()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(PyValueError::new_err)
+}
+
+/// Write points into an `(N, 3)` array, the inverse of `points_from_array`.
+#[cfg(feature = "python")]
+fn points_to_array<'py>(py: Python<'py>, points: &[Vec3f]) -> Bound<'py, PyArray2<Float>> {
+    let flat = points.iter().flat_map(|p| [p.x, p.y, p.z]).collect();
+    Array2::from_shape_vec((points.len(), 3), flat)
+        .unwrap()
+        .into_pyarray(py)
+}
@@ -2,26 +2,38 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "python")]
 use std::sync::Arc;
 
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
-use crate::sdf::{DynSDF, SDF};
+#[cfg(feature = "python")]
+use crate::composition::{intersect, merge, subtract};
+use crate::description::SDFDesc;
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+#[cfg(feature = "python")]
+use crate::sdf::DynSDF;
+use crate::sdf::SDF;
 use crate::solid_geometry::proj_vector_on_plane;
-use crate::vec3::{self, Vec3f};
+use crate::vec3::{self, Float, Vec3f};
 
-#[pyclass]
+/// Cone capped by flat disks of radius `ra` at `a` and `rb` at `b`.
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
 #[derive(Clone, Copy, Debug)]
 pub struct FrustumCone {
     a: Vec3f,
     b: Vec3f,
-    ra: f32,
-    rb: f32,
+    ra: Float,
+    rb: Float,
     bounding_box: (Vec3f, Vec3f),
 }
 
 impl FrustumCone {
-    pub fn new(a: Vec3f, b: Vec3f, ra: f32, rb: f32) -> FrustumCone {
+    /// Cone from `a` to `b`.
+    #[must_use]
+    pub fn new(a: Vec3f, b: Vec3f, ra: Float, rb: Float) -> FrustumCone {
         let bounding_box = aabb_frustum_cone(a, b, ra, rb);
         Self {
             a,
@@ -33,43 +45,80 @@ impl FrustumCone {
     }
 }
 
+#[cfg(feature = "python")]
 #[pymethods]
 impl FrustumCone {
     #[new]
-    pub fn __new__(a: (f32, f32, f32), b: (f32, f32, f32), ra: f32, rb: f32) -> FrustumCone {
+    fn __new__(
+        a: (Float, Float, Float),
+        b: (Float, Float, Float),
+        ra: Float,
+        rb: Float,
+    ) -> FrustumCone {
         Self::new(a.into(), b.into(), ra, rb)
     }
 
-    pub fn distance(&self, p: (f32, f32, f32)) -> f32 {
+    fn distance(&self, p: (Float, Float, Float)) -> Float {
         SDF::distance(self, Vec3f::from(p))
     }
 
-    pub fn inside(&self, p: (f32, f32, f32)) -> bool {
+    fn inside(&self, p: (Float, Float, Float)) -> bool {
         SDF::inside(self, Vec3f::from(p))
     }
 
-    pub fn bounding_box(&self) -> ((f32, f32, f32), (f32, f32, f32)) {
+    fn bounding_box(&self) -> ((Float, Float, Float), (Float, Float, Float)) {
         let (min, max) = self.bounding_box;
         ((min.x, min.y, min.z), (max.x, max.y, max.z))
     }
 
-    pub fn into(&self) -> DynSDF {
+    fn into(&self) -> DynSDF {
         let arc: Arc<dyn SDF> = Arc::new(*self);
         DynSDF::from(arc)
     }
+
+    fn __or__(&self, other: DynSDF) -> DynSDF {
+        merge(Self::into(self), other)
+    }
+
+    fn __and__(&self, other: DynSDF) -> DynSDF {
+        intersect(Self::into(self), other)
+    }
+
+    fn __sub__(&self, other: DynSDF) -> DynSDF {
+        subtract(Self::into(self), other)
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let args = (self.a, self.b, self.ra, self.rb);
+        pickle::new::<Self>(py, args)
+    }
 }
 
 impl SDF for FrustumCone {
-    fn distance(&self, p: Vec3f) -> f32 {
+    fn distance(&self, p: Vec3f) -> Float {
         sd_frustum_cone(p, self.a, self.b, self.ra, self.rb)
     }
 
     fn bounding_box(&self) -> (Vec3f, Vec3f) {
         self.bounding_box
     }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, *self)
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        Ok(SDFDesc::FrustumCone {
+            a: self.a,
+            b: self.b,
+            ra: self.ra,
+            rb: self.rb,
+        })
+    }
 }
 
-fn aabb_frustum_cone(a: Vec3f, b: Vec3f, ra: f32, rb: f32) -> (Vec3f, Vec3f) {
+fn aabb_frustum_cone(a: Vec3f, b: Vec3f, ra: Float, rb: Float) -> (Vec3f, Vec3f) {
     let up = a - b;
 
     let vx = proj_vector_on_plane(Vec3f::new(1.0, 0.0, 0.0), up);
@@ -84,18 +133,18 @@ fn aabb_frustum_cone(a: Vec3f, b: Vec3f, ra: f32, rb: f32) -> (Vec3f, Vec3f) {
     (vec3::minimum(a1, b1), vec3::maximum(a2, b2))
 }
 
-fn sd_frustum_cone(p: Vec3f, a: Vec3f, b: Vec3f, ra: f32, rb: f32) -> f32 {
+fn sd_frustum_cone(p: Vec3f, a: Vec3f, b: Vec3f, ra: Float, rb: Float) -> Float {
     let rba = rb - ra;
     let baba = vec3::dot(b - a, b - a);
     let papa = vec3::dot(p - a, p - a);
     let paba_b = vec3::dot(p - a, b - a) / baba;
     let x = (papa - paba_b * paba_b * baba).sqrt();
-    let cax = f32::max(0.0, x - (if paba_b < 0.5 { ra } else { rb }));
+    let cax = Float::max(0.0, x - (if paba_b < 0.5 { ra } else { rb }));
     let cay = (paba_b - 0.5).abs() - 0.5;
     let k = rba * rba + baba;
     let f = ((rba * (x - ra) + paba_b * baba) / k).clamp(0.0, 1.0);
     let cbx = x - ra - f * rba;
     let cby = paba_b - f;
     let s = if cbx < 0.0 && cay < 0.0 { -1.0 } else { 1.0 };
-    s * (f32::min(cax * cax + cay * cay * baba, cbx * cbx + cby * cby * baba)).sqrt()
+    s * (Float::min(cax * cax + cay * cay * baba, cbx * cbx + cby * cby * baba)).sqrt()
 }
@@ -0,0 +1,186 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+#[cfg(feature = "python")]
+use numpy::{ndarray::Array2, AllowTypeChange, IntoPyArray, PyArrayLike1};
+#[cfg(feature = "python")]
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::callback;
+use crate::composition::{intersect, merge, subtract};
+use crate::description::SDFDesc;
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+use crate::sdf::{hit_batch_from_distance, DynSDF, SDFHitInfo, SDF};
+use crate::vec3::{Float, Vec3f};
+
+/**
+ * Function SDF
+ *
+ * A SDF defined by a Python callable `f(p)` returning the signed distance at
+ * `p`. When `vectorized`, it receives an array of shape `(N, 3)` and returns
+ * the `N` distances instead. The bounding box is required for the SDF to be
+ * placed in a scene.
+ */
+
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
+#[derive(Clone, Debug)]
+pub struct FunctionSDF {
+    func: Arc<Py<PyAny>>,
+    vectorized: bool,
+    bounding_box: (Vec3f, Vec3f),
+}
+
+impl FunctionSDF {
+    /// Call `func` for the distances of points in `bounding_box`, with arrays
+    /// of points when `vectorized`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the `bounding_box` is inverted.
+    pub fn new(
+        func: Py<PyAny>,
+        bounding_box: (Vec3f, Vec3f),
+        vectorized: bool,
+    ) -> Result<Self, &'static str> {
+        let (min, max) = bounding_box;
+        if min.x > max.x || min.y > max.y || min.z > max.z {
+            return Err("bounding box should have min <= max");
+        }
+
+        Ok(Self {
+            func: Arc::new(func),
+            vectorized,
+            bounding_box,
+        })
+    }
+
+    fn call(&self, p: Vec3f) -> PyResult<Float> {
+        Python::attach(|py| self.func.bind(py).call1(((p.x, p.y, p.z),))?.extract())
+    }
+
+    fn call_batch(&self, ps: &[Vec3f], out: &mut [Float]) -> PyResult<()> {
+        Python::attach(|py| {
+            let p = ps.iter().flat_map(|p| [p.x, p.y, p.z]).collect::<Vec<_>>();
+            let p = Array2::from_shape_vec((ps.len(), 3), p).unwrap();
+
+            let distances = self.func.bind(py).call1((p.into_pyarray(py),))?;
+            let distances = distances.extract::<PyArrayLike1<'_, Float, AllowTypeChange>>()?;
+            let distances = distances.as_array();
+            if distances.len() != ps.len() {
+                return Err(PyValueError::new_err(
+                    "SDF function should return an array of shape (N,)",
+                ));
+            }
+
+            for (o, d) in out.iter_mut().zip(distances.iter()) {
+                *o = *d;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl FunctionSDF {
+    #[new]
+    #[pyo3(signature = (func, bounding_box, vectorized = false))]
+    #[allow(clippy::type_complexity)]
+    fn __new__(
+        func: Py<PyAny>,
+        bounding_box: ((Float, Float, Float), (Float, Float, Float)),
+        vectorized: bool,
+    ) -> PyResult<Self> {
+        let (min, max) = bounding_box;
+        Self::new(func, (min.into(), max.into()), vectorized).map_err(PyValueError::new_err)
+    }
+
+    fn distance(&self, p: (Float, Float, Float)) -> PyResult<Float> {
+        let distance = SDF::distance(self, Vec3f::from(p));
+        callback::check()?;
+        Ok(distance)
+    }
+
+    fn inside(&self, p: (Float, Float, Float)) -> PyResult<bool> {
+        let inside = SDF::inside(self, Vec3f::from(p));
+        callback::check()?;
+        Ok(inside)
+    }
+
+    fn bounding_box(&self) -> ((Float, Float, Float), (Float, Float, Float)) {
+        let (min, max) = self.bounding_box;
+        ((min.x, min.y, min.z), (max.x, max.y, max.z))
+    }
+
+    fn into(&self) -> DynSDF {
+        let arc: Arc<dyn SDF> = Arc::new(self.clone());
+        DynSDF::from(arc)
+    }
+
+    fn __or__(&self, other: DynSDF) -> DynSDF {
+        merge(Self::into(self), other)
+    }
+
+    fn __and__(&self, other: DynSDF) -> DynSDF {
+        intersect(Self::into(self), other)
+    }
+
+    fn __sub__(&self, other: DynSDF) -> DynSDF {
+        subtract(Self::into(self), other)
+    }
+
+    /// The function is pickled by Python, so it should be importable, e.g. not
+    /// a lambda.
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let args = (self.func.clone_ref(py), self.bounding_box, self.vectorized);
+        pickle::new::<Self>(py, args)
+    }
+}
+
+impl SDF for FunctionSDF {
+    fn distance(&self, p: Vec3f) -> Float {
+        let mut out = [0.0];
+        self.distance_batch(&[p], &mut out);
+        let [distance] = out;
+        distance
+    }
+
+    /// NaN when the function fails, a miss, its error is raised once the
+    /// query returns to Python.
+    fn distance_batch(&self, ps: &[Vec3f], out: &mut [Float]) {
+        let result = callback::run(|| {
+            if self.vectorized {
+                self.call_batch(ps, out)
+            } else {
+                ps.iter().zip(out.iter_mut()).try_for_each(|(p, o)| {
+                    *o = self.call(*p)?;
+                    Ok(())
+                })
+            }
+        });
+        if result.is_none() {
+            out.fill(Float::NAN);
+        }
+    }
+
+    fn hit_batch(&self, ps: &[Vec3f], out: &mut [Option<SDFHitInfo>]) {
+        hit_batch_from_distance(self, ps, out);
+    }
+
+    fn bounding_box(&self) -> (Vec3f, Vec3f) {
+        self.bounding_box
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, self.clone())
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        Err("function SDFs cannot be saved to a scene file")
+    }
+}
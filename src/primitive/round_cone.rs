@@ -2,26 +2,38 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "python")]
 use std::sync::Arc;
 
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
-use crate::sdf::{DynSDF, SDFHitInfo, SDF};
+#[cfg(feature = "python")]
+use crate::composition::{intersect, merge, subtract};
+use crate::description::SDFDesc;
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+#[cfg(feature = "python")]
+use crate::sdf::DynSDF;
+use crate::sdf::{SDFHitInfo, SDF};
 use crate::solid_geometry::proj_p_to_line;
-use crate::vec3::{self, Vec3f};
+use crate::vec3::{self, Float, Vec3f};
 
-#[pyclass]
+/// Cone capped by spheres of radius `ra` at `a` and `rb` at `b`.
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
 #[derive(Clone, Copy, Debug)]
 pub struct RoundCone {
     a: Vec3f,
     b: Vec3f,
-    ra: f32,
-    rb: f32,
+    ra: Float,
+    rb: Float,
     bounding_box: (Vec3f, Vec3f),
 }
 
 impl RoundCone {
-    pub fn new(a: Vec3f, b: Vec3f, ra: f32, rb: f32) -> RoundCone {
+    /// Cone from `a` to `b`.
+    #[must_use]
+    pub fn new(a: Vec3f, b: Vec3f, ra: Float, rb: Float) -> RoundCone {
         let (min_a, min_b) = (a - ra, b - rb);
         let (max_a, max_b) = (a + ra, b + rb);
         let bounding_box = (vec3::minimum(min_a, min_b), vec3::maximum(max_a, max_b));
@@ -33,36 +45,75 @@ impl RoundCone {
             bounding_box,
         }
     }
+
+    pub(crate) fn a(&self) -> Vec3f {
+        self.a
+    }
+
+    pub(crate) fn b(&self) -> Vec3f {
+        self.b
+    }
+
+    pub(crate) fn ra(&self) -> Float {
+        self.ra
+    }
+
+    pub(crate) fn rb(&self) -> Float {
+        self.rb
+    }
 }
 
+#[cfg(feature = "python")]
 #[pymethods]
 impl RoundCone {
     #[new]
-    pub fn __new__(a: (f32, f32, f32), b: (f32, f32, f32), ra: f32, rb: f32) -> RoundCone {
+    fn __new__(
+        a: (Float, Float, Float),
+        b: (Float, Float, Float),
+        ra: Float,
+        rb: Float,
+    ) -> RoundCone {
         Self::new(a.into(), b.into(), ra, rb)
     }
 
-    pub fn distance(&self, p: (f32, f32, f32)) -> f32 {
+    fn distance(&self, p: (Float, Float, Float)) -> Float {
         SDF::distance(self, Vec3f::from(p))
     }
 
-    pub fn inside(&self, p: (f32, f32, f32)) -> bool {
+    fn inside(&self, p: (Float, Float, Float)) -> bool {
         SDF::inside(self, Vec3f::from(p))
     }
 
-    pub fn bounding_box(&self) -> ((f32, f32, f32), (f32, f32, f32)) {
+    fn bounding_box(&self) -> ((Float, Float, Float), (Float, Float, Float)) {
         let (min, max) = self.bounding_box;
         ((min.x, min.y, min.z), (max.x, max.y, max.z))
     }
 
-    pub fn into(&self) -> DynSDF {
+    fn into(&self) -> DynSDF {
         let arc: Arc<dyn SDF> = Arc::new(*self);
         DynSDF::from(arc)
     }
+
+    fn __or__(&self, other: DynSDF) -> DynSDF {
+        merge(Self::into(self), other)
+    }
+
+    fn __and__(&self, other: DynSDF) -> DynSDF {
+        intersect(Self::into(self), other)
+    }
+
+    fn __sub__(&self, other: DynSDF) -> DynSDF {
+        subtract(Self::into(self), other)
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let args = (self.a, self.b, self.ra, self.rb);
+        pickle::new::<Self>(py, args)
+    }
 }
 
 impl SDF for RoundCone {
-    fn distance(&self, p: Vec3f) -> f32 {
+    fn distance(&self, p: Vec3f) -> Float {
         sd_round_cone(p, self.a, self.b, self.ra, self.rb)
     }
 
@@ -83,12 +134,31 @@ impl SDF for RoundCone {
     fn bounding_box(&self) -> (Vec3f, Vec3f) {
         self.bounding_box
     }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, *self)
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        Ok(SDFDesc::RoundCone {
+            a: self.a,
+            b: self.b,
+            ra: self.ra,
+            rb: self.rb,
+        })
+    }
 }
 
-fn sd_round_cone(p: Vec3f, a: Vec3f, b: Vec3f, ra: f32, rb: f32) -> f32 {
+fn sd_round_cone(p: Vec3f, a: Vec3f, b: Vec3f, ra: Float, rb: Float) -> Float {
     // sampling independent computations (only depend on shape)
     let ba = b - a;
     let l2 = vec3::dot(ba, ba);
+    if l2 == 0.0 {
+        // degenerated into a sphere, e.g. duplicated nodes
+        return (p - a).norm() - Float::max(ra, rb);
+    }
+
     let rr = ra - rb;
     let a2 = l2 - rr * rr;
     let il2 = 1.0 / l2;
@@ -102,12 +172,30 @@ fn sd_round_cone(p: Vec3f, a: Vec3f, b: Vec3f, ra: f32, rb: f32) -> f32 {
     let z2 = z * z * l2;
 
     // single square root!
-    let k = f32::signum(rr) * rr * rr * x2;
-    if f32::signum(z) * a2 * z2 > k {
-        f32::sqrt(x2 + z2) * il2 - rb
-    } else if f32::signum(y) * a2 * y2 < k {
-        f32::sqrt(x2 + y2) * il2 - ra
+    let k = Float::signum(rr) * rr * rr * x2;
+    if Float::signum(z) * a2 * z2 > k {
+        Float::sqrt(x2 + z2) * il2 - rb
+    } else if Float::signum(y) * a2 * y2 < k {
+        Float::sqrt(x2 + y2) * il2 - ra
     } else {
-        (f32::sqrt(x2 * a2 * il2) + y * rr) * il2 - ra
+        (Float::sqrt(x2 * a2 * il2) + y * rr) * il2 - ra
+    }
+}
+
+#[cfg(all(test, feature = "f64"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_large_coordinates() {
+        // a thin neurite far from the origin, as in whole-brain reconstructions
+        let distance = |o: Vec3f| {
+            let cone = RoundCone::new(o, o + Vec3f::new(1.2, 0.4, 0.3), 0.3, 0.2);
+            SDF::distance(&cone, o + Vec3f::new(0.6, 0.2, 0.25))
+        };
+
+        let far = distance(Vec3f::new(40000.0, 52000.0, 31000.0));
+        let near = distance(Vec3f::new(0.0, 0.0, 0.0));
+        assert!((far - near).abs() < 1e-9, "{far} != {near}");
     }
 }
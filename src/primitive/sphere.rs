@@ -2,59 +2,96 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "python")]
 use std::sync::Arc;
 
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
-use crate::sdf::{DynSDF, SDFHitInfo, SDF};
-use crate::vec3::Vec3f;
+#[cfg(feature = "python")]
+use crate::composition::{intersect, merge, subtract};
+use crate::description::SDFDesc;
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+#[cfg(feature = "python")]
+use crate::sdf::DynSDF;
+use crate::sdf::{SDFHitInfo, SDF};
+use crate::vec3::{Float, Vec3f};
 
-#[pyclass]
+/// Sphere of `radius` around `center`.
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
 #[derive(Clone, Copy, Debug)]
 pub struct Sphere {
     center: Vec3f,
-    radius: f32,
+    radius: Float,
     bounding_box: (Vec3f, Vec3f),
 }
 
 impl Sphere {
-    pub fn new(center: Vec3f, radius: f32) -> Self {
+    /// Sphere of `radius` around `center`.
+    #[must_use]
+    pub fn new(center: Vec3f, radius: Float) -> Self {
         Self {
             center,
             radius,
             bounding_box: (center - radius, center + radius),
         }
     }
+
+    pub(crate) fn center(&self) -> Vec3f {
+        self.center
+    }
+
+    pub(crate) fn radius(&self) -> Float {
+        self.radius
+    }
 }
 
+#[cfg(feature = "python")]
 #[pymethods]
 impl Sphere {
     #[new]
-    pub fn __new__(center: (f32, f32, f32), radius: f32) -> Self {
+    fn __new__(center: (Float, Float, Float), radius: Float) -> Self {
         Self::new(center.into(), radius)
     }
 
-    pub fn distance(&self, p: (f32, f32, f32)) -> f32 {
+    fn distance(&self, p: (Float, Float, Float)) -> Float {
         SDF::distance(self, Vec3f::from(p))
     }
 
-    pub fn inside(&self, p: (f32, f32, f32)) -> bool {
+    fn inside(&self, p: (Float, Float, Float)) -> bool {
         SDF::inside(self, Vec3f::from(p))
     }
 
-    pub fn bounding_box(&self) -> ((f32, f32, f32), (f32, f32, f32)) {
+    fn bounding_box(&self) -> ((Float, Float, Float), (Float, Float, Float)) {
         let (min, max) = self.bounding_box;
         ((min.x, min.y, min.z), (max.x, max.y, max.z))
     }
 
-    pub fn into(&self) -> DynSDF {
+    fn into(&self) -> DynSDF {
         let arc: Arc<dyn SDF> = Arc::new(*self);
         DynSDF::from(arc)
     }
+
+    fn __or__(&self, other: DynSDF) -> DynSDF {
+        merge(Self::into(self), other)
+    }
+
+    fn __and__(&self, other: DynSDF) -> DynSDF {
+        intersect(Self::into(self), other)
+    }
+
+    fn __sub__(&self, other: DynSDF) -> DynSDF {
+        subtract(Self::into(self), other)
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::new::<Self>(py, (self.center, self.radius))
+    }
 }
 
 impl SDF for Sphere {
-    fn distance(&self, p: Vec3f) -> f32 {
+    fn distance(&self, p: Vec3f) -> Float {
         sd_sphere(p, self.center, self.radius)
     }
 
@@ -75,8 +112,20 @@ impl SDF for Sphere {
     fn bounding_box(&self) -> (Vec3f, Vec3f) {
         self.bounding_box
     }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, *self)
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        Ok(SDFDesc::Sphere {
+            center: self.center,
+            radius: self.radius,
+        })
+    }
 }
 
-fn sd_sphere(p: Vec3f, c: Vec3f, r: f32) -> f32 {
+fn sd_sphere(p: Vec3f, c: Vec3f, r: Float) -> Float {
     (p - c).norm() - r
 }
@@ -0,0 +1,337 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(feature = "python")]
+use std::sync::Arc;
+
+#[cfg(feature = "python")]
+use numpy::{AllowTypeChange, IntoPyArray, PyArray1, PyArray2, PyArrayLike1, PyArrayLike2};
+#[cfg(feature = "python")]
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::accelerator::BVHNode;
+#[cfg(feature = "python")]
+use crate::composition::{intersect, merge, subtract};
+use crate::description::SDFDesc;
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+use crate::primitive::Sphere;
+#[cfg(feature = "python")]
+use crate::primitive::{points_from_array, points_to_array};
+#[cfg(feature = "python")]
+use crate::sdf::DynSDF;
+use crate::sdf::{SDFHitInfo, SDF};
+use crate::vec3::{self, Float, Vec3f};
+
+/**
+ * Many spheres in a single SDF
+ */
+
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
+#[derive(Clone)]
+#[allow(missing_debug_implementations)]
+pub struct Spheres {
+    items: Vec<Sphere>,
+    bvh: BVHNode,
+    bounding_box: (Vec3f, Vec3f),
+}
+
+impl Spheres {
+    /// One sphere per entry of `centers` and `radii`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `centers` and `radii` differ in length, are empty,
+    /// or a radius is negative or NaN.
+    pub fn new(centers: &[Vec3f], radii: &[Float]) -> Result<Spheres, &'static str> {
+        if centers.len() != radii.len() {
+            return Err("centers and radii should have the same length");
+        }
+
+        if radii.iter().any(|r| r.is_nan() || *r < 0.0) {
+            return Err("radii should not be negative");
+        }
+
+        let items = centers
+            .iter()
+            .zip(radii)
+            .map(|(c, r)| Sphere::new(*c, *r))
+            .collect::<Vec<_>>();
+
+        let bounding_boxes = items.iter().map(SDF::bounding_box).collect::<Vec<_>>();
+        let bounding_box = bounding_boxes
+            .iter()
+            .copied()
+            .reduce(|(min, max), (emin, emax)| (vec3::minimum(min, emin), vec3::maximum(max, emax)))
+            .ok_or("spheres requires at least one sphere")?;
+
+        Ok(Self {
+            items,
+            bvh: BVHNode::new(&bounding_boxes),
+            bounding_box,
+        })
+    }
+
+    /// Centers and radii of the spheres, as passed from Python.
+    #[cfg(feature = "python")]
+    fn to_arrays<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> (Bound<'py, PyArray2<Float>>, Bound<'py, PyArray1<Float>>) {
+        let (centers, radii) = self.centers_radii();
+        (points_to_array(py, &centers), radii.into_pyarray(py))
+    }
+
+    fn centers_radii(&self) -> (Vec<Vec3f>, Vec<Float>) {
+        self.items.iter().map(|s| (s.center(), s.radius())).unzip()
+    }
+
+    /// Signed distance and index of the closest sphere.
+    fn closest(&self, p: Vec3f) -> (usize, Float) {
+        self.bvh.nearest(p, |i| {
+            self.items
+                .get(i)
+                .map_or(Float::INFINITY, |s| SDF::distance(s, p))
+        })
+    }
+
+    fn hit_info(&self, idx: usize, distance: Float) -> Option<SDFHitInfo> {
+        let sphere = self.items.get(idx)?;
+        Some(SDFHitInfo {
+            distance,
+            u: (distance / sphere.radius()).clamp(0.0, 1.0),
+            v: 0.0,
+            w: 0.0,
+        })
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Spheres {
+    #[new]
+    #[allow(clippy::needless_pass_by_value)]
+    fn __new__(
+        centers: PyArrayLike2<'_, Float, AllowTypeChange>,
+        radii: PyArrayLike1<'_, Float, AllowTypeChange>,
+    ) -> PyResult<Spheres> {
+        let centers = points_from_array(&centers)?;
+        let radii = radii.as_array().to_vec();
+        Self::new(&centers, &radii).map_err(PyValueError::new_err)
+    }
+
+    fn distance(&self, p: (Float, Float, Float)) -> Float {
+        SDF::distance(self, Vec3f::from(p))
+    }
+
+    fn inside(&self, p: (Float, Float, Float)) -> bool {
+        SDF::inside(self, Vec3f::from(p))
+    }
+
+    fn bounding_box(&self) -> ((Float, Float, Float), (Float, Float, Float)) {
+        let (min, max) = self.bounding_box;
+        ((min.x, min.y, min.z), (max.x, max.y, max.z))
+    }
+
+    fn into(&self) -> DynSDF {
+        let arc: Arc<dyn SDF> = Arc::new(self.clone());
+        DynSDF::from(arc)
+    }
+
+    fn __or__(&self, other: DynSDF) -> DynSDF {
+        merge(Self::into(self), other)
+    }
+
+    fn __and__(&self, other: DynSDF) -> DynSDF {
+        intersect(Self::into(self), other)
+    }
+
+    fn __sub__(&self, other: DynSDF) -> DynSDF {
+        subtract(Self::into(self), other)
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::new::<Self>(py, self.to_arrays(py))
+    }
+}
+
+impl SDF for Spheres {
+    fn distance(&self, p: Vec3f) -> Float {
+        self.closest(p).1
+    }
+
+    fn hit(&self, p: Vec3f) -> Option<SDFHitInfo> {
+        let (idx, distance) = self.closest(p);
+        if distance <= 0.0 {
+            self.hit_info(idx, distance)
+        } else {
+            None
+        }
+    }
+
+    fn bounding_box(&self) -> (Vec3f, Vec3f) {
+        self.bounding_box
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, self.clone())
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        let (centers, radii) = self.centers_radii();
+        Ok(SDFDesc::Spheres { centers, radii })
+    }
+}
+
+/**
+ * Spheres blended with a smooth minimum
+ */
+
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
+#[derive(Clone)]
+#[allow(missing_debug_implementations)]
+pub struct Metaballs {
+    spheres: Spheres,
+    k: Float,
+    bounding_box: (Vec3f, Vec3f),
+}
+
+impl Metaballs {
+    /// Spheres smoothly blended over a distance `k`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `k` is not positive, or as `Spheres::new`.
+    pub fn new(centers: &[Vec3f], radii: &[Float], k: Float) -> Result<Metaballs, &'static str> {
+        if k <= 0.0 {
+            return Err("blending factor should be positive");
+        }
+
+        let spheres = Spheres::new(centers, radii)?;
+
+        // the smooth minimum never goes below the minimum by more than k
+        let (min, max) = spheres.bounding_box;
+        Ok(Self {
+            spheres,
+            k,
+            bounding_box: (min - k, max + k),
+        })
+    }
+
+    /// Signed distance and index of the closest sphere.
+    fn closest(&self, p: Vec3f) -> (usize, Float) {
+        let (idx, d) = self.spheres.closest(p);
+
+        // only spheres within k of the closest one contribute to the blending
+        let limit = d + self.k;
+        let mut distances = Vec::new();
+        self.spheres.bvh.within(p, limit, |i| {
+            if let Some(s) = self.spheres.items.get(i) {
+                let sd = SDF::distance(s, p);
+                if sd < limit {
+                    distances.push(sd);
+                }
+            }
+        });
+
+        // blend in ascending order, so that the result is independent of the tree
+        distances.sort_unstable_by(Float::total_cmp);
+        let distance = distances
+            .into_iter()
+            .reduce(|a, b| smooth_min(a, b, self.k))
+            .unwrap_or(d);
+        (idx, distance)
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Metaballs {
+    #[new]
+    #[allow(clippy::needless_pass_by_value)]
+    fn __new__(
+        centers: PyArrayLike2<'_, Float, AllowTypeChange>,
+        radii: PyArrayLike1<'_, Float, AllowTypeChange>,
+        k: Float,
+    ) -> PyResult<Metaballs> {
+        let centers = points_from_array(&centers)?;
+        let radii = radii.as_array().to_vec();
+        Self::new(&centers, &radii, k).map_err(PyValueError::new_err)
+    }
+
+    fn distance(&self, p: (Float, Float, Float)) -> Float {
+        SDF::distance(self, Vec3f::from(p))
+    }
+
+    fn inside(&self, p: (Float, Float, Float)) -> bool {
+        SDF::inside(self, Vec3f::from(p))
+    }
+
+    fn bounding_box(&self) -> ((Float, Float, Float), (Float, Float, Float)) {
+        let (min, max) = self.bounding_box;
+        ((min.x, min.y, min.z), (max.x, max.y, max.z))
+    }
+
+    fn into(&self) -> DynSDF {
+        let arc: Arc<dyn SDF> = Arc::new(self.clone());
+        DynSDF::from(arc)
+    }
+
+    fn __or__(&self, other: DynSDF) -> DynSDF {
+        merge(Self::into(self), other)
+    }
+
+    fn __and__(&self, other: DynSDF) -> DynSDF {
+        intersect(Self::into(self), other)
+    }
+
+    fn __sub__(&self, other: DynSDF) -> DynSDF {
+        subtract(Self::into(self), other)
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let (centers, radii) = self.spheres.to_arrays(py);
+        pickle::new::<Self>(py, (centers, radii, self.k))
+    }
+}
+
+impl SDF for Metaballs {
+    fn distance(&self, p: Vec3f) -> Float {
+        self.closest(p).1
+    }
+
+    fn hit(&self, p: Vec3f) -> Option<SDFHitInfo> {
+        let (idx, distance) = self.closest(p);
+        if distance <= 0.0 {
+            self.spheres.hit_info(idx, distance)
+        } else {
+            None
+        }
+    }
+
+    fn bounding_box(&self) -> (Vec3f, Vec3f) {
+        self.bounding_box
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, self.clone())
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        let (centers, radii) = self.spheres.centers_radii();
+        Ok(SDFDesc::Metaballs {
+            centers,
+            radii,
+            k: self.k,
+        })
+    }
+}
+
+/// Polynomial smooth minimum, blends `a` and `b` when they are closer than `k`.
+fn smooth_min(a: Float, b: Float, k: Float) -> Float {
+    let h = Float::max(k - (a - b).abs(), 0.0) / k;
+    Float::min(a, b) - h * h * k * 0.25
+}
@@ -0,0 +1,424 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(feature = "python")]
+use std::sync::Arc;
+
+#[cfg(feature = "python")]
+use numpy::{AllowTypeChange, IntoPyArray, PyArrayLike1, PyArrayLike2};
+#[cfg(feature = "python")]
+use pyo3::{exceptions::PyValueError, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::accelerator::BVHNode;
+#[cfg(feature = "python")]
+use crate::composition::{intersect, merge, subtract};
+use crate::description::SDFDesc;
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+#[cfg(feature = "python")]
+use crate::primitive::{points_from_array, points_to_array};
+#[cfg(feature = "python")]
+use crate::sdf::DynSDF;
+use crate::sdf::{SDFHitInfo, SDF};
+use crate::vec3::{self, Float, Vec3f};
+
+const COARSE_SAMPLES: usize = 16;
+const REFINE_ITERATIONS: usize = 20;
+
+/// Curve interpolating the points of a `SplineTube`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SplineKind {
+    /// `SplineTube::catmull_rom`.
+    CatmullRom,
+    /// `SplineTube::bezier`.
+    Bezier,
+}
+
+impl TryFrom<&str> for SplineKind {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "catmull_rom" | "catmull-rom" | "CatmullRom" => Ok(Self::CatmullRom),
+            "bezier" | "Bezier" => Ok(Self::Bezier),
+            _ => Err(()),
+        }
+    }
+}
+
+/**
+ * A tube with varying radius swept along a smooth curve
+ */
+
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
+#[derive(Clone)]
+#[allow(missing_debug_implementations)]
+pub struct SplineTube {
+    segments: Vec<CubicSegment>,
+    arc_lengths: Vec<Float>, // arc-length at the start of each segment
+    length: Float,
+    bvh: BVHNode,
+    bounding_box: (Vec3f, Vec3f),
+}
+
+impl SplineTube {
+    /// Catmull-Rom curve passing through every point, the radius is interpolated
+    /// linearly between points.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `points` and `radii` differ in length, or there
+    /// are less than two points.
+    pub fn catmull_rom(points: &[Vec3f], radii: &[Float]) -> Result<SplineTube, &'static str> {
+        if points.len() != radii.len() {
+            return Err("points and radii should have the same length");
+        }
+
+        // duplicate the end points, so that the curve passes through all points
+        let [first, .., last] = points else {
+            return Err("spline tube requires at least two points");
+        };
+        let padded = std::iter::once(first)
+            .chain(points)
+            .chain(std::iter::once(last))
+            .copied()
+            .collect::<Vec<_>>();
+
+        let segments = padded
+            .windows(4)
+            .zip(radii.windows(2))
+            .map(|(p, r)| match (p, r) {
+                ([p0, p1, p2, p3], [r1, r2]) => {
+                    let b1 = *p1 + (*p2 - *p0) / 6.0;
+                    let b2 = *p2 - (*p3 - *p1) / 6.0;
+                    let dr = (r2 - r1) / 3.0;
+                    CubicSegment::new([*p1, b1, b2, *p2], [*r1, r1 + dr, r2 - dr, *r2])
+                }
+                _ => unreachable!(),
+            })
+            .collect();
+
+        Ok(Self::from_segments(segments))
+    }
+
+    /// Piecewise cubic Bezier curve, consecutive segments share their end points
+    /// so that `3k + 1` control points are required. The radius is interpolated
+    /// with the same Bernstein basis as the control points.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `points` and `radii` differ in length, or there
+    /// are not `3k + 1` points.
+    pub fn bezier(points: &[Vec3f], radii: &[Float]) -> Result<SplineTube, &'static str> {
+        if points.len() != radii.len() {
+            return Err("points and radii should have the same length");
+        }
+
+        if points.len() < 4 || points.len() % 3 != 1 {
+            return Err("bezier spline tube requires 3k + 1 control points");
+        }
+
+        let segments = points
+            .windows(4)
+            .step_by(3)
+            .zip(radii.windows(4).step_by(3))
+            .map(|(p, r)| match (p, r) {
+                ([p0, p1, p2, p3], [r0, r1, r2, r3]) => {
+                    CubicSegment::new([*p0, *p1, *p2, *p3], [*r0, *r1, *r2, *r3])
+                }
+                _ => unreachable!(),
+            })
+            .collect();
+
+        Ok(Self::from_segments(segments))
+    }
+
+    fn from_segments(segments: Vec<CubicSegment>) -> SplineTube {
+        let mut length = 0.0;
+        let mut arc_lengths = Vec::with_capacity(segments.len());
+        for s in &segments {
+            arc_lengths.push(length);
+            length += s.length;
+        }
+
+        let bounding_boxes = segments.iter().map(|s| s.bounding_box).collect::<Vec<_>>();
+        let bounding_box = bounding_boxes
+            .iter()
+            .copied()
+            .reduce(|(min, max), (emin, emax)| (vec3::minimum(min, emin), vec3::maximum(max, emax)))
+            .unwrap();
+
+        Self {
+            segments,
+            arc_lengths,
+            length,
+            bvh: BVHNode::new(&bounding_boxes),
+            bounding_box,
+        }
+    }
+
+    /// The bezier control points and radii of the segments, the inverse of
+    /// `bezier`.
+    fn bezier_points(&self) -> (Vec<Vec3f>, Vec<Float>) {
+        // consecutive segments share their end points
+        let first = self.segments.first().map(|s| (s.points[0], s.radii[0]));
+        first
+            .into_iter()
+            .chain(self.segments.iter().flat_map(|s| {
+                let [_, p1, p2, p3] = s.points;
+                let [_, r1, r2, r3] = s.radii;
+                [(p1, r1), (p2, r2), (p3, r3)]
+            }))
+            .unzip()
+    }
+
+    /// Signed distance and index of the closest segment.
+    fn closest(&self, p: Vec3f) -> (usize, Float) {
+        self.bvh.nearest(p, |i| {
+            self.segments
+                .get(i)
+                .map_or(Float::INFINITY, |s| s.closest(p).1)
+        })
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl SplineTube {
+    #[new]
+    #[pyo3(signature = (points, radii, kind = "catmull_rom"))]
+    #[allow(clippy::needless_pass_by_value)]
+    fn __new__(
+        points: PyArrayLike2<'_, Float, AllowTypeChange>,
+        radii: PyArrayLike1<'_, Float, AllowTypeChange>,
+        kind: &str,
+    ) -> PyResult<SplineTube> {
+        let kind: SplineKind = kind
+            .try_into()
+            .map_err(|()| PyValueError::new_err("Invalid spline kind"))?;
+
+        let points = points_from_array(&points)?;
+        let radii = radii.as_array().to_vec();
+        match kind {
+            SplineKind::CatmullRom => Self::catmull_rom(&points, &radii),
+            SplineKind::Bezier => Self::bezier(&points, &radii),
+        }
+        .map_err(PyValueError::new_err)
+    }
+
+    fn distance(&self, p: (Float, Float, Float)) -> Float {
+        SDF::distance(self, Vec3f::from(p))
+    }
+
+    fn inside(&self, p: (Float, Float, Float)) -> bool {
+        SDF::inside(self, Vec3f::from(p))
+    }
+
+    fn bounding_box(&self) -> ((Float, Float, Float), (Float, Float, Float)) {
+        let (min, max) = self.bounding_box;
+        ((min.x, min.y, min.z), (max.x, max.y, max.z))
+    }
+
+    fn into(&self) -> DynSDF {
+        let arc: Arc<dyn SDF> = Arc::new(self.clone());
+        DynSDF::from(arc)
+    }
+
+    fn __or__(&self, other: DynSDF) -> DynSDF {
+        merge(Self::into(self), other)
+    }
+
+    fn __and__(&self, other: DynSDF) -> DynSDF {
+        intersect(Self::into(self), other)
+    }
+
+    fn __sub__(&self, other: DynSDF) -> DynSDF {
+        subtract(Self::into(self), other)
+    }
+
+    /// Pickled as the equivalent bezier spline tube, both kinds are stored as
+    /// their bezier segments.
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let (points, radii) = self.bezier_points();
+        let args = (
+            points_to_array(py, &points),
+            radii.into_pyarray(py),
+            "bezier",
+        );
+        pickle::new::<Self>(py, args)
+    }
+}
+
+impl SDF for SplineTube {
+    fn distance(&self, p: Vec3f) -> Float {
+        self.closest(p).1
+    }
+
+    fn hit(&self, p: Vec3f) -> Option<SDFHitInfo> {
+        let (idx, distance) = self.closest(p);
+        if distance > 0.0 {
+            return None;
+        }
+
+        let (segment, start) = (self.segments.get(idx)?, self.arc_lengths.get(idx)?);
+        let (t, _) = segment.closest(p);
+        let v = if self.length > 0.0 {
+            (start + segment.arc_length(t)) / self.length
+        } else {
+            0.0
+        };
+
+        Some(SDFHitInfo {
+            distance,
+            u: distance.clamp(0.0, 1.0),
+            v: v.clamp(0.0, 1.0),
+            w: 0.0,
+        })
+    }
+
+    fn bounding_box(&self) -> (Vec3f, Vec3f) {
+        self.bounding_box
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, self.clone())
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        let (points, radii) = self.bezier_points();
+        Ok(SDFDesc::SplineTube {
+            points,
+            radii,
+            kind: SplineKind::Bezier,
+        })
+    }
+}
+
+/**
+ * Cubic Bezier segment with radius in Bernstein form
+ */
+
+#[derive(Clone, Copy, Debug)]
+struct CubicSegment {
+    points: [Vec3f; 4],
+    radii: [Float; 4],
+    lengths: [Float; COARSE_SAMPLES + 1], // cumulative chord lengths of the samples
+    length: Float,
+    bounding_box: (Vec3f, Vec3f),
+}
+
+impl CubicSegment {
+    fn new(points: [Vec3f; 4], radii: [Float; 4]) -> CubicSegment {
+        // a bezier curve lies in the convex hull of its control points, and the
+        // radius is bounded by the largest control radius
+        let r = radii.iter().copied().fold(0.0, Float::max);
+        let [p0, p1, p2, p3] = points;
+        let min = vec3::minimum(vec3::minimum(p0, p1), vec3::minimum(p2, p3));
+        let max = vec3::maximum(vec3::maximum(p0, p1), vec3::maximum(p2, p3));
+
+        let mut segment = CubicSegment {
+            points,
+            radii,
+            lengths: [0.0; COARSE_SAMPLES + 1],
+            length: 0.0,
+            bounding_box: (min - r, max + r),
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let step = 1.0 / COARSE_SAMPLES as Float;
+        let mut lengths = [0.0; COARSE_SAMPLES + 1];
+        let mut length = 0.0;
+        for (i, l) in lengths.iter_mut().enumerate().skip(1) {
+            #[allow(clippy::cast_precision_loss)]
+            let t = i as Float * step;
+            length += (segment.at(t).0 - segment.at(t - step).0).norm();
+            *l = length;
+        }
+        segment.lengths = lengths;
+        segment.length = length;
+        segment
+    }
+
+    /// Approximate arc-length from the start of the segment to `t`.
+    fn arc_length(&self, t: Float) -> Float {
+        #[allow(clippy::cast_precision_loss)]
+        let x = t.clamp(0.0, 1.0) * COARSE_SAMPLES as Float;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let i = (x.floor() as usize).min(COARSE_SAMPLES - 1);
+        match (self.lengths.get(i), self.lengths.get(i + 1)) {
+            #[allow(clippy::cast_precision_loss)]
+            (Some(l0), Some(l1)) => l0 + (l1 - l0) * (x - i as Float),
+            _ => self.length,
+        }
+    }
+
+    fn at(&self, t: Float) -> (Vec3f, Float) {
+        let s = 1.0 - t;
+        let (b0, b1, b2, b3) = (s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t);
+        let [p0, p1, p2, p3] = self.points;
+        let [r0, r1, r2, r3] = self.radii;
+        (
+            p0 * b0 + p1 * b1 + p2 * b2 + p3 * b3,
+            r0 * b0 + r1 * b1 + r2 * b2 + r3 * b3,
+        )
+    }
+
+    fn distance_at(&self, p: Vec3f, t: Float) -> Float {
+        let (c, r) = self.at(t);
+        (p - c).norm() - r
+    }
+
+    /// The union of spheres swept along the curve has the distance
+    /// `min_t |p - c(t)| - r(t)`, which is minimized by a coarse scan followed by
+    /// a golden-section search around the best sample.
+    fn closest(&self, p: Vec3f) -> (Float, Float) {
+        #[allow(clippy::cast_precision_loss)]
+        let step = 1.0 / COARSE_SAMPLES as Float;
+        #[allow(clippy::cast_precision_loss)]
+        let (t, _) = (0..=COARSE_SAMPLES)
+            .map(|i| i as Float * step)
+            .map(|t| (t, self.distance_at(p, t)))
+            .fold(
+                (0.0, Float::INFINITY),
+                |best, x| if x.1 < best.1 { x } else { best },
+            );
+
+        let ratio = (Float::sqrt(5.0) - 1.0) / 2.0;
+        let (mut lo, mut hi) = ((t - step).max(0.0), (t + step).min(1.0));
+        let mut x1 = hi - ratio * (hi - lo);
+        let mut x2 = lo + ratio * (hi - lo);
+        let (mut f1, mut f2) = (self.distance_at(p, x1), self.distance_at(p, x2));
+        for _ in 0..REFINE_ITERATIONS {
+            if f1 < f2 {
+                hi = x2;
+                (x2, f2) = (x1, f1);
+                x1 = hi - ratio * (hi - lo);
+                f1 = self.distance_at(p, x1);
+            } else {
+                lo = x1;
+                (x1, f1) = (x2, f2);
+                x2 = lo + ratio * (hi - lo);
+                f2 = self.distance_at(p, x2);
+            }
+        }
+
+        // the end points are not visited by the search
+        [
+            (x1, f1),
+            (0.0, self.distance_at(p, 0.0)),
+            (1.0, self.distance_at(p, 1.0)),
+        ]
+        .into_iter()
+        .fold((t, self.distance_at(p, t)), |best, x| {
+            if x.1 < best.1 {
+                x
+            } else {
+                best
+            }
+        })
+    }
+}
@@ -0,0 +1,208 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(feature = "python")]
+use std::sync::Arc;
+
+#[cfg(feature = "python")]
+use numpy::{AllowTypeChange, IntoPyArray, PyArrayLike1, PyArrayLike2};
+#[cfg(feature = "python")]
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::accelerator::BVHNode;
+#[cfg(feature = "python")]
+use crate::composition::{intersect, merge, subtract};
+use crate::description::SDFDesc;
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+use crate::primitive::RoundCone;
+#[cfg(feature = "python")]
+use crate::primitive::{points_from_array, points_to_array};
+#[cfg(feature = "python")]
+use crate::sdf::DynSDF;
+use crate::sdf::{SDFHitInfo, SDF};
+use crate::vec3::{self, Float, Vec3f};
+
+/**
+ * A polyline of round cones evaluated as a single SDF
+ */
+
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
+#[derive(Clone)]
+#[allow(missing_debug_implementations)]
+pub struct Tube {
+    segments: Vec<RoundCone>,
+    arc_lengths: Vec<Float>, // arc-length at the start of each segment
+    length: Float,
+    bvh: BVHNode,
+    bounding_box: (Vec3f, Vec3f),
+}
+
+impl Tube {
+    /// Round cones between consecutive `nodes`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `nodes` and `radii` differ in length, there are
+    /// less than two nodes, or a radius is negative or NaN.
+    pub fn new(nodes: &[Vec3f], radii: &[Float]) -> Result<Tube, &'static str> {
+        if nodes.len() != radii.len() {
+            return Err("nodes and radii should have the same length");
+        }
+
+        if radii.iter().any(|r| r.is_nan() || *r < 0.0) {
+            return Err("radii should not be negative");
+        }
+
+        let segments = nodes
+            .windows(2)
+            .zip(radii.windows(2))
+            .map(|(n, r)| match (n, r) {
+                ([a, b], [ra, rb]) => RoundCone::new(*a, *b, *ra, *rb),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+
+        let mut length = 0.0;
+        let mut arc_lengths = Vec::with_capacity(segments.len());
+        for s in &segments {
+            arc_lengths.push(length);
+            length += (s.b() - s.a()).norm();
+        }
+
+        let bounding_boxes = segments.iter().map(SDF::bounding_box).collect::<Vec<_>>();
+        let bounding_box = bounding_boxes
+            .iter()
+            .copied()
+            .reduce(|(min, max), (emin, emax)| (vec3::minimum(min, emin), vec3::maximum(max, emax)))
+            .ok_or("tube requires at least two nodes")?;
+
+        Ok(Self {
+            segments,
+            arc_lengths,
+            length,
+            bvh: BVHNode::new(&bounding_boxes),
+            bounding_box,
+        })
+    }
+
+    /// The nodes and radii of the segments, the inverse of `new`.
+    fn nodes(&self) -> (Vec<Vec3f>, Vec<Float>) {
+        let last = self.segments.last().map(|s| (s.b(), s.rb()));
+        self.segments
+            .iter()
+            .map(|s| (s.a(), s.ra()))
+            .chain(last)
+            .unzip()
+    }
+
+    /// Signed distance and index of the closest segment.
+    fn closest(&self, p: Vec3f) -> (usize, Float) {
+        self.bvh.nearest(p, |i| {
+            self.segments
+                .get(i)
+                .map_or(Float::INFINITY, |s| SDF::distance(s, p))
+        })
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Tube {
+    #[new]
+    #[allow(clippy::needless_pass_by_value)]
+    fn __new__(
+        nodes: PyArrayLike2<'_, Float, AllowTypeChange>,
+        radii: PyArrayLike1<'_, Float, AllowTypeChange>,
+    ) -> PyResult<Tube> {
+        let nodes = points_from_array(&nodes)?;
+        let radii = radii.as_array().to_vec();
+        Self::new(&nodes, &radii).map_err(PyValueError::new_err)
+    }
+
+    fn distance(&self, p: (Float, Float, Float)) -> Float {
+        SDF::distance(self, Vec3f::from(p))
+    }
+
+    fn inside(&self, p: (Float, Float, Float)) -> bool {
+        SDF::inside(self, Vec3f::from(p))
+    }
+
+    fn bounding_box(&self) -> ((Float, Float, Float), (Float, Float, Float)) {
+        let (min, max) = self.bounding_box;
+        ((min.x, min.y, min.z), (max.x, max.y, max.z))
+    }
+
+    fn into(&self) -> DynSDF {
+        let arc: Arc<dyn SDF> = Arc::new(self.clone());
+        DynSDF::from(arc)
+    }
+
+    fn __or__(&self, other: DynSDF) -> DynSDF {
+        merge(Self::into(self), other)
+    }
+
+    fn __and__(&self, other: DynSDF) -> DynSDF {
+        intersect(Self::into(self), other)
+    }
+
+    fn __sub__(&self, other: DynSDF) -> DynSDF {
+        subtract(Self::into(self), other)
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let (nodes, radii) = self.nodes();
+        let args = (points_to_array(py, &nodes), radii.into_pyarray(py));
+        pickle::new::<Self>(py, args)
+    }
+}
+
+impl SDF for Tube {
+    fn distance(&self, p: Vec3f) -> Float {
+        self.closest(p).1
+    }
+
+    fn hit(&self, p: Vec3f) -> Option<SDFHitInfo> {
+        let (idx, distance) = self.closest(p);
+        if distance > 0.0 {
+            return None;
+        }
+
+        // arc-length of the projection of p, normalized over the whole tube
+        let (segment, start) = (self.segments.get(idx)?, self.arc_lengths.get(idx)?);
+        let ab = segment.b() - segment.a();
+        let l2 = ab.dot2();
+        let k = if l2 > 0.0 {
+            (vec3::dot(p - segment.a(), ab) / l2).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let v = if self.length > 0.0 {
+            (start + k * l2.sqrt()) / self.length
+        } else {
+            0.0
+        };
+
+        Some(SDFHitInfo {
+            distance,
+            u: distance.clamp(0.0, 1.0),
+            v: v.clamp(0.0, 1.0),
+            w: 0.0,
+        })
+    }
+
+    fn bounding_box(&self) -> (Vec3f, Vec3f) {
+        self.bounding_box
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, self.clone())
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        let (nodes, radii) = self.nodes();
+        Ok(SDFDesc::Tube { nodes, radii })
+    }
+}
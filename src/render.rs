@@ -0,0 +1,432 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sampling scenes on regular grids, without Python
+//!
+//! A scene is sampled into a [`Volume`] over a [`Grid`], which can then be
+//! reduced to a maximum intensity projection or meshed by surface nets.
+
+use std::thread;
+
+use crate::sampler::hit_points;
+use crate::scene::Scene;
+use crate::vec3::{Float, Vec3f};
+
+/// Samples from `min` up to but excluding `max`, `stride` apart, as in
+/// `RangeSampler`.
+#[derive(Clone, Copy, Debug)]
+pub struct Grid {
+    min: Vec3f,
+    max: Vec3f,
+    stride: Vec3f,
+}
+
+impl Grid {
+    /// Create a grid, `stride` should be positive and the bounds finite.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on a non-positive stride or non-finite bounds.
+    pub fn new(min: Vec3f, max: Vec3f, stride: Vec3f) -> Result<Self, &'static str> {
+        if !(stride.x > 0.0 && stride.y > 0.0 && stride.z > 0.0) {
+            return Err("stride should be positive");
+        }
+
+        let finite = |v: Vec3f| v.x.is_finite() && v.y.is_finite() && v.z.is_finite();
+        if !finite(min) || !finite(max) {
+            return Err("bounds should be finite");
+        }
+
+        Ok(Self { min, max, stride })
+    }
+
+    /// Lower corner, the position of the first sample.
+    #[must_use]
+    pub fn min(&self) -> Vec3f {
+        self.min
+    }
+
+    /// Distance between neighbouring samples along each axis.
+    #[must_use]
+    pub fn stride(&self) -> Vec3f {
+        self.stride
+    }
+
+    /// Number of samples along each axis.
+    #[must_use]
+    pub fn shape(&self) -> [usize; 3] {
+        let size = (self.max - self.min) / self.stride;
+        let count = |n: Float| {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let n = n.ceil().max(0.0) as usize;
+            n
+        };
+        [count(size.x), count(size.y), count(size.z)]
+    }
+
+    /// Position of the sample at index `(i, j, k)`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn point(&self, i: usize, j: usize, k: usize) -> Vec3f {
+        self.min + Vec3f::new(i as Float, j as Float, k as Float) * self.stride
+    }
+}
+
+/// Channels sampled on a grid, in C order of `(x, y, z, channel)`.
+#[derive(Clone, Debug)]
+pub struct Volume {
+    /// Number of samples along each axis.
+    pub shape: [usize; 3],
+    /// Number of values per sample.
+    pub channels: usize,
+    /// Values of the samples, `shape[0] * shape[1] * shape[2] * channels`.
+    pub data: Vec<f32>,
+}
+
+/// Sample `scene` on `grid`, averaging `supersampling³` points spread over
+/// each voxel, with up to `threads` threads.
+///
+/// The slices along x are split between the threads, so the result does not
+/// depend on the number of threads.
+pub fn sample(scene: &dyn Scene, grid: &Grid, supersampling: usize, threads: usize) -> Volume {
+    let shape = grid.shape();
+    let [nx, ny, nz] = shape;
+    let channels = scene.channels();
+    let slice = ny * nz * channels;
+    let mut data = vec![0.0; nx * slice];
+
+    let per_thread = nx.div_ceil(threads.max(1)).max(1);
+    if slice > 0 {
+        thread::scope(|s| {
+            for (t, chunk) in data.chunks_mut(per_thread * slice).enumerate() {
+                s.spawn(move || {
+                    for (i, out) in chunk.chunks_mut(slice).enumerate() {
+                        sample_slice(scene, grid, supersampling, t * per_thread + i, out);
+                    }
+                });
+            }
+        });
+    }
+
+    Volume {
+        shape,
+        channels,
+        data,
+    }
+}
+
+/// Sample the slice at `i` along x into `out`.
+fn sample_slice(scene: &dyn Scene, grid: &Grid, supersampling: usize, i: usize, out: &mut [f32]) {
+    let [_, ny, nz] = grid.shape();
+    let points = (0..ny).flat_map(|j| (0..nz).map(move |k| grid.point(i, j, k)));
+    if supersampling <= 1 {
+        hit_points(scene, points, out);
+        return;
+    }
+
+    // sub-samples at the centers of a regular split of the voxel around each
+    // sample
+    #[allow(clippy::cast_precision_loss)]
+    let offset = |a: usize| (a as Float + 0.5) / supersampling as Float - 0.5;
+    let mut offsets = Vec::with_capacity(supersampling.pow(3));
+    for a in 0..supersampling {
+        for b in 0..supersampling {
+            for c in 0..supersampling {
+                offsets.push(Vec3f::new(offset(a), offset(b), offset(c)) * grid.stride);
+            }
+        }
+    }
+
+    let channels = scene.channels();
+    let mut hits = vec![0.0; out.len() * offsets.len()];
+    let points = points.flat_map(|p| offsets.iter().map(move |o| p + *o));
+    hit_points(scene, points, &mut hits);
+
+    #[allow(clippy::cast_precision_loss)]
+    let n = offsets.len() as f32;
+    let samples = hits.chunks_exact(channels * offsets.len());
+    for (out, hits) in out.chunks_exact_mut(channels).zip(samples) {
+        for hit in hits.chunks_exact(channels) {
+            for (o, h) in out.iter_mut().zip(hit) {
+                *o += h;
+            }
+        }
+
+        for o in out {
+            *o /= n;
+        }
+    }
+}
+
+impl Volume {
+    fn index(&self, [i, j, k]: [usize; 3]) -> usize {
+        let [_, ny, nz] = self.shape;
+        ((i * ny + j) * nz + k) * self.channels
+    }
+
+    /// Maximum intensity projection along `axis`, 0 for x up to 2 for z,
+    /// keeping the axis with a single sample.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `axis` is not 0, 1 or 2.
+    pub fn project(&self, axis: usize) -> Result<Volume, &'static str> {
+        let mut shape = self.shape;
+        match shape.get_mut(axis) {
+            Some(n) => *n = (*n).min(1),
+            None => return Err("axis should be 0, 1 or 2"),
+        }
+
+        let mut projection = Volume {
+            shape,
+            channels: self.channels,
+            data: vec![f32::NEG_INFINITY; shape.iter().product::<usize>() * self.channels],
+        };
+
+        let [_, ny, nz] = self.shape;
+        let samples = self.data.chunks_exact(self.channels.max(1));
+        for (n, sample) in samples.enumerate() {
+            let mut p = [n / (ny * nz), n / nz % ny, n % nz];
+            if let Some(x) = p.get_mut(axis) {
+                *x = 0;
+            }
+
+            let start = projection.index(p);
+            if let Some(out) = projection.data.get_mut(start..start + self.channels) {
+                for (o, v) in out.iter_mut().zip(sample) {
+                    *o = o.max(*v);
+                }
+            }
+        }
+
+        Ok(projection)
+    }
+}
+
+/// Triangle mesh, with counterclockwise faces seen from outside.
+#[derive(Clone, Debug, Default)]
+pub struct Mesh {
+    /// Positions of the vertices.
+    pub vertices: Vec<Vec3f>,
+    /// Indices of the vertices of each triangle.
+    pub triangles: Vec<[usize; 3]>,
+}
+
+/// Unit step along each axis, followed by the steps along the two other axes
+/// in right-handed order.
+const AXES: [[[usize; 3]; 3]; 3] = [
+    [[1, 0, 0], [0, 1, 0], [0, 0, 1]],
+    [[0, 1, 0], [0, 0, 1], [1, 0, 0]],
+    [[0, 0, 1], [1, 0, 0], [0, 1, 0]],
+];
+
+fn add([i, j, k]: [usize; 3], [a, b, c]: [usize; 3]) -> [usize; 3] {
+    [i + a, j + b, k + c]
+}
+
+fn sub([i, j, k]: [usize; 3], [a, b, c]: [usize; 3]) -> Option<[usize; 3]> {
+    Some([i.checked_sub(a)?, j.checked_sub(b)?, k.checked_sub(c)?])
+}
+
+/// Mesh the surface where `channel` of `volume`, sampled on `grid`, crosses
+/// `iso`, by surface nets. Samples above `iso` are inside.
+///
+/// # Errors
+///
+/// Returns an error when `channel` is out of range.
+pub fn surface_nets(
+    volume: &Volume,
+    grid: &Grid,
+    channel: usize,
+    iso: f32,
+) -> Result<Mesh, &'static str> {
+    if channel >= volume.channels {
+        return Err("channel out of range");
+    }
+
+    let [nx, ny, nz] = volume.shape;
+    let within = |[i, j, k]: [usize; 3]| i < nx && j < ny && k < nz;
+    let value = |p: [usize; 3]| {
+        if within(p) {
+            volume.data.get(volume.index(p) + channel).copied()
+        } else {
+            None
+        }
+    };
+
+    // one vertex per cell crossed by the surface, at the mean of the crossings
+    // on its edges
+    let cells = [nx, ny, nz].map(|n| n.saturating_sub(1));
+    let [cx, cy, cz] = cells;
+    let cell_index =
+        |[i, j, k]: [usize; 3]| (i < cx && j < cy && k < cz).then(|| (i * cy + j) * cz + k);
+    let mut vertex_of = vec![None; cx * cy * cz];
+    let mut mesh = Mesh::default();
+    for i in 0..cx {
+        for j in 0..cy {
+            for k in 0..cz {
+                let mut sum = Vec3f::new(0.0, 0.0, 0.0);
+                let mut crossings = 0_u8;
+                for [e, u, v] in AXES {
+                    for [a, b] in [[0, 0], [0, 1], [1, 0], [1, 1]] {
+                        let p0 = add(add([i, j, k], u.map(|x| x * a)), v.map(|x| x * b));
+                        let p1 = add(p0, e);
+                        let (Some(v0), Some(v1)) = (value(p0), value(p1)) else {
+                            continue;
+                        };
+                        if (v0 > iso) == (v1 > iso) {
+                            continue;
+                        }
+
+                        let t = Float::from((iso - v0) / (v1 - v0));
+                        #[allow(clippy::cast_precision_loss)]
+                        let [x, y, z] = p0.map(|x| x as Float);
+                        let [dx, dy, dz] = e.map(|x| if x == 0 { 0.0 } else { t });
+                        sum = sum + Vec3f::new(x + dx, y + dy, z + dz);
+                        crossings += 1;
+                    }
+                }
+
+                if crossings > 0 {
+                    let p = sum / Float::from(crossings);
+                    if let Some(slot) = cell_index([i, j, k]).and_then(|n| vertex_of.get_mut(n)) {
+                        *slot = Some(mesh.vertices.len());
+                    }
+                    mesh.vertices.push(grid.min + p * grid.stride);
+                }
+            }
+        }
+    }
+
+    // one quad per edge crossed by the surface, joining the four cells around
+    // it and facing away from the inside
+    let vertex = |p: Option<[usize; 3]>| -> Option<usize> {
+        cell_index(p?).and_then(|n| vertex_of.get(n).copied().flatten())
+    };
+    for i in 0..nx {
+        for j in 0..ny {
+            for k in 0..nz {
+                let p = [i, j, k];
+                for [e, u, v] in AXES {
+                    let (Some(v0), Some(v1)) = (value(p), value(add(p, e))) else {
+                        continue;
+                    };
+                    if (v0 > iso) == (v1 > iso) {
+                        continue;
+                    }
+
+                    let quad = [
+                        vertex(sub(p, add(u, v))),
+                        vertex(sub(p, v)),
+                        vertex(Some(p)),
+                        vertex(sub(p, u)),
+                    ];
+                    let [Some(a), Some(b), Some(c), Some(d)] = quad else {
+                        continue;
+                    };
+
+                    if v0 > iso {
+                        mesh.triangles.push([a, b, c]);
+                        mesh.triangles.push([a, c, d]);
+                    } else {
+                        mesh.triangles.push([a, c, b]);
+                        mesh.triangles.push([a, d, c]);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(mesh)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::material::{ChannelsMaterial, DynMaterial, Material};
+    use crate::object::SDFObject;
+    use crate::primitive::Sphere;
+    use crate::scene::ObjectsScene;
+    use crate::sdf::{DynSDF, SDF};
+    use crate::vec3;
+
+    fn sphere_scene() -> ObjectsScene {
+        let sdf: Arc<dyn SDF> = Arc::new(Sphere::new(Vec3f::new(0.0, 0.0, 0.0), 1.0));
+        let material: Arc<dyn Material> = Arc::new(ChannelsMaterial::new(vec![1.0], None).unwrap());
+        let object = SDFObject::new(DynSDF::from(sdf), DynMaterial::from(material));
+        let mut scene = ObjectsScene::new(1).unwrap();
+        scene.add_object(Arc::new(object), 0).unwrap();
+        scene
+    }
+
+    fn grid() -> Grid {
+        let min = Vec3f::new(-1.5, -1.5, -1.5);
+        let max = Vec3f::new(1.5, 1.5, 1.5);
+        Grid::new(min, max, Vec3f::new(0.25, 0.25, 0.25)).unwrap()
+    }
+
+    #[test]
+    fn test_sample() {
+        let scene = sphere_scene();
+        let volume = sample(&scene, &grid(), 1, 1);
+        assert_eq!(volume.shape, [12, 12, 12]);
+        assert_eq!(volume.data.len(), 12 * 12 * 12);
+
+        // the same samples whatever the number of threads
+        for threads in [2, 5, 64] {
+            assert_eq!(
+                sample(&scene, &grid(), 2, threads).data,
+                sample(&scene, &grid(), 2, 1).data
+            );
+        }
+
+        let supersampled = sample(&scene, &grid(), 3, 4);
+        assert!(supersampled.data.iter().any(|&v| v > 0.0 && v < 1.0));
+    }
+
+    #[test]
+    fn test_project() {
+        let volume = sample(&sphere_scene(), &grid(), 1, 2);
+        let projection = volume.project(2).unwrap();
+        assert_eq!(projection.shape, [12, 12, 1]);
+        assert!(volume.project(3).is_err());
+    }
+
+    #[test]
+    fn test_surface_nets() {
+        let grid = grid();
+        let volume = sample(&sphere_scene(), &grid, 1, 2);
+        let mesh = surface_nets(&volume, &grid, 0, 0.5).unwrap();
+        assert!(!mesh.triangles.is_empty());
+        assert!(surface_nets(&volume, &grid, 1, 0.5).is_err());
+
+        // closed: each edge is shared by two triangles, once in each direction
+        let mut edges = HashMap::new();
+        for &[a, b, c] in &mesh.triangles {
+            for edge in [(a, b), (b, c), (c, a)] {
+                *edges.entry(edge).or_insert(0) += 1;
+            }
+        }
+        for (&(a, b), &n) in &edges {
+            assert_eq!(n, 1);
+            assert_eq!(edges.get(&(b, a)), Some(&1));
+        }
+
+        // facing outward: a positive signed volume, close to the sphere's within
+        // the resolution of the mask
+        let volume: Float = mesh
+            .triangles
+            .iter()
+            .filter_map(|t| {
+                let [a, b, c] = t.map(|i| mesh.vertices.get(i).copied());
+                Some(vec3::dot(a?, vec3::cross(b?, c?)) / 6.0)
+            })
+            .sum();
+        let sphere = 4.0 / 3.0 * vec3::consts::PI;
+        assert!((volume / sphere - 1.0).abs() < 0.15, "{volume}");
+    }
+}
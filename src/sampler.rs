@@ -2,8 +2,38 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "python")]
 mod distribution;
+#[cfg(feature = "python")]
 mod range;
 
+#[cfg(feature = "python")]
 pub use distribution::UniformSampler;
+#[cfg(feature = "python")]
 pub use range::RangeSampler;
+
+use crate::scene::Scene;
+use crate::vec3::Vec3f;
+
+/// Number of points hit at once, bounding the memory of a batch.
+const BATCH_SIZE: usize = 4096;
+
+/// Hit `scene` at each of `points` in batches, writing `channels` values per
+/// point into `out`.
+pub(crate) fn hit_points(scene: &dyn Scene, points: impl Iterator<Item = Vec3f>, out: &mut [f32]) {
+    let mut chunks = out.chunks_mut(BATCH_SIZE * scene.channels());
+    let mut batch = Vec::with_capacity(BATCH_SIZE);
+    for p in points {
+        batch.push(p);
+        if batch.len() == BATCH_SIZE {
+            if let Some(out) = chunks.next() {
+                scene.hit_batch(&batch, out);
+            }
+            batch.clear();
+        }
+    }
+
+    if let Some(out) = chunks.next() {
+        scene.hit_batch(&batch, out);
+    }
+}
@@ -2,15 +2,20 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::Arc;
-
+#[cfg(feature = "python")]
 use numpy::{ndarray::prelude::*, IntoPyArray, PyArray2};
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 use rand::distributions::{Distribution, Uniform};
 use rand::rngs::ThreadRng;
 
+#[cfg(feature = "python")]
+use crate::callback;
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+use crate::sampler::hit_points;
 use crate::scene::{DynScene, Scene};
-use crate::vec3::Vec3f;
+use crate::vec3::{Float, Vec3f};
 
 /**
  * Distribution Sampler
@@ -18,38 +23,33 @@ use crate::vec3::Vec3f;
 #[derive(Debug)]
 pub struct DistributionSampler<D>
 where
-    D: Distribution<f32>,
+    D: Distribution<Float>,
 {
     dist: (D, D, D),
 }
 
 impl<D> DistributionSampler<D>
 where
-    D: Distribution<f32> + Copy,
+    D: Distribution<Float> + Copy,
 {
-    fn sample(&self, scene: Arc<dyn Scene>, count: usize) -> Array2<f32> {
+    fn sample(&self, scene: &dyn Scene, count: usize) -> Array2<f32> {
         let samples = DistributionSamples {
-            scene,
             count,
             rng: rand::thread_rng(),
             dist: self.dist,
         };
 
-        let mut flat = Vec::with_capacity(count * 3);
-        for v in samples {
-            flat.push(v.x);
-            flat.push(v.y);
-            flat.push(v.z);
-        }
-        Array::from_shape_vec((count, 3), flat).unwrap()
+        let channels = scene.channels();
+        let mut flat = vec![0.0; count * channels];
+        hit_points(scene, samples, &mut flat);
+        Array::from_shape_vec((count, channels), flat).unwrap()
     }
 }
 
 struct DistributionSamples<D>
 where
-    D: Distribution<f32>,
+    D: Distribution<Float>,
 {
-    scene: Arc<dyn Scene>,
     count: usize,
     rng: ThreadRng,
     dist: (D, D, D),
@@ -57,7 +57,7 @@ where
 
 impl<D> Iterator for DistributionSamples<D>
 where
-    D: Distribution<f32>,
+    D: Distribution<Float>,
 {
     type Item = Vec3f;
 
@@ -70,8 +70,7 @@ where
         let x = self.dist.0.sample(&mut self.rng);
         let y = self.dist.1.sample(&mut self.rng);
         let z = self.dist.2.sample(&mut self.rng);
-        let p = Vec3f::new(x, y, z);
-        Some(self.scene.hit(p))
+        Some(Vec3f::new(x, y, z))
     }
 }
 
@@ -79,36 +78,50 @@ where
  * Uniform Sampler
  */
 
-#[pyclass]
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
 #[derive(Debug)]
-pub struct UniformSampler(DistributionSampler<Uniform<f32>>);
+pub struct UniformSampler {
+    sampler: DistributionSampler<Uniform<Float>>,
+    min: Vec3f,
+    max: Vec3f,
+}
 
 impl UniformSampler {
     fn new(min: Vec3f, max: Vec3f) -> Self {
-        Self(DistributionSampler {
-            dist: (
-                Uniform::from(min.x..max.x),
-                Uniform::from(min.y..max.y),
-                Uniform::from(min.z..max.z),
-            ),
-        })
+        Self {
+            sampler: DistributionSampler {
+                dist: (
+                    Uniform::from(min.x..max.x),
+                    Uniform::from(min.y..max.y),
+                    Uniform::from(min.z..max.z),
+                ),
+            },
+            min,
+            max,
+        }
     }
 }
 
+#[cfg(feature = "python")]
 #[pymethods]
 impl UniformSampler {
     #[new]
-    fn __new__(min: (f32, f32, f32), max: (f32, f32, f32)) -> Self {
+    fn __new__(min: (Float, Float, Float), max: (Float, Float, Float)) -> Self {
         Self::new(min.into(), max.into())
     }
 
     fn sample<'py>(
         &self,
         py: Python<'py>,
-        scene: DynScene,
+        scene: &DynScene,
         count: usize,
-    ) -> Bound<'py, PyArray2<f32>> {
-        let samples = self.0.sample(scene.into(), count);
-        samples.into_pyarray(py)
+    ) -> PyResult<Bound<'py, PyArray2<f32>>> {
+        let samples = self.sampler.sample(scene, count);
+        callback::check()?;
+        Ok(samples.into_pyarray(py))
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::new::<Self>(py, (self.min, self.max))
     }
 }
@@ -2,15 +2,20 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::Arc;
-
+#[cfg(feature = "python")]
 use numpy::{ndarray::prelude::*, IntoPyArray, PyArray4};
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
+#[cfg(feature = "python")]
+use crate::callback;
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+use crate::sampler::hit_points;
 use crate::scene::{DynScene, Scene};
-use crate::vec3::Vec3f;
+use crate::vec3::{Float, Vec3f};
 
-#[pyclass]
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
 #[derive(Clone, Copy, Debug)]
 pub struct RangeSampler {
     min: Vec3f,
@@ -23,10 +28,9 @@ impl RangeSampler {
         Self { min, max, stride }
     }
 
-    fn sample(&self, scene: Arc<dyn Scene>) -> Array4<f32> {
+    fn sample(&self, scene: &dyn Scene) -> Array4<f32> {
         let samples = RangeSamples {
             cur: self.min,
-            scene,
 
             min: self.min,
             max: self.max,
@@ -34,13 +38,10 @@ impl RangeSampler {
         };
 
         let (x, y, z) = self.samples_shape();
-        let mut flat = Vec::with_capacity(x * y * z * 3);
-        for v in samples {
-            flat.push(v.x);
-            flat.push(v.y);
-            flat.push(v.z);
-        }
-        Array::from_shape_vec((x, y, z, 3), flat).unwrap()
+        let channels = scene.channels();
+        let mut flat = vec![0.0; x * y * z * channels];
+        hit_points(scene, samples, &mut flat);
+        Array::from_shape_vec((x, y, z, channels), flat).unwrap()
     }
 
     fn samples_shape(&self) -> (usize, usize, usize) {
@@ -54,23 +55,37 @@ impl RangeSampler {
     }
 }
 
+#[cfg(feature = "python")]
 #[pymethods]
 impl RangeSampler {
     #[new]
-    fn __new__(min: (f32, f32, f32), max: (f32, f32, f32), stride: (f32, f32, f32)) -> Self {
+    fn __new__(
+        min: (Float, Float, Float),
+        max: (Float, Float, Float),
+        stride: (Float, Float, Float),
+    ) -> Self {
         Self::new(min.into(), max.into(), stride.into())
     }
 
     #[pyo3(name = "sample")]
-    fn py_sample<'py>(&self, py: Python<'py>, scene: DynScene) -> Bound<'py, PyArray4<f32>> {
-        let samples = self.sample(scene.into());
-        samples.into_pyarray(py)
+    fn py_sample<'py>(
+        &self,
+        py: Python<'py>,
+        scene: &DynScene,
+    ) -> PyResult<Bound<'py, PyArray4<f32>>> {
+        let samples = self.sample(scene);
+        callback::check()?;
+        Ok(samples.into_pyarray(py))
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let args = (self.min, self.max, self.stride);
+        pickle::new::<Self>(py, args)
     }
 }
 
 pub struct RangeSamples {
     cur: Vec3f,
-    scene: Arc<dyn Scene>,
 
     min: Vec3f,
     max: Vec3f,
@@ -98,6 +113,6 @@ impl Iterator for RangeSamples {
         let p = self.cur;
         self.cur.z += self.stride.z;
 
-        Some(self.scene.hit(p))
+        Some(p)
     }
 }
@@ -2,53 +2,156 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeMap;
+#[cfg(feature = "python")]
+use std::path::PathBuf;
 use std::sync::Arc;
 
-use pyo3::{exceptions::PyValueError, prelude::*};
+#[cfg(feature = "python")]
+use pyo3::{
+    exceptions::{PyTypeError, PyValueError},
+    prelude::*,
+    types::PyTuple,
+};
+use serde::{Deserialize, Serialize};
 
-use crate::accelerator::{Accelerator, BVH};
-use crate::object::{DynObject, Object};
-use crate::vec3::{self, Vec3f};
+#[cfg(feature = "python")]
+use crate::accelerator;
+use crate::accelerator::{Accelerator, BVHMethod, FlatBVH};
+#[cfg(feature = "python")]
+use crate::callback;
+#[cfg(feature = "python")]
+use crate::description;
+use crate::description::{ObjectEntry, SceneDesc};
+#[cfg(feature = "python")]
+use crate::object::DynObject;
+use crate::object::Object;
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+use crate::sdf::SDFHitInfo;
+use crate::vec3::{self, Float, Vec3f};
 
+mod elastic;
+
+pub use elastic::ElasticScene;
+
+/// Channels sampled at points in space, built from objects.
 pub trait Scene: Send + Sync {
-    fn hit(&self, p: Vec3f) -> Vec3f;
+    /// Write the channels at `p` into `out`, which holds exactly `channels`
+    /// values.
+    fn hit(&self, p: Vec3f, out: &mut [f32]);
+
+    /// Write the channels at each of `ps` into `out`, `channels` values per
+    /// point.
+    fn hit_batch(&self, ps: &[Vec3f], out: &mut [f32]) {
+        for (p, out) in ps.iter().zip(out.chunks_exact_mut(self.channels())) {
+            self.hit(*p, out);
+        }
+    }
+
+    /// Number of values written per point.
+    fn channels(&self) -> usize;
+
+    /// Bounds of everything the scene draws, `None` when it is empty.
     fn bounding_box(&self) -> Option<(Vec3f, Vec3f)>;
+
+    /// Recipe rebuilding the scene from Python, for pickling.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the scene cannot be pickled, the default for
+    /// scenes defined outside of sdflit.
+    #[cfg(feature = "python")]
+    fn reduce(&self, _py: Python<'_>) -> PyResult<Reduce> {
+        Err(PyTypeError::new_err("scene cannot be pickled"))
+    }
+
+    /// Plain data describing the scene, for scene files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the scene holds nodes that cannot be saved, and
+    /// by default for scenes defined outside of sdflit.
+    fn describe(&self) -> Result<SceneDesc, &'static str> {
+        Err("scene cannot be saved to a scene file")
+    }
 }
 
 /**
  * Wrapper for scene
  */
 
-#[pyclass]
-#[pyo3(name = "Scene")]
+#[cfg_attr(feature = "python", pyclass(module = "sdflit", name = "Scene"))]
 #[derive(Clone)]
 #[allow(missing_debug_implementations)]
 pub struct DynScene(Arc<dyn Scene>);
 
+#[cfg(feature = "python")]
 #[pymethods]
 impl DynScene {
-    fn hit(&self, p: (f32, f32, f32)) -> (f32, f32, f32) {
-        self.0.hit(Vec3f::from(p)).into()
+    fn hit<'py>(&self, py: Python<'py>, p: (Float, Float, Float)) -> PyResult<Bound<'py, PyTuple>> {
+        let mut out = vec![0.0; self.0.channels()];
+        self.0.hit(Vec3f::from(p), &mut out);
+        callback::check()?;
+        PyTuple::new(py, out)
+    }
+
+    fn channels(&self) -> usize {
+        self.0.channels()
     }
 
     #[allow(clippy::type_complexity)]
-    fn bounding_box(&self) -> Option<((f32, f32, f32), (f32, f32, f32))> {
+    fn bounding_box(&self) -> Option<((Float, Float, Float), (Float, Float, Float))> {
         if let Some((min, max)) = self.0.bounding_box() {
             Some((min.into(), max.into()))
         } else {
             None
         }
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        self.0.reduce(py)
+    }
+
+    /// Save the scene to a JSON or TOML file, by the extension of `path`.
+    #[allow(clippy::needless_pass_by_value)]
+    fn save(&self, path: PathBuf) -> PyResult<()> {
+        description::save_scene(self.0.as_ref(), &path).map_err(PyValueError::new_err)
+    }
 }
 
 impl Scene for DynScene {
-    fn hit(&self, p: Vec3f) -> Vec3f {
-        self.0.hit(p)
+    fn hit(&self, p: Vec3f, out: &mut [f32]) {
+        self.0.hit(p, out);
+    }
+
+    fn hit_batch(&self, ps: &[Vec3f], out: &mut [f32]) {
+        self.0.hit_batch(ps, out);
+    }
+
+    fn channels(&self) -> usize {
+        self.0.channels()
     }
 
     fn bounding_box(&self) -> Option<(Vec3f, Vec3f)> {
         self.0.bounding_box()
     }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        self.0.reduce(py)
+    }
+
+    fn describe(&self) -> Result<SceneDesc, &'static str> {
+        self.0.describe()
+    }
+}
+
+impl DynScene {
+    /// Wrap `scene`, to be shared by the scenes deforming it.
+    pub fn new(scene: impl Scene + 'static) -> Self {
+        Self(Arc::new(scene))
+    }
 }
 
 impl From<Arc<dyn Scene>> for DynScene {
@@ -63,56 +166,243 @@ impl From<DynScene> for Arc<dyn Scene> {
     }
 }
 
+/// How the channels of overlapping objects are combined.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Blend {
+    /// The first object hit, ignoring opacity.
+    First,
+    /// Per-channel maximum of the background and the hits scaled by opacity.
+    Max,
+    /// Sum of the background and the hits scaled by opacity.
+    Additive,
+    /// Composite the hits front to back by opacity, objects of higher priority
+    /// in front.
+    AlphaOver,
+}
+
+impl TryFrom<&str> for Blend {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "first" | "First" => Ok(Self::First),
+            "max" | "Max" => Ok(Self::Max),
+            "additive" | "Additive" => Ok(Self::Additive),
+            "alpha_over" | "alpha-over" | "AlphaOver" => Ok(Self::AlphaOver),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Blend {
+    #[cfg(feature = "python")]
+    fn as_str(self) -> &'static str {
+        match self {
+            Blend::First => "first",
+            Blend::Max => "max",
+            Blend::Additive => "additive",
+            Blend::AlphaOver => "alpha_over",
+        }
+    }
+}
+
+/// Pickled state of an `ObjectsScene`: its objects and their priorities, the
+/// background, the blend and the method and leaf size of the BVH when built.
+#[cfg(feature = "python")]
+type ObjectsSceneState = (
+    Vec<DynObject>,
+    Vec<i32>,
+    Vec<f32>,
+    String,
+    Option<(String, usize)>,
+);
+
 /**
  * A scene with a list of objects
  */
 
-#[pyclass]
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
 #[derive(Clone)]
 #[allow(missing_debug_implementations)]
 pub struct ObjectsScene {
-    objects: Option<Vec<Arc<dyn Object>>>,
-    background: Vec3f,
+    objects: Option<Vec<Arc<dyn Object>>>, // sorted by descending priority
+    priorities: Vec<i32>,
+    channels: usize,
+    background: Vec<f32>,
+    blend: Blend,
     acceletor: Option<Arc<dyn Accelerator>>,
+    bvh: Option<(BVHMethod, usize)>, // build options of the accelerator
 }
 
-#[pymethods]
 impl ObjectsScene {
-    #[new]
-    pub fn new() -> Self {
-        Self {
+    /// Empty scene of `channels` channels.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `channels` is zero.
+    pub fn new(channels: usize) -> Result<Self, &'static str> {
+        if channels == 0 {
+            return Err("channels should be positive");
+        }
+
+        Ok(Self {
             objects: Some(Vec::new()),
-            background: Vec3f::new(0.0, 0.0, 0.0),
+            priorities: Vec::new(),
+            channels,
+            background: vec![0.0; channels],
+            blend: Blend::First,
             acceletor: None,
+            bvh: None,
+        })
+    }
+
+    /// Add `object`, drawn over the objects of lower `priority`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the channels of `object` do not match the scene, or
+    /// the scene is no longer editable after `build_bvh`.
+    pub fn add_object(
+        &mut self,
+        object: Arc<dyn Object>,
+        priority: i32,
+    ) -> Result<(), &'static str> {
+        if object.channels() != self.channels {
+            return Err("object channels should match the scene");
         }
+
+        let Some(objs) = &mut self.objects else {
+            return Err("scene is not editable");
+        };
+
+        let i = self.priorities.partition_point(|&q| q >= priority);
+        objs.insert(i, object);
+        self.priorities.insert(i, priority);
+        Ok(())
     }
 
-    pub fn add_object(&mut self, object: DynObject) -> PyResult<()> {
-        match &mut self.objects {
-            Some(objs) => {
-                objs.push(object.into());
-                Ok(())
-            }
-            None => Err(PyValueError::new_err("scene is not editable")),
+    /// Index the objects with a BVH built by `method` on up to `threads`
+    /// threads, with up to `leaf_size` objects per leaf, after which no object
+    /// can be added.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the BVH is already built or `leaf_size` is zero.
+    pub fn build_bvh(
+        &mut self,
+        method: BVHMethod,
+        leaf_size: usize,
+        threads: usize,
+    ) -> Result<(), &'static str> {
+        if leaf_size == 0 {
+            return Err("leaf size should be positive");
+        }
+
+        let Some(objs) = self.objects.take() else {
+            return Err("scene is not editable");
+        };
+
+        self.acceletor = Some(Arc::from(FlatBVH::build(objs, method, leaf_size, threads)?));
+        self.bvh = Some((method, leaf_size));
+        Ok(())
+    }
+
+    /// Expected cost of a query of the BVH, `None` before it is built.
+    #[must_use]
+    pub fn bvh_cost(&self) -> Option<Float> {
+        self.acceletor.as_ref().map(|acc| acc.cost())
+    }
+
+    /// Channels of the points missing every object.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `background` does not match the scene channels.
+    pub fn set_background(&mut self, background: Vec<f32>) -> Result<(), &'static str> {
+        if background.len() != self.channels {
+            return Err("background channels should match the scene");
         }
+
+        self.background = background;
+        Ok(())
     }
 
-    pub fn build_bvh(&mut self) -> PyResult<()> {
-        match self.objects.take() {
-            Some(objs) => {
-                self.acceletor = Some(Arc::from(BVH::new(objs)));
-                Ok(())
-            }
-            None => Err(PyValueError::new_err("scene is not editable")),
+    /// How overlapping objects are combined.
+    pub fn set_blend(&mut self, blend: Blend) {
+        self.blend = blend;
+    }
+
+    /// The objects in order of priority, whether the BVH is built or not.
+    fn all_objects(&self) -> &[Arc<dyn Object>] {
+        match (&self.acceletor, &self.objects) {
+            (Some(acc), _) => acc.objects(),
+            (None, Some(objs)) => objs.as_slice(),
+            (None, None) => &[],
         }
     }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl ObjectsScene {
+    #[new]
+    #[pyo3(signature = (channels = 3))]
+    fn __new__(channels: usize) -> PyResult<Self> {
+        Self::new(channels).map_err(PyValueError::new_err)
+    }
+
+    /// Add an object, which wins over the overlapping objects of a lower
+    /// `priority` and of the same priority added later.
+    #[pyo3(name = "add_object", signature = (object, priority = 0))]
+    fn py_add_object(&mut self, object: DynObject, priority: i32) -> PyResult<()> {
+        self.add_object(object.into(), priority)
+            .map_err(PyValueError::new_err)
+    }
 
-    pub fn set_background(&mut self, background: (f32, f32, f32)) {
-        self.background = background.into();
+    /// Build the BVH on up to `threads` threads, defaults to the available
+    /// parallelism.
+    #[pyo3(name = "build_bvh", signature = (method = "median", leaf_size = 1, threads = None))]
+    fn py_build_bvh(
+        &mut self,
+        method: &str,
+        leaf_size: usize,
+        threads: Option<usize>,
+    ) -> PyResult<()> {
+        let method = method
+            .try_into()
+            .map_err(|()| PyValueError::new_err("Invalid BVH method"))?;
+        let threads = threads.unwrap_or_else(accelerator::available_threads);
+        self.build_bvh(method, leaf_size, threads)
+            .map_err(PyValueError::new_err)
+    }
+
+    #[pyo3(name = "bvh_cost")]
+    fn py_bvh_cost(&self) -> Option<Float> {
+        self.bvh_cost()
+    }
+
+    #[pyo3(name = "set_background")]
+    fn py_set_background(&mut self, background: Vec<f32>) -> PyResult<()> {
+        self.set_background(background)
+            .map_err(PyValueError::new_err)
+    }
+
+    #[pyo3(name = "set_blend")]
+    fn py_set_blend(&mut self, blend: &str) -> PyResult<()> {
+        let blend = blend
+            .try_into()
+            .map_err(|()| PyValueError::new_err("Invalid blend"))?;
+        self.set_blend(blend);
+        Ok(())
+    }
+
+    fn channels(&self) -> usize {
+        self.channels
     }
 
     #[allow(clippy::type_complexity)]
-    pub fn bounding_box(&self) -> Option<((f32, f32, f32), (f32, f32, f32))> {
+    fn bounding_box(&self) -> Option<((Float, Float, Float), (Float, Float, Float))> {
         if let Some((min, max)) = Scene::bounding_box(self) {
             Some((min.into(), max.into()))
         } else {
@@ -120,19 +410,214 @@ impl ObjectsScene {
         }
     }
 
-    pub fn into(&self) -> DynScene {
+    fn into(&self) -> DynScene {
         DynScene(Arc::new(self.clone()))
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<(Py<PyAny>, Py<PyTuple>, ObjectsSceneState)> {
+        let (cls, args) = pickle::new::<Self>(py, (self.channels,))?;
+        Ok((cls, args, self.__getstate__()))
+    }
+
+    fn __getstate__(&self) -> ObjectsSceneState {
+        (
+            self.all_objects()
+                .iter()
+                .cloned()
+                .map(DynObject::from)
+                .collect(),
+            self.priorities.clone(),
+            self.background.clone(),
+            self.blend.as_str().to_owned(),
+            self.bvh
+                .map(|(method, leaf_size)| (method.as_str().to_owned(), leaf_size)),
+        )
+    }
+
+    fn __setstate__(&mut self, state: ObjectsSceneState) -> PyResult<()> {
+        let (objects, priorities, background, blend, bvh) = state;
+        for (object, priority) in objects.into_iter().zip(priorities) {
+            self.py_add_object(object, priority)?;
+        }
+
+        self.py_set_background(background)?;
+        self.py_set_blend(&blend)?;
+        if let Some((method, leaf_size)) = bvh {
+            self.py_build_bvh(&method, leaf_size, None)?;
+        }
+        Ok(())
+    }
 }
 
-impl Scene for ObjectsScene {
-    fn hit(&self, p: Vec3f) -> Vec3f {
-        let hit = match (&self.acceletor, &self.objects) {
-            (Some(acc), _) => acc.hit(p),
-            (None, Some(objs)) => objs.iter().find_map(|obj| obj.hit(p)),
+impl ObjectsScene {
+    fn hit_first(&self, p: Vec3f, out: &mut [f32]) -> Option<f32> {
+        match (&self.acceletor, &self.objects) {
+            (Some(acc), _) => acc.hit(p, out),
+            (None, Some(objs)) => objs
+                .iter()
+                .filter(|obj| contains(obj.bounding_box(), p))
+                .find_map(|obj| obj.hit(p, out)),
             (None, None) => panic!("unexpect mode"),
-        };
-        hit.unwrap_or(self.background)
+        }
+    }
+
+    /// Write the channels of the first hit at each of `ps` into `out`,
+    /// locating and shading the points of each object in a single batch.
+    fn hit_first_batch(&self, ps: &[Vec3f], out: &mut [f32]) {
+        let mut groups = BTreeMap::<usize, (Vec<usize>, Vec<Vec3f>, Vec<SDFHitInfo>)>::new();
+        let located = self.locate_batch(ps);
+        for (k, ((p, hit), out)) in ps
+            .iter()
+            .zip(located)
+            .zip(out.chunks_exact_mut(self.channels))
+            .enumerate()
+        {
+            if let Some((i, info)) = hit {
+                let (ks, ps, infos) = groups.entry(i).or_default();
+                ks.push(k);
+                ps.push(*p);
+                infos.push(info);
+            } else {
+                out.copy_from_slice(&self.background);
+            }
+        }
+
+        for (i, (ks, ps, infos)) in groups {
+            let Some(obj) = self.object(i) else { continue };
+            let mut buf = vec![0.0; ps.len() * self.channels];
+            obj.shade_batch(&ps, &infos, &mut buf);
+            for (k, channels) in ks.into_iter().zip(buf.chunks_exact(self.channels)) {
+                let start = k * self.channels;
+                if let Some(out) = out.get_mut(start..start + self.channels) {
+                    out.copy_from_slice(channels);
+                }
+            }
+        }
+    }
+
+    /// Index and hit info of the first object hit at each of `ps`, trying the
+    /// objects in order on the points they may contain and not yet hit.
+    fn locate_batch(&self, ps: &[Vec3f]) -> Vec<Option<(usize, SDFHitInfo)>> {
+        let mut candidates = BTreeMap::<usize, Vec<usize>>::new();
+        for (k, p) in ps.iter().enumerate() {
+            self.visit(*p, &mut |i, _| candidates.entry(i).or_default().push(k));
+        }
+
+        let mut located = vec![None; ps.len()];
+        for (i, ks) in candidates {
+            let ks = ks
+                .into_iter()
+                .filter(|&k| located.get(k).is_some_and(Option::is_none))
+                .collect::<Vec<_>>();
+            let Some(obj) = self.object(i).filter(|_| !ks.is_empty()) else {
+                continue;
+            };
+
+            let points = ks.iter().filter_map(|&k| ps.get(k).copied());
+            let points = points.collect::<Vec<_>>();
+            let mut infos = vec![None; points.len()];
+            obj.hit_info_batch(&points, &mut infos);
+            for (k, info) in ks.into_iter().zip(infos) {
+                if let (Some(info), Some(l)) = (info, located.get_mut(k)) {
+                    *l = Some((i, info));
+                }
+            }
+        }
+        located
+    }
+
+    /// Call `f` with the index, the opacity and the channels of every hit.
+    fn hit_all(&self, p: Vec3f, f: &mut dyn FnMut(usize, f32, &[f32])) {
+        let mut buf = vec![0.0; self.channels];
+        self.visit(p, &mut |i, obj| {
+            if let Some(alpha) = obj.hit(p, &mut buf) {
+                f(i, alpha, &buf);
+            }
+        });
+    }
+
+    /// Call `f` with the index and the object of every object that may
+    /// contain `p`.
+    fn visit(&self, p: Vec3f, f: &mut dyn FnMut(usize, &dyn Object)) {
+        match (&self.acceletor, &self.objects) {
+            (Some(acc), _) => acc.visit(p, f),
+            (None, Some(objs)) => objs
+                .iter()
+                .enumerate()
+                .filter(|(_, obj)| contains(obj.bounding_box(), p))
+                .for_each(|(i, obj)| f(i, obj.as_ref())),
+            (None, None) => panic!("unexpect mode"),
+        }
+    }
+
+    fn object(&self, i: usize) -> Option<&dyn Object> {
+        match (&self.acceletor, &self.objects) {
+            (Some(acc), _) => acc.object(i),
+            (None, Some(objs)) => objs.get(i).map(AsRef::as_ref),
+            (None, None) => panic!("unexpect mode"),
+        }
+    }
+}
+
+impl Scene for ObjectsScene {
+    fn hit(&self, p: Vec3f, out: &mut [f32]) {
+        match self.blend {
+            Blend::First => {
+                if self.hit_first(p, out).is_none() {
+                    out.copy_from_slice(&self.background);
+                }
+            }
+            Blend::Max => {
+                out.copy_from_slice(&self.background);
+                self.hit_all(p, &mut |_, alpha, channels| {
+                    for (o, c) in out.iter_mut().zip(channels) {
+                        *o = o.max(alpha * c);
+                    }
+                });
+            }
+            Blend::Additive => {
+                out.copy_from_slice(&self.background);
+                self.hit_all(p, &mut |_, alpha, channels| {
+                    for (o, c) in out.iter_mut().zip(channels) {
+                        *o += alpha * c;
+                    }
+                });
+            }
+            Blend::AlphaOver => {
+                let mut hits = Vec::new();
+                self.hit_all(p, &mut |i, alpha, channels| {
+                    hits.push((i, alpha, channels.to_vec()));
+                });
+                hits.sort_by_key(|(i, _, _)| *i);
+
+                out.fill(0.0);
+                let mut transmittance = 1.0;
+                for (_, alpha, channels) in hits {
+                    for (o, c) in out.iter_mut().zip(channels) {
+                        *o += transmittance * alpha * c;
+                    }
+                    transmittance *= 1.0 - alpha;
+                }
+                for (o, b) in out.iter_mut().zip(&self.background) {
+                    *o += transmittance * b;
+                }
+            }
+        }
+    }
+
+    fn hit_batch(&self, ps: &[Vec3f], out: &mut [f32]) {
+        match self.blend {
+            Blend::First => self.hit_first_batch(ps, out),
+            _ => {
+                for (p, out) in ps.iter().zip(out.chunks_exact_mut(self.channels)) {
+                    self.hit(*p, out);
+                }
+            }
+        }
+    }
+
+    fn channels(&self) -> usize {
+        self.channels
     }
 
     fn bounding_box(&self) -> Option<(Vec3f, Vec3f)> {
@@ -151,4 +636,36 @@ impl Scene for ObjectsScene {
             (None, None) => None,
         }
     }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, self.clone())
+    }
+
+    fn describe(&self) -> Result<SceneDesc, &'static str> {
+        let objects = self.all_objects().iter().zip(&self.priorities);
+        let objects = objects
+            .map(|(object, &priority)| {
+                Ok(ObjectEntry {
+                    object: object.describe()?,
+                    priority,
+                })
+            })
+            .collect::<Result<_, &'static str>>()?;
+        Ok(SceneDesc::Objects {
+            channels: self.channels,
+            objects,
+            background: self.background.clone(),
+            blend: self.blend,
+            bvh: self.bvh.is_some(),
+            bvh_method: self
+                .bvh
+                .map_or_else(BVHMethod::default, |(method, _)| method),
+            bvh_leaf_size: self.bvh.map_or(1, |(_, leaf_size)| leaf_size),
+        })
+    }
+}
+
+fn contains((min, max): (Vec3f, Vec3f), p: Vec3f) -> bool {
+    p.x >= min.x && p.y >= min.y && p.z >= min.z && p.x <= max.x && p.y <= max.y && p.z <= max.z
 }
@@ -0,0 +1,272 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+#[cfg(feature = "python")]
+use pyo3::{exceptions::PyValueError, prelude::*};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::description::SceneDesc;
+#[cfg(feature = "python")]
+use crate::pickle::{self, Reduce};
+#[cfg(feature = "python")]
+use crate::scene::DynScene;
+use crate::scene::Scene;
+use crate::vec3::consts::PI;
+use crate::vec3::{Float, Vec3f};
+
+/**
+ * Elastic deformation of a scene
+ *
+ * Query points are displaced by a smooth random field before hitting the
+ * inner scene. The field is a cubic B-spline over a grid of control points,
+ * with displacements drawn from a normal distribution of `sigma`.
+ */
+
+#[cfg_attr(feature = "python", pyclass(module = "sdflit"))]
+#[derive(Clone)]
+#[allow(missing_debug_implementations)]
+pub struct ElasticScene {
+    scene: Arc<dyn Scene>,
+    spacing: Vec3f,
+    sigma: Float,
+    seed: u64,
+    field: Arc<BSplineField>,
+    bounding_box: Option<(Vec3f, Vec3f)>,
+}
+
+impl ElasticScene {
+    /// Deform `scene` by a field of control points every `spacing`, displaced
+    /// by `sigma` and drawn from `seed`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `spacing` is not positive, `sigma` is negative or
+    /// not finite, `scene` has no finite bounding box or the field would need
+    /// more than `2^24` control points.
+    pub fn new(
+        scene: Arc<dyn Scene>,
+        spacing: Vec3f,
+        sigma: Float,
+        seed: u64,
+    ) -> Result<ElasticScene, &'static str> {
+        // NaN fails every comparison, so check for the valid values
+        let positive = |x: Float| x.is_finite() && x > 0.0;
+        if !(positive(spacing.x) && positive(spacing.y) && positive(spacing.z)) {
+            return Err("spacing should be positive");
+        }
+
+        if !sigma.is_finite() || sigma < 0.0 {
+            return Err("sigma should be finite and not negative");
+        }
+
+        let Some((min, max)) = scene.bounding_box() else {
+            return Err("scene should have a bounding box");
+        };
+
+        let size = max - min;
+        if !(size.x.is_finite() && size.y.is_finite() && size.z.is_finite()) {
+            return Err("scene should have a finite bounding box");
+        }
+
+        let shape = BSplineField::shape(min, max, spacing)
+            .ok_or("spacing is too small for the size of the scene")?;
+        let field = BSplineField::random(min, shape, spacing, sigma, seed);
+        let m = field.max_norm();
+        Ok(Self {
+            scene,
+            spacing,
+            sigma,
+            seed,
+            field: Arc::new(field),
+            bounding_box: Some((min - m, max + m)),
+        })
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl ElasticScene {
+    #[new]
+    #[pyo3(signature = (scene, spacing, sigma, seed = 0))]
+    fn __new__(
+        scene: DynScene,
+        spacing: (Float, Float, Float),
+        sigma: Float,
+        seed: u64,
+    ) -> PyResult<ElasticScene> {
+        Self::new(scene.into(), spacing.into(), sigma, seed).map_err(PyValueError::new_err)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn bounding_box(&self) -> Option<((Float, Float, Float), (Float, Float, Float))> {
+        Scene::bounding_box(self).map(|(min, max)| (min.into(), max.into()))
+    }
+
+    fn into(&self) -> DynScene {
+        let arc: Arc<dyn Scene> = Arc::new(self.clone());
+        DynScene::from(arc)
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        let scene = DynScene::from(self.scene.clone());
+        let args = (scene, self.spacing, self.sigma, self.seed);
+        pickle::new::<Self>(py, args)
+    }
+}
+
+impl Scene for ElasticScene {
+    fn hit(&self, p: Vec3f, out: &mut [f32]) {
+        self.scene.hit(p + self.field.at(p), out);
+    }
+
+    fn hit_batch(&self, ps: &[Vec3f], out: &mut [f32]) {
+        let ps = ps.iter().map(|&p| p + self.field.at(p)).collect::<Vec<_>>();
+        self.scene.hit_batch(&ps, out);
+    }
+
+    fn channels(&self) -> usize {
+        self.scene.channels()
+    }
+
+    fn bounding_box(&self) -> Option<(Vec3f, Vec3f)> {
+        self.bounding_box
+    }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        pickle::into(py, self.clone())
+    }
+
+    fn describe(&self) -> Result<SceneDesc, &'static str> {
+        Ok(SceneDesc::Elastic {
+            scene: Box::new(self.scene.describe()?),
+            spacing: self.spacing,
+            sigma: self.sigma,
+            seed: self.seed,
+        })
+    }
+}
+
+/// Most control points of a field, about 200 MB of single precision values.
+const MAX_CONTROL_POINTS: usize = 1 << 24;
+
+/**
+ * Cubic B-spline vector field over a regular grid of control points
+ */
+struct BSplineField {
+    values: Vec<Vec3f>, // in C order of (x, y, z)
+    shape: (usize, usize, usize),
+    origin: Vec3f,
+    spacing: Vec3f,
+}
+
+impl BSplineField {
+    /// Control points covering `[min, max]`, padded so that every point inside
+    /// is supported by a full 4x4x4 neighbourhood, `None` when they exceed
+    /// `MAX_CONTROL_POINTS`.
+    fn shape(min: Vec3f, max: Vec3f, spacing: Vec3f) -> Option<(usize, usize, usize)> {
+        let size = (max - min) / spacing;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let count = |x: Float| (x.ceil() as usize).checked_add(4); // saturating cast
+        let shape = (count(size.x)?, count(size.y)?, count(size.z)?);
+        shape
+            .0
+            .checked_mul(shape.1)?
+            .checked_mul(shape.2)
+            .filter(|&n| n <= MAX_CONTROL_POINTS)
+            .map(|_| shape)
+    }
+
+    /// Random field of `shape` control points from `min`, see `shape`.
+    fn random(
+        min: Vec3f,
+        shape: (usize, usize, usize),
+        spacing: Vec3f,
+        sigma: Float,
+        seed: u64,
+    ) -> BSplineField {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut normal = || {
+            // Box-Muller transform
+            let (u1, u2): (Float, Float) = (1.0 - rng.gen::<Float>(), rng.gen());
+            sigma * Float::sqrt(-2.0 * u1.ln()) * Float::cos(2.0 * PI * u2)
+        };
+
+        let values = (0..shape.0 * shape.1 * shape.2)
+            .map(|_| Vec3f::new(normal(), normal(), normal()))
+            .collect();
+
+        Self {
+            values,
+            shape,
+            origin: min - spacing,
+            spacing,
+        }
+    }
+
+    fn get(&self, i: isize, j: isize, k: isize) -> Vec3f {
+        let (nx, ny, nz) = self.shape;
+        match (
+            usize::try_from(i).ok().filter(|i| *i < nx),
+            usize::try_from(j).ok().filter(|j| *j < ny),
+            usize::try_from(k).ok().filter(|k| *k < nz),
+        ) {
+            (Some(i), Some(j), Some(k)) => self
+                .values
+                .get((i * ny + j) * nz + k)
+                .copied()
+                .unwrap_or(Vec3f::new(0.0, 0.0, 0.0)),
+            // control points outside the grid have no displacement
+            _ => Vec3f::new(0.0, 0.0, 0.0),
+        }
+    }
+
+    fn at(&self, p: Vec3f) -> Vec3f {
+        let g = (p - self.origin) / self.spacing;
+        let (fx, fy, fz) = (g.x.floor(), g.y.floor(), g.z.floor());
+
+        // no control point supports a cell beyond the grid, nor a NaN, and
+        // the indices of the far ones would overflow
+        #[allow(clippy::cast_precision_loss)]
+        let near = |f: Float, n: usize| (-2.0..=n as Float).contains(&f);
+        let (nx, ny, nz) = self.shape;
+        if !(near(fx, nx) && near(fy, ny) && near(fz, nz)) {
+            return Vec3f::new(0.0, 0.0, 0.0);
+        }
+
+        let (wx, wy, wz) = (weights(g.x - fx), weights(g.y - fy), weights(g.z - fz));
+        #[allow(clippy::cast_possible_truncation)]
+        let (i, j, k) = (fx as isize, fy as isize, fz as isize);
+
+        let mut v = Vec3f::new(0.0, 0.0, 0.0);
+        for (di, wi) in (-1..=2).zip(wx) {
+            for (dj, wj) in (-1..=2).zip(wy) {
+                for (dk, wk) in (-1..=2).zip(wz) {
+                    v = v + self.get(i + di, j + dj, k + dk) * (wi * wj * wk);
+                }
+            }
+        }
+        v
+    }
+
+    /// The weights are non-negative and sum up to one, so that the field never
+    /// exceeds its largest control point.
+    fn max_norm(&self) -> Float {
+        self.values.iter().map(|v| v.norm()).fold(0.0, Float::max)
+    }
+}
+
+/// Uniform cubic B-spline basis at `t` in `[0, 1)`.
+fn weights(t: Float) -> [Float; 4] {
+    let s = 1.0 - t;
+    [
+        s * s * s / 6.0,
+        (3.0 * t * t * t - 6.0 * t * t + 4.0) / 6.0,
+        (-3.0 * t * t * t + 3.0 * t * t + 3.0 * t + 1.0) / 6.0,
+        t * t * t / 6.0,
+    ]
+}
@@ -4,82 +4,224 @@
 
 use std::sync::Arc;
 
-use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::{exceptions::PyTypeError, prelude::*};
 
-use crate::vec3::Vec3f;
+#[cfg(feature = "python")]
+use crate::callback;
+#[cfg(feature = "python")]
+use crate::composition::{intersect, merge, subtract};
+use crate::description::SDFDesc;
+#[cfg(feature = "python")]
+use crate::pickle::Reduce;
+use crate::vec3::{Float, Vec3f};
 
+/// Signed distance field, negative inside the surface.
 #[allow(clippy::upper_case_acronyms)]
 pub trait SDF: Send + Sync {
-    fn distance(&self, p: Vec3f) -> f32;
+    /// Signed distance from `p` to the surface.
+    fn distance(&self, p: Vec3f) -> Float;
 
+    /// Write the distance at each of `ps` into `out`.
+    fn distance_batch(&self, ps: &[Vec3f], out: &mut [Float]) {
+        for (p, out) in ps.iter().zip(out) {
+            *out = self.distance(*p);
+        }
+    }
+
+    /// Locate `p` on the SDF, `None` when it is outside.
     fn hit(&self, p: Vec3f) -> Option<SDFHitInfo> {
-        let sd = self.distance(p);
-        if sd < 0.0 {
-            Some(SDFHitInfo {
-                distance: sd,
-                u: 0.0,
-                v: 0.0,
-                w: 0.0,
-            })
-        } else {
-            None
+        hit_from_distance(self.distance(p))
+    }
+
+    /// Write the hit at each of `ps` into `out`.
+    fn hit_batch(&self, ps: &[Vec3f], out: &mut [Option<SDFHitInfo>]) {
+        for (p, out) in ps.iter().zip(out) {
+            *out = self.hit(*p);
         }
     }
 
+    /// Whether `p` is strictly inside the surface.
     fn inside(&self, p: Vec3f) -> bool {
         self.inside_bounding_box(p) && self.distance(p) < 0.0
     }
 
+    /// Corners of a box containing the whole surface.
     fn bounding_box(&self) -> (Vec3f, Vec3f);
 
+    /// Whether `p` is inside the bounding box, borders included.
     fn inside_bounding_box(&self, p: Vec3f) -> bool {
         let (min, max) = self.bounding_box();
         p.x >= min.x && p.y >= min.y && p.z >= min.z && p.x <= max.x && p.y <= max.y && p.z <= max.z
     }
+
+    /// Recipe rebuilding the SDF from Python, for pickling.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the SDF cannot be pickled, the default for
+    /// SDFs defined outside of sdflit.
+    #[cfg(feature = "python")]
+    fn reduce(&self, _py: Python<'_>) -> PyResult<Reduce> {
+        Err(PyTypeError::new_err("SDF cannot be pickled"))
+    }
+
+    /// Plain data describing the SDF, for scene files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the SDF cannot be saved, the default for
+    /// SDFs defined outside of sdflit.
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        Err("SDF cannot be saved to a scene file")
+    }
 }
 
+/// Where a point hits an SDF, for materials to shade it.
 #[derive(Clone, Copy, Debug)]
 pub struct SDFHitInfo {
-    pub distance: f32,
-    pub u: f32,
-    pub v: f32,
-    pub w: f32,
+    /// Signed distance to the surface, negative inside.
+    pub distance: Float,
+    /// First surface coordinate in `[0, 1]`, as defined by each primitive.
+    pub u: Float,
+    /// Second surface coordinate in `[0, 1]`, as defined by each primitive.
+    pub v: Float,
+    /// Third surface coordinate in `[0, 1]`, as defined by each primitive.
+    pub w: Float,
+}
+
+/// Hit info of a point at `distance`, for SDFs without a parametrization.
+pub(crate) fn hit_from_distance(distance: Float) -> Option<SDFHitInfo> {
+    if distance < 0.0 {
+        Some(SDFHitInfo {
+            distance,
+            u: 0.0,
+            v: 0.0,
+            w: 0.0,
+        })
+    } else {
+        None
+    }
+}
+
+/// Batched `hit_from_distance`, for SDFs overriding `distance_batch`.
+pub(crate) fn hit_batch_from_distance(sdf: &dyn SDF, ps: &[Vec3f], out: &mut [Option<SDFHitInfo>]) {
+    let mut distances = vec![0.0; ps.len()];
+    sdf.distance_batch(ps, &mut distances);
+    for (out, d) in out.iter_mut().zip(distances) {
+        *out = hit_from_distance(d);
+    }
 }
 
 /**
  * Wrapper for SDF
  */
 
-#[pyclass]
-#[pyo3(name = "SDF")]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "sdflit", skip_from_py_object, name = "SDF")
+)]
 #[derive(Clone)]
 #[allow(missing_debug_implementations)]
 pub struct DynSDF(Arc<dyn SDF>);
 
+/// Accept the primitives directly, as well as any SDF converted by `into()`.
+#[cfg(feature = "python")]
+impl<'a, 'py> FromPyObject<'a, 'py> for DynSDF {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        if let Ok(sdf) = obj.cast::<DynSDF>() {
+            return Ok(sdf.borrow().clone());
+        }
+
+        if let Ok(sdf) = obj.call_method0("into") {
+            if let Ok(sdf) = sdf.cast::<DynSDF>() {
+                return Ok(sdf.borrow().clone());
+            }
+        }
+
+        Err(PyTypeError::new_err("expected an SDF"))
+    }
+}
+
+#[cfg(feature = "python")]
 #[pymethods]
 impl DynSDF {
-    fn distance(&self, p: (f32, f32, f32)) -> f32 {
-        SDF::distance(self, Vec3f::from(p))
+    fn distance(&self, p: (Float, Float, Float)) -> PyResult<Float> {
+        let distance = SDF::distance(self, Vec3f::from(p));
+        callback::check()?;
+        Ok(distance)
     }
 
-    fn inside(&self, p: (f32, f32, f32)) -> bool {
-        SDF::inside(self, Vec3f::from(p))
+    fn inside(&self, p: (Float, Float, Float)) -> PyResult<bool> {
+        let inside = SDF::inside(self, Vec3f::from(p));
+        callback::check()?;
+        Ok(inside)
     }
 
-    fn bounding_box(&self) -> ((f32, f32, f32), (f32, f32, f32)) {
+    fn bounding_box(&self) -> ((Float, Float, Float), (Float, Float, Float)) {
         let (min, max) = SDF::bounding_box(self);
         (min.into(), max.into())
     }
+
+    fn __or__(&self, other: DynSDF) -> DynSDF {
+        merge(self.clone(), other)
+    }
+
+    fn __and__(&self, other: DynSDF) -> DynSDF {
+        intersect(self.clone(), other)
+    }
+
+    fn __sub__(&self, other: DynSDF) -> DynSDF {
+        subtract(self.clone(), other)
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<Reduce> {
+        self.0.reduce(py)
+    }
 }
 
 impl SDF for DynSDF {
-    fn distance(&self, p: Vec3f) -> f32 {
+    fn distance(&self, p: Vec3f) -> Float {
         self.0.distance(p)
     }
 
+    fn distance_batch(&self, ps: &[Vec3f], out: &mut [Float]) {
+        self.0.distance_batch(ps, out);
+    }
+
+    fn hit(&self, p: Vec3f) -> Option<SDFHitInfo> {
+        self.0.hit(p)
+    }
+
+    fn hit_batch(&self, ps: &[Vec3f], out: &mut [Option<SDFHitInfo>]) {
+        self.0.hit_batch(ps, out);
+    }
+
+    fn inside(&self, p: Vec3f) -> bool {
+        self.0.inside(p)
+    }
+
     fn bounding_box(&self) -> (Vec3f, Vec3f) {
         self.0.bounding_box()
     }
+
+    #[cfg(feature = "python")]
+    fn reduce(&self, py: Python<'_>) -> PyResult<Reduce> {
+        self.0.reduce(py)
+    }
+
+    fn describe(&self) -> Result<SDFDesc, &'static str> {
+        self.0.describe()
+    }
+}
+
+impl DynSDF {
+    /// Wrap `sdf`, to be shared by composed SDFs and objects.
+    pub fn new(sdf: impl SDF + 'static) -> Self {
+        Self(Arc::new(sdf))
+    }
 }
 
 impl From<Arc<dyn SDF>> for DynSDF {
@@ -87,3 +229,38 @@ impl From<Arc<dyn SDF>> for DynSDF {
         Self(sdf)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::material::{ColoredMaterial, DynMaterial};
+    use crate::object::{Object, SDFObject};
+    use crate::scene::{ObjectsScene, Scene};
+
+    /// An SDF defined outside of the crate, with only the required methods.
+    struct Cube;
+
+    impl SDF for Cube {
+        fn distance(&self, p: Vec3f) -> Float {
+            p.x.abs().max(p.y.abs()).max(p.z.abs()) - 1.0
+        }
+
+        fn bounding_box(&self) -> (Vec3f, Vec3f) {
+            (Vec3f::new(-1.0, -1.0, -1.0), Vec3f::new(1.0, 1.0, 1.0))
+        }
+    }
+
+    #[test]
+    fn test_custom() {
+        let material = DynMaterial::new(ColoredMaterial::new(Vec3f::new(1.0, 0.0, 0.0)));
+        let object = SDFObject::new(DynSDF::new(Cube), material);
+        assert!(object.hit_info(Vec3f::new(0.9, 0.9, 0.0)).is_some());
+        assert!(object.describe().is_err());
+
+        let mut scene = ObjectsScene::new(3).unwrap();
+        scene.add_object(Arc::new(object), 0).unwrap();
+        assert!(scene.describe().is_err());
+    }
+}
@@ -2,9 +2,9 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::vec3::{self, Vec3f};
+use crate::vec3::{self, Float, Vec3f};
 
-pub fn proj_p_to_line(p: Vec3f, a: Vec3f, b: Vec3f) -> f32 {
+pub fn proj_p_to_line(p: Vec3f, a: Vec3f, b: Vec3f) -> Float {
     let ap = p - a;
     let ab = b - a;
     let ao = a + ab * (vec3::dot(ap, ab) / vec3::dot(ab, ab)); // proj P to AB in O
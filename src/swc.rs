@@ -0,0 +1,196 @@
+// SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::accelerator::{self, BVHMethod};
+use crate::material::{ChannelsMaterial, DynMaterial, Material};
+use crate::object::SDFObject;
+use crate::primitive::{Sphere, Tube};
+use crate::scene::{ObjectsScene, Scene};
+use crate::sdf::{DynSDF, SDF};
+use crate::vec3::{Float, Vec3f};
+
+/**
+ * SWC neuron morphology
+ *
+ * Each line holds `id type x y z radius parent`, with a negative parent on the
+ * roots. The tree is split into unbranched paths, each rendered as a tube
+ * from the branch point it starts at, in a single channel mask.
+ */
+
+#[derive(Clone, Copy, Debug)]
+struct Node {
+    id: i64,
+    position: Vec3f,
+    radius: Float,
+    parent: i64,
+}
+
+/// Load a SWC file as a single channel scene, with its BVH built.
+///
+/// # Errors
+///
+/// Returns an error when the file cannot be read or does not hold a tree.
+pub fn load_swc(path: &Path) -> Result<Arc<dyn Scene>, String> {
+    let text =
+        fs::read_to_string(path).map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+    let nodes = parse(&text).map_err(|e| format!("{}: {e}", path.display()))?;
+    build(&nodes)
+}
+
+fn parse(text: &str) -> Result<Vec<Node>, String> {
+    let mut nodes = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let node = parse_node(line).map_err(|e| format!("line {}: {e}", i + 1))?;
+        nodes.push(node);
+    }
+    Ok(nodes)
+}
+
+fn parse_node(line: &str) -> Result<Node, String> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [id, _kind, x, y, z, radius, parent] = fields.as_slice() else {
+        return Err(format!("expected 7 fields, got {}", fields.len()));
+    };
+
+    let float = |s: &str| {
+        s.parse::<Float>()
+            .map_err(|e| format!("invalid number {s}: {e}"))
+    };
+    let int = |s: &str| s.parse::<i64>().map_err(|e| format!("invalid id {s}: {e}"));
+    Ok(Node {
+        id: int(id)?,
+        position: Vec3f::new(float(x)?, float(y)?, float(z)?),
+        radius: float(radius)?,
+        parent: int(parent)?,
+    })
+}
+
+fn build(nodes: &[Node]) -> Result<Arc<dyn Scene>, String> {
+    let mut index = HashMap::with_capacity(nodes.len());
+    for (i, node) in nodes.iter().enumerate() {
+        if index.insert(node.id, i).is_some() {
+            return Err(format!("duplicate node {}", node.id));
+        }
+    }
+
+    let mut children = vec![Vec::new(); nodes.len()];
+    let mut roots = Vec::new();
+    for (i, node) in nodes.iter().enumerate() {
+        if node.parent < 0 {
+            roots.push(i);
+        } else if let Some(&parent) = index.get(&node.parent) {
+            if let Some(c) = children.get_mut(parent) {
+                c.push(i);
+            }
+        } else {
+            return Err(format!(
+                "node {} has an unknown parent {}",
+                node.id, node.parent
+            ));
+        }
+    }
+
+    let material: Arc<dyn Material> = Arc::new(ChannelsMaterial::new(vec![1.0], None)?);
+    let mut scene = ObjectsScene::new(1)?;
+    let mut add = |sdf: Arc<dyn SDF>| {
+        let object = SDFObject::new(DynSDF::from(sdf), DynMaterial::from(material.clone()));
+        scene.add_object(Arc::new(object), 0)
+    };
+
+    // walk down from each root, a path runs from a branch point (or root) to
+    // the next branch point or leaf
+    let mut starts: Vec<(usize, usize)> = Vec::new();
+    for &root in &roots {
+        let (Some(node), Some(c)) = (nodes.get(root), children.get(root)) else {
+            continue;
+        };
+        if c.is_empty() {
+            add(Arc::new(Sphere::new(node.position, node.radius)))?;
+        }
+        starts.extend(c.iter().map(|&child| (root, child)));
+    }
+
+    let mut visited = 0;
+    while let Some((from, mut cur)) = starts.pop() {
+        let mut path = vec![from, cur];
+        loop {
+            visited += 1;
+            match children.get(cur).map(Vec::as_slice) {
+                Some(&[next]) => {
+                    path.push(next);
+                    cur = next;
+                }
+                Some(c) => {
+                    starts.extend(c.iter().map(|&child| (cur, child)));
+                    break;
+                }
+                None => break,
+            }
+        }
+
+        let path: Vec<&Node> = path.iter().filter_map(|&i| nodes.get(i)).collect();
+        let points: Vec<Vec3f> = path.iter().map(|n| n.position).collect();
+        let radii: Vec<Float> = path.iter().map(|n| n.radius).collect();
+        add(Arc::new(Tube::new(&points, &radii)?))?;
+    }
+
+    if visited + roots.len() != nodes.len() {
+        return Err("the tree contains a cycle".to_owned());
+    }
+
+    scene.build_bvh(BVHMethod::Median, 1, accelerator::available_threads())?;
+    Ok(Arc::new(scene))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SWC: &str = "\
+# a soma with two branches
+1 1 0 0 0 2 -1
+2 3 4 0 0 1 1
+3 3 8 0 0 1 2
+4 3 8 4 0 1 3
+5 3 8 -4 0 1 3
+";
+
+    #[test]
+    fn test_swc() {
+        let nodes = parse(SWC).unwrap();
+        assert_eq!(nodes.len(), 5);
+
+        let scene = build(&nodes).unwrap();
+        let mut out = [0.0];
+        for (p, expected) in [
+            ((0.0, 0.0, 0.0), 1.0),
+            ((6.0, 0.0, 0.0), 1.0),
+            ((8.0, 3.5, 0.0), 1.0),
+            ((8.0, -3.5, 0.0), 1.0),
+            ((4.0, 4.0, 0.0), 0.0),
+        ] {
+            scene.hit(Vec3f::from(p), &mut out);
+            let [v] = out;
+            assert!((v - expected).abs() < 1e-6, "at {p:?}");
+        }
+    }
+
+    #[test]
+    fn test_swc_errors() {
+        assert!(parse("1 1 0 0 0 1").is_err());
+        assert!(parse("1 1 0 0 x 1 -1").is_err());
+        assert!(build(&parse("1 1 0 0 0 1 2").unwrap()).is_err());
+        assert!(build(&parse("1 1 0 0 0 1 2\n2 1 1 0 0 1 1").unwrap()).is_err());
+    }
+}
@@ -5,17 +5,36 @@
 use std::cmp::PartialOrd;
 use std::ops::{Add, Div, Mul, Sub};
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg(feature = "python")]
+use pyo3::{prelude::*, types::PyTuple};
+use serde::{Deserialize, Serialize};
+
+/// A point or vector in 3D, stored as `[x, y, z]` in scene files.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "[T; 3]", into = "[T; 3]")]
+#[serde(bound(
+    serialize = "T: Serialize + Copy",
+    deserialize = "T: Deserialize<'de> + Copy"
+))]
 pub struct Vec3<T> {
+    /// X component.
     pub x: T,
+    /// Y component.
     pub y: T,
+    /// Z component.
     pub z: T,
 }
 
 impl<T> Vec3<T> {
+    /// Create a vector from its components.
     pub fn new(x: T, y: T, z: T) -> Vec3<T> {
         Vec3 { x, y, z }
     }
+
+    /// Apply `f` to each component.
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> Vec3<U> {
+        Vec3::new(f(self.x), f(self.y), f(self.z))
+    }
 }
 
 impl<T> From<(T, T, T)> for Vec3<T> {
@@ -50,6 +69,18 @@ impl<T> From<Vec3<T>> for (T, T, T) {
     }
 }
 
+/// Passed to Python as a tuple.
+#[cfg(feature = "python")]
+impl<'py, T: IntoPyObject<'py>> IntoPyObject<'py> for Vec3<T> {
+    type Target = PyTuple;
+    type Output = Bound<'py, PyTuple>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Self::Output> {
+        (self.x, self.y, self.z).into_pyobject(py)
+    }
+}
+
 impl<T: Copy> TryFrom<Vec<T>> for Vec3<T> {
     type Error = &'static str;
 
@@ -73,27 +104,37 @@ impl<T> From<Vec3<T>> for Vec<T> {
 }
 
 impl<T: Add<Output = T> + Mul<Output = T>> Vec3<T> {
+    /// Dot product with `b`.
+    #[must_use]
     pub fn dot(self, b: Vec3<T>) -> T {
         dot(self, b)
     }
 }
 
 impl<T: Add<Output = T> + Mul<Output = T> + Copy> Vec3<T> {
+    /// Dot product with itself, the squared norm.
+    #[must_use]
     pub fn dot2(self) -> T {
         dot(self, self)
     }
 }
 
 impl Vec3f {
-    pub fn norm(self) -> f32 {
+    /// Euclidean norm.
+    #[must_use]
+    pub fn norm(self) -> Float {
         norm(self)
     }
 
+    /// Unit vector of the same direction.
+    #[must_use]
     pub fn normalize(self) -> Vec3f {
         normalize(self)
     }
 
-    pub fn interpolate(self, v: Vec3f, k: f32) -> Vec3f {
+    /// Linear interpolation towards `v`, `self` at `k = 1`.
+    #[must_use]
+    pub fn interpolate(self, v: Vec3f, k: Float) -> Vec3f {
         interpolate(self, v, k)
     }
 }
@@ -170,10 +211,21 @@ impl<T: Div<Output = T>> Div<Vec3<T>> for Vec3<T> {
     }
 }
 
+/// Dot product of `a` and `b`.
 pub fn dot<T: Add<Output = T> + Mul<Output = T>>(a: Vec3<T>, b: Vec3<T>) -> T {
     a.x * b.x + a.y * b.y + a.z * b.z
 }
 
+/// Cross product of `a` and `b`.
+pub fn cross<T: Sub<Output = T> + Mul<Output = T> + Copy>(a: Vec3<T>, b: Vec3<T>) -> Vec3<T> {
+    Vec3::new(
+        a.y * b.z - a.z * b.y,
+        a.z * b.x - a.x * b.z,
+        a.x * b.y - a.y * b.x,
+    )
+}
+
+/// Componentwise maximum of `a` and `b`.
 pub fn maximum<T: PartialOrd>(a: Vec3<T>, b: Vec3<T>) -> Vec3<T> {
     let (xa, ya, za) = a.into();
     let (xb, yb, zb) = b.into();
@@ -183,6 +235,7 @@ pub fn maximum<T: PartialOrd>(a: Vec3<T>, b: Vec3<T>) -> Vec3<T> {
     Vec3::new(x, y, z)
 }
 
+/// Componentwise minimum of `a` and `b`.
 pub fn minimum<T: PartialOrd>(a: Vec3<T>, b: Vec3<T>) -> Vec3<T> {
     let (xa, ya, za) = a.into();
     let (xb, yb, zb) = b.into();
@@ -192,10 +245,14 @@ pub fn minimum<T: PartialOrd>(a: Vec3<T>, b: Vec3<T>) -> Vec3<T> {
     Vec3::new(x, y, z)
 }
 
-pub fn norm(v: Vec3f) -> f32 {
-    f32::sqrt(v.x * v.x + v.y * v.y + v.z * v.z)
+/// Euclidean norm of `v`.
+#[must_use]
+pub fn norm(v: Vec3f) -> Float {
+    Float::sqrt(v.x * v.x + v.y * v.y + v.z * v.z)
 }
 
+/// Unit vector in the direction of `v`, `v` itself when zero.
+#[must_use]
 pub fn normalize(v: Vec3f) -> Vec3f {
     let norm = v.norm();
     if norm == 0.0 {
@@ -205,7 +262,9 @@ pub fn normalize(v: Vec3f) -> Vec3f {
     }
 }
 
-pub fn interpolate(v1: Vec3f, v2: Vec3f, k: f32) -> Vec3f {
+/// Linear interpolation from `v2` at `k = 0` to `v1` at `k = 1`.
+#[must_use]
+pub fn interpolate(v1: Vec3f, v2: Vec3f, k: Float) -> Vec3f {
     let a = 1.0 - k;
     Vec3::new(
         v1.x * k + v2.x * a,
@@ -214,17 +273,58 @@ pub fn interpolate(v1: Vec3f, v2: Vec3f, k: f32) -> Vec3f {
     )
 }
 
-pub type Vec3f = Vec3<f32>;
+/// Rotate `v` around the unit vector `axis` by `angle` (Rodrigues' rotation formula).
+#[must_use]
+pub fn rotate(v: Vec3f, axis: Vec3f, angle: Float) -> Vec3f {
+    let (sin, cos) = angle.sin_cos();
+    v * cos + cross(axis, v) * sin + axis * (dot(axis, v) * (1.0 - cos))
+}
+
+/// Scalar of the geometry, double precision with the `f64` feature.
+///
+/// Channels stay single precision either way, and so do the samples returned
+/// to Python.
+#[cfg(not(feature = "f64"))]
+pub type Float = f32;
+
+/// Scalar of the geometry, double precision with the `f64` feature.
+///
+/// Channels stay single precision either way, and so do the samples returned
+/// to Python.
+#[cfg(feature = "f64")]
+pub type Float = f64;
+
+/// Mathematical constants of `Float`.
+#[cfg(not(feature = "f64"))]
+pub use std::f32::consts;
+
+/// Mathematical constants of `Float`.
+#[cfg(feature = "f64")]
+pub use std::f64::consts;
+
+/// Round `x` to the single precision of channels.
+#[must_use]
+#[allow(
+    trivial_numeric_casts,
+    clippy::cast_possible_truncation,
+    clippy::unnecessary_cast
+)]
+pub fn to_f32(x: Float) -> f32 {
+    x as f32
+}
+
+/// Vector of `Float`, used throughout the geometry.
+pub type Vec3f = Vec3<Float>;
 
 #[cfg(test)]
 mod tests {
-    use super::{dot, minimum, Vec3f};
+    use super::{dot, minimum, Float, Vec3f};
 
     #[test]
     fn dot_two() {
         let v1 = Vec3f::new(1., 2., 3.);
         let v2 = Vec3f::new(4., 5., 6.);
-        assert!((dot(v1, v2) - 32.).abs() < f32::EPSILON);
+        assert!((dot(v1, v2) - 32.).abs() < Float::EPSILON);
     }
 
     #[test]
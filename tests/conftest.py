@@ -0,0 +1,20 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import pytest
+
+from sdflit import ObjectsScene, SDFObject, Sphere
+
+
+@pytest.fixture
+def sphere_scene():
+    """Build a scene of a single sphere at the origin shaded by `material`."""
+
+    def make(material, radius=1, channels=3):
+        scene = ObjectsScene(channels)
+        sdf = Sphere((0, 0, 0), radius).into()
+        scene.add_object(SDFObject(sdf, material).into())
+        return scene
+
+    return make
@@ -0,0 +1,58 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import numpy.testing as npt
+import pytest
+
+from sdflit import ColoredMaterial, ObjectsScene, OpacityMaterial, SDFObject, Sphere
+
+
+def make_scene(blend, bvh):
+    scene = ObjectsScene()
+    for color, opacity in [((1, 0, 0), 0.5), ((0, 1, 0), 0.5), ((0, 0, 1), 1)]:
+        material = OpacityMaterial(ColoredMaterial(color).into(), opacity)
+        obj = SDFObject(Sphere((0, 0, 0), 1).into(), material.into())
+        scene.add_object(obj.into())
+
+    scene.set_background((0.1, 0.1, 0.1))
+    scene.set_blend(blend)
+    if bvh:
+        scene.build_bvh()
+    return scene.into()
+
+
+class TestBlend:
+    @pytest.mark.parametrize("bvh", [False, True])
+    @pytest.mark.parametrize(
+        "blend, expected",
+        [
+            ("max", (0.5, 0.5, 1)),
+            ("additive", (0.6, 0.6, 1.1)),
+            ("alpha_over", (0.5, 0.25, 0.25)),
+        ],
+    )
+    def test_overlap(self, blend, expected, bvh):
+        scene = make_scene(blend, bvh)
+        npt.assert_allclose(scene.hit((0, 0, 0)), expected, atol=1e-6)
+        npt.assert_allclose(scene.hit((2, 0, 0)), (0.1, 0.1, 0.1), atol=1e-6)
+
+    def test_alpha_over_background(self):
+        scene = ObjectsScene()
+        material = OpacityMaterial(ColoredMaterial((1, 0, 0)).into(), 0.25)
+        scene.add_object(SDFObject(Sphere((0, 0, 0), 1).into(), material.into()).into())
+        scene.set_background((0, 0, 1))
+        scene.set_blend("alpha_over")
+        npt.assert_allclose(scene.into().hit((0, 0, 0)), (0.25, 0, 0.75), atol=1e-6)
+
+    def test_intensity(self):
+        scene = ObjectsScene()
+        material = OpacityMaterial(ColoredMaterial((1, 1, 1)).into(), intensity=2)
+        scene.add_object(SDFObject(Sphere((0, 0, 0), 1).into(), material.into()).into())
+        npt.assert_allclose(scene.into().hit((0, 0, 0)), (2, 2, 2), atol=1e-6)
+
+    def test_invalid(self):
+        with pytest.raises(ValueError):
+            ObjectsScene().set_blend("multiply")
+        with pytest.raises(ValueError):
+            OpacityMaterial(ColoredMaterial((1, 1, 1)).into(), 1.5)
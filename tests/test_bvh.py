@@ -0,0 +1,88 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import math
+
+import numpy.testing as npt
+import pytest
+
+from sdflit import (
+    ColoredMaterial,
+    ObjectsScene,
+    RangeSampler,
+    RoundCone,
+    SDFObject,
+    Sphere,
+    repeat,
+)
+
+
+def make_scene():
+    # a thin neurite with a few long segments among many short ones
+    scene = ObjectsScene()
+    x = 0.0
+    for i in range(60):
+        length = 8.0 if i % 15 == 0 else 0.5
+        a, b = (x, 0, 0), (x + length, 0.1 * length, 0)
+        material = ColoredMaterial((i / 60, 1, 0)).into()
+        scene.add_object(SDFObject(RoundCone(a, b, 0.3, 0.3).into(), material).into())
+        x += length
+
+    return scene
+
+
+class TestBVH:
+    @pytest.mark.parametrize(
+        "method,leaf_size", [("median", 1), ("median", 4), ("sah", 1), ("sah", 4)]
+    )
+    def test_build(self, method, leaf_size):
+        expected = make_scene()
+        scene = make_scene()
+        assert scene.bvh_cost() is None
+        scene.build_bvh(method, leaf_size)
+        assert scene.bvh_cost() > 0
+
+        sampler = RangeSampler((-1, -1, -1), (60, 6, 1), (0.25, 0.25, 0.5))
+        npt.assert_array_equal(
+            sampler.sample(scene.into()), sampler.sample(expected.into())
+        )
+
+    def test_cost(self):
+        median, sah = make_scene(), make_scene()
+        median.build_bvh("median")
+        sah.build_bvh("sah")
+        assert sah.bvh_cost() < median.bvh_cost()
+
+    @pytest.mark.parametrize("method", ["median", "sah"])
+    def test_threads(self, method):
+        single, parallel = make_scene(), make_scene()
+        single.build_bvh(method, threads=1)
+        parallel.build_bvh(method, threads=4)
+        assert parallel.bvh_cost() == single.bvh_cost()
+
+    @pytest.mark.parametrize("leaf_size", [1, 4])
+    def test_unbounded(self, leaf_size):
+        def scene():
+            # a grid of spheres repeated forever along x and y, among the segments
+            scene = make_scene()
+            grid = repeat(Sphere((0, 0, 0), 0.2).into(), (3, 3, 0))
+            material = ColoredMaterial((0, 0, 1)).into()
+            scene.add_object(SDFObject(grid, material).into(), priority=-1)
+            return scene
+
+        expected, bvh = scene(), scene()
+        bvh.build_bvh("sah", leaf_size)
+        assert math.isfinite(bvh.bvh_cost())
+
+        sampler = RangeSampler((-1, -1, -1), (60, 6, 1), (0.25, 0.25, 0.5))
+        npt.assert_array_equal(
+            sampler.sample(bvh.into()), sampler.sample(expected.into())
+        )
+
+    def test_invalid(self):
+        with pytest.raises(ValueError):
+            make_scene().build_bvh("octree")
+
+        with pytest.raises(ValueError):
+            make_scene().build_bvh("sah", leaf_size=0)
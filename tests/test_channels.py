@@ -0,0 +1,64 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import numpy as np
+import numpy.testing as npt
+import pytest
+
+from sdflit import (
+    ChannelsMaterial,
+    ColoredMaterial,
+    FalloffMaterial,
+    ObjectsScene,
+    RangeSampler,
+    SDFObject,
+    Sphere,
+    UniformSampler,
+)
+
+
+class TestChannels:
+    values = np.array([1, 0, 0.5, 0.25, 2])
+
+    def test_hit(self, sphere_scene):
+        scene = sphere_scene(ChannelsMaterial(self.values).into(), channels=5)
+        scene.set_background((0, 0, 0, 0, -1))
+        s = scene.into()
+        assert s.channels() == 5
+        npt.assert_allclose(s.hit((0, 0, 0)), self.values)
+        npt.assert_allclose(s.hit((2, 0, 0)), (0, 0, 0, 0, -1))
+
+    def test_bvh(self, sphere_scene):
+        scene = sphere_scene(ChannelsMaterial(self.values).into(), channels=5)
+        scene.build_bvh()
+        npt.assert_allclose(scene.into().hit((0, 0, 0)), self.values)
+
+    def test_intensity(self, sphere_scene):
+        intensity = FalloffMaterial((1, 1, 1), 1).into()
+        material = ChannelsMaterial(self.values, intensity=intensity).into()
+        s = sphere_scene(material, channels=5).into()
+        npt.assert_allclose(s.hit((0.5, 0, 0)), self.values / 2, atol=1e-6)
+
+    def test_samplers(self, sphere_scene):
+        s = sphere_scene(ChannelsMaterial(self.values).into(), channels=5).into()
+        samples = UniformSampler((-1, -1, -1), (1, 1, 1)).sample(s, 100)
+        assert samples.shape == (100, 5)
+
+        sampler = RangeSampler((-1, -1, -1), (1, 1, 1), (0.5, 0.5, 0.5))
+        assert sampler.sample(s).shape == (4, 4, 4, 5)
+
+    def test_rgb_default(self, sphere_scene):
+        s = sphere_scene(ColoredMaterial((1, 1, 1)).into(), channels=3).into()
+        assert s.channels() == 3
+        assert s.hit((0, 0, 0)) == (1, 1, 1)
+
+    def test_mismatch(self):
+        scene = ObjectsScene(5)
+        obj = SDFObject(Sphere((0, 0, 0), 1).into(), ColoredMaterial((1, 1, 1)).into())
+        with pytest.raises(ValueError):
+            scene.add_object(obj.into())
+        with pytest.raises(ValueError):
+            scene.set_background((0, 0, 0))
+        with pytest.raises(ValueError):
+            ObjectsScene(0)
@@ -0,0 +1,60 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import numpy as np
+import numpy.testing as npt
+import pytest
+
+from sdflit import ColormapMaterial, ObjectsScene, SDFObject, Tube
+
+
+def make_scene(material):
+    # v runs from 0 to 1 along the tube
+    tube = Tube(np.array([[0, 0, 0], [10, 0, 0]]), np.array([1, 1])).into()
+    objs = ObjectsScene()
+    objs.add_object(SDFObject(tube, material).into())
+    return objs.into()
+
+
+class TestColormapMaterial:
+    def test_lut(self):
+        lut = np.array([[0, 0, 0], [1, 0, 0], [1, 1, 0]])
+        scene = make_scene(ColormapMaterial(lut, axis="v").into())
+        npt.assert_allclose(scene.hit((0, 0, 0)), (0, 0, 0), atol=1e-6)
+        npt.assert_allclose(scene.hit((2.5, 0, 0)), (0.5, 0, 0), atol=1e-6)
+        npt.assert_allclose(scene.hit((5, 0, 0)), (1, 0, 0), atol=1e-6)
+        npt.assert_allclose(scene.hit((7.5, 0, 0)), (1, 0.5, 0), atol=1e-6)
+
+    def test_stops(self):
+        lut = np.array([[0, 0, 0], [1, 1, 1]])
+        material = ColormapMaterial(lut, axis="v", stops=[0.5, 1]).into()
+        scene = make_scene(material)
+        npt.assert_allclose(scene.hit((2, 0, 0)), (0, 0, 0), atol=1e-6)
+        npt.assert_allclose(scene.hit((7.5, 0, 0)), (0.5, 0.5, 0.5), atol=1e-6)
+
+    def test_range(self):
+        lut = np.array([[0, 0, 0], [1, 1, 1]])
+        material = ColormapMaterial(lut, axis="v", vmin=0.5, vmax=1).into()
+        scene = make_scene(material)
+        npt.assert_allclose(scene.hit((2, 0, 0)), (0, 0, 0), atol=1e-6)
+        npt.assert_allclose(scene.hit((7.5, 0, 0)), (0.5, 0.5, 0.5), atol=1e-6)
+
+    @pytest.mark.parametrize("name", ["viridis", "magma", "turbo"])
+    def test_builtin(self, name):
+        scene = make_scene(ColormapMaterial(name, axis="v").into())
+        colors = np.array([scene.hit((x, 0, 0)) for x in np.linspace(0, 10, 11)])
+        assert np.all((0 <= colors) & (colors <= 1))
+        assert not np.allclose(colors[0], colors[-1])
+
+    def test_invalid(self):
+        with pytest.raises(ValueError):
+            ColormapMaterial("jet")
+        with pytest.raises(ValueError):
+            ColormapMaterial(np.zeros((3, 4)))
+        with pytest.raises(ValueError):
+            ColormapMaterial(np.zeros((3, 3)), stops=[0, 1])
+        with pytest.raises(ValueError):
+            ColormapMaterial(np.zeros((2, 3)), stops=[1, 0])
+        with pytest.raises(ValueError):
+            ColormapMaterial("viridis", axis="x")
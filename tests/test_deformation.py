@@ -0,0 +1,82 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import math
+
+import numpy as np
+import numpy.testing as npt
+import pytest
+
+from sdflit import RoundCone, Sphere, bend, twist, warp
+
+
+def lipschitz(sdf, n=1000, seed=0):
+    rng = np.random.default_rng(seed)
+    (min, max) = sdf.bounding_box()
+    p = rng.uniform(min, max, (n, 3))
+    q = p + rng.normal(0, 1e-2, (n, 3))
+    d = [abs(sdf.distance(tuple(a)) - sdf.distance(tuple(b))) for a, b in zip(p, q)]
+    return np.max(d / np.linalg.norm(p - q, axis=1))
+
+
+class TestTwist:
+    def test_distance(self):
+        capsule = RoundCone((1, 0, 0), (1, 0, 4), 0.3, 0.3).into()
+        sdf = twist(capsule, math.pi / 8)
+        assert sdf.inside((1, 0, 0))
+        assert sdf.inside((0, 1, 4))
+        assert not sdf.inside((1, 0, 4))
+
+    def test_lipschitz(self):
+        capsule = RoundCone((1, 0, 0), (1, 0, 4), 0.3, 0.3).into()
+        sdf = twist(capsule, math.pi / 8)
+        assert lipschitz(sdf) <= 1 + 1e-3
+
+    def test_bounding_box(self):
+        capsule = RoundCone((1, 0, 0), (1, 0, 4), 0.3, 0.3).into()
+        (min, max) = twist(capsule, math.pi / 8).bounding_box()
+        npt.assert_allclose(min[2], -0.3, rtol=1e-6)
+        npt.assert_allclose(max[2], 4.3, rtol=1e-6)
+        assert min[1] <= -1.3 and max[1] >= 1.3
+
+
+class TestBend:
+    def test_distance(self):
+        capsule = RoundCone((0, 0, 0), (4, 0, 0), 0.3, 0.3).into()
+        sdf = bend(capsule, 0.2)
+        assert sdf.inside((0, 0, 0))
+
+        # the center line is mapped to (x, -x * tan(k * x))
+        x = 2.0
+        assert sdf.inside((x, -x * math.tan(0.2 * x), 0))
+        assert not sdf.inside((x, 0, 0))
+
+    def test_lipschitz(self):
+        capsule = RoundCone((0, 0, 0), (4, 0, 0), 0.3, 0.3).into()
+        assert lipschitz(bend(capsule, 0.2)) <= 1 + 1e-3
+
+    @pytest.mark.parametrize("normal", [(0, 0, 0), (2, 0, 0)])
+    def test_invalid(self, normal):
+        capsule = RoundCone((0, 0, 0), (4, 0, 0), 0.3, 0.3).into()
+        with pytest.raises(ValueError):
+            bend(capsule, 0.2, (1, 0, 0), normal)
+
+
+class TestWarp:
+    def test_constant(self):
+        field = np.zeros((2, 2, 2, 3))
+        field[..., 0] = 1
+        sdf = warp(Sphere((0, 0, 0), 1).into(), field, (-1, -1, -1), (1, 1, 1))
+        npt.assert_allclose(sdf.distance((-1, 0, 0)), -1)
+        npt.assert_allclose(sdf.bounding_box(), ((-2, -2, -2), (2, 2, 2)))
+
+    def test_lipschitz(self):
+        rng = np.random.default_rng(0)
+        field = rng.normal(0, 0.1, (8, 8, 8, 3))
+        sdf = warp(Sphere((0, 0, 0), 1).into(), field, (-2, -2, -2), (2, 2, 2))
+        assert lipschitz(sdf) <= 1 + 1e-3
+
+    def test_invalid(self):
+        with pytest.raises(ValueError):
+            warp(Sphere((0, 0, 0), 1).into(), np.zeros((1, 2, 2, 3)), (-1, -1, -1), (1, 1, 1))
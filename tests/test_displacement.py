@@ -0,0 +1,48 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import numpy as np
+import numpy.testing as npt
+import pytest
+
+from sdflit import Sphere, displace
+
+
+class TestDisplace:
+    points = np.random.default_rng(0).uniform(-3, 3, (200, 3))
+
+    @pytest.mark.parametrize("kind", ["perlin", "simplex"])
+    def test_amplitude(self, kind):
+        sphere = Sphere((0, 0, 0), 2).into()
+        sdf = displace(sphere, 0.2, 2, octaves=4, kind=kind)
+        for p in map(tuple, self.points):
+            assert abs(sdf.distance(p) - sphere.distance(p)) <= 0.2 + 1e-6
+
+    def test_seed(self):
+        sphere = Sphere((0, 0, 0), 2).into()
+        a = displace(sphere, 0.2, 2, seed=1)
+        b = displace(sphere, 0.2, 2, seed=1)
+        c = displace(sphere, 0.2, 2, seed=2)
+        da = [a.distance(tuple(p)) for p in self.points]
+        npt.assert_array_equal(da, [b.distance(tuple(p)) for p in self.points])
+        assert not np.allclose(da, [c.distance(tuple(p)) for p in self.points])
+
+    def test_bounding_box(self):
+        sdf = displace(Sphere((0, 0, 0), 2).into(), 0.5)
+        npt.assert_allclose(sdf.bounding_box(), ((-2.5, -2.5, -2.5), (2.5, 2.5, 2.5)))
+
+    def test_invalid(self):
+        with pytest.raises(ValueError):
+            displace(Sphere((0, 0, 0), 2).into(), 0.5, kind="worley")
+
+    @pytest.mark.parametrize("octaves", [0, 17, 40])
+    def test_invalid_octaves(self, octaves):
+        with pytest.raises(ValueError):
+            displace(Sphere((0, 0, 0), 2).into(), 0.5, octaves=octaves)
+
+    def test_far(self):
+        sphere = Sphere((0, 0, 0), 2).into()
+        sdf = displace(sphere, 0.2, 2, octaves=16)
+        for p in [(3e9, 0.5, 0.5), (-1e15, 0, 0), (2, 0.5, 0.5)]:
+            assert abs(sdf.distance(p) - sphere.distance(p)) <= 0.2 * (1 + 1e-6)
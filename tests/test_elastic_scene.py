@@ -0,0 +1,83 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import math
+
+import numpy as np
+import numpy.testing as npt
+import pytest
+
+from sdflit import (
+    ColoredMaterial,
+    ElasticScene,
+    ObjectsScene,
+    RangeSampler,
+    SDFObject,
+    Sphere,
+)
+
+
+def sphere_scene():
+    sdf = Sphere((0, 0, 0), 1).into()
+    material = ColoredMaterial((1, 1, 1)).into()
+    scene = ObjectsScene()
+    scene.add_object(SDFObject(sdf, material).into())
+    return scene.into()
+
+
+class TestElasticScene:
+    sampler = RangeSampler((-1.5, -1.5, -1.5), (1.5, 1.5, 1.5), (0.1, 0.1, 0.1))
+
+    def test_identity(self):
+        scene = sphere_scene()
+        elastic = ElasticScene(scene, (0.5, 0.5, 0.5), 0)
+        npt.assert_array_equal(
+            self.sampler.sample(elastic.into()), self.sampler.sample(scene)
+        )
+
+    def test_deform(self):
+        scene = sphere_scene()
+        a = self.sampler.sample(ElasticScene(scene, (0.5, 0.5, 0.5), 0.1, seed=1).into())
+        b = self.sampler.sample(ElasticScene(scene, (0.5, 0.5, 0.5), 0.1, seed=1).into())
+        c = self.sampler.sample(ElasticScene(scene, (0.5, 0.5, 0.5), 0.1, seed=2).into())
+        npt.assert_array_equal(a, b)
+        assert not np.array_equal(a, c)
+
+        # the volume is roughly kept by a smooth deformation
+        ref = self.sampler.sample(scene)
+        npt.assert_allclose(a[..., 0].sum(), ref[..., 0].sum(), rtol=0.2)
+
+    def test_bounding_box(self):
+        (min, max) = ElasticScene(sphere_scene(), (0.5, 0.5, 0.5), 0.1).bounding_box()
+        assert all(a <= -1 for a in min) and all(a >= 1 for a in max)
+
+    def test_far(self):
+        # points far from the control grid are not displaced
+        elastic = ElasticScene(sphere_scene(), (0.5, 0.5, 0.5), 0.1).into()
+        for p in [(math.inf, 0, 0), (-math.inf, 0, 0), (0, 3e18, 0), (0, 0, 1e30)]:
+            assert elastic.hit(p) == (0, 0, 0)
+
+        assert elastic.hit((math.nan, 0, 0)) == (0, 0, 0)
+
+    def test_invalid(self):
+        with pytest.raises(ValueError):
+            ElasticScene(sphere_scene(), (0, 0.5, 0.5), 0.1)
+
+        with pytest.raises(ValueError):
+            ElasticScene(ObjectsScene().into(), (0.5, 0.5, 0.5), 0.1)
+
+    @pytest.mark.parametrize(
+        "spacing, sigma",
+        [
+            ((math.nan, 0.5, 0.5), 0.1),
+            ((0.5, 0.5, 0.5), math.nan),
+            ((0.5, 0.5, 0.5), -0.1),
+            # too many control points for the size of the scene
+            ((1e-3, 1e-3, 1e-3), 0.1),
+            ((1e-30, 1e-30, 1e-30), 0.1),
+        ],
+    )
+    def test_invalid_field(self, spacing, sigma):
+        with pytest.raises(ValueError):
+            ElasticScene(sphere_scene(), spacing, sigma)
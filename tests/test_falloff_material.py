@@ -0,0 +1,49 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import numpy as np
+import numpy.testing as npt
+import pytest
+
+from sdflit import FalloffMaterial
+
+
+class TestFalloffMaterial:
+    def test_surface(self, sphere_scene):
+        material = FalloffMaterial((1, 1, 1), 1)
+        scene = sphere_scene(material.into(), radius=2).into()
+        npt.assert_allclose(scene.hit((2, 0, 0)), (1, 1, 1), atol=1e-6)
+        npt.assert_allclose(scene.hit((1.5, 0, 0)), (0.5, 0.5, 0.5), atol=1e-6)
+        npt.assert_allclose(scene.hit((0.5, 0, 0)), (0, 0, 0), atol=1e-6)
+
+    def test_centerline(self, sphere_scene):
+        material = FalloffMaterial((1, 1, 1), 1, origin="centerline", radius=2)
+        scene = sphere_scene(material.into(), radius=2).into()
+        npt.assert_allclose(scene.hit((0, 0, 0)), (1, 1, 1), atol=1e-6)
+        npt.assert_allclose(scene.hit((0.5, 0, 0)), (0.5, 0.5, 0.5), atol=1e-6)
+        npt.assert_allclose(scene.hit((1.5, 0, 0)), (0, 0, 0), atol=1e-6)
+
+    @pytest.mark.parametrize(
+        "profile, expected",
+        [("gaussian", np.exp(-0.5)), ("exponential", np.exp(-1))],
+    )
+    def test_profile(self, sphere_scene, profile, expected):
+        material = FalloffMaterial((1, 0, 0), 0.5, profile=profile)
+        scene = sphere_scene(material.into(), radius=2).into()
+        npt.assert_allclose(scene.hit((1.5, 0, 0)), (expected, 0, 0), atol=1e-6)
+
+    def test_background(self, sphere_scene):
+        material = FalloffMaterial((1, 0, 0), 1, background=(0, 0, 1))
+        scene = sphere_scene(material.into(), radius=2).into()
+        npt.assert_allclose(scene.hit((0, 0, 0)), (0, 0, 1), atol=1e-6)
+
+    def test_invalid(self):
+        with pytest.raises(ValueError):
+            FalloffMaterial((1, 1, 1), 0)
+        with pytest.raises(ValueError):
+            FalloffMaterial((1, 1, 1), 1, profile="cosine")
+        with pytest.raises(ValueError):
+            FalloffMaterial((1, 1, 1), 1, origin="centerline")
+        with pytest.raises(ValueError):
+            FalloffMaterial((1, 1, 1), 1, radius=1)
@@ -0,0 +1,88 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import numpy as np
+import numpy.testing as npt
+import pytest
+
+from sdflit import FunctionMaterial, RangeSampler, UniformSampler
+
+
+class TestFunctionMaterial:
+    def test_scalar(self, sphere_scene):
+        def depth(distance, u, v, w, p):
+            return (-distance, p[0], 1)
+
+        s = sphere_scene(FunctionMaterial(depth).into()).into()
+        npt.assert_allclose(s.hit((0.5, 0, 0)), (0.5, 0.5, 1), atol=1e-6)
+        npt.assert_allclose(s.hit((2, 0, 0)), (0, 0, 0))
+
+    def test_vectorized(self, sphere_scene):
+        calls = []
+
+        def depth(distance, u, v, w, p):
+            calls.append(len(distance))
+            assert p.shape == (len(distance), 3)
+            return np.stack([-distance, p[:, 0]], axis=1)
+
+        material = FunctionMaterial(depth, channels=2, vectorized=True).into()
+        s = sphere_scene(material, channels=2).into()
+        npt.assert_allclose(s.hit((0.5, 0, 0)), (0.5, 0.5), atol=1e-6)
+
+        calls.clear()
+        sampler = RangeSampler((-1, -1, -1), (1, 1, 1), (0.1, 0.1, 0.1))
+        samples = sampler.sample(s)
+        assert samples.shape == (20, 20, 20, 2)
+        assert len(calls) < 10  # shaded in batches, not per point
+
+        inside = samples[..., 0] > 0
+        npt.assert_allclose(samples[inside].max(axis=0)[0], 1, atol=0.1)
+
+    def test_samplers_match(self, sphere_scene):
+        def f(distance, u, v, w, p):
+            return np.stack([-distance, p[:, 1], p[:, 2]], axis=1)
+
+        def g(distance, u, v, w, p):
+            return (-distance, p[1], p[2])
+
+        a = sphere_scene(FunctionMaterial(f, vectorized=True).into()).into()
+        b = sphere_scene(FunctionMaterial(g).into()).into()
+        sampler = RangeSampler((-1, -1, -1), (1, 1, 1), (0.25, 0.25, 0.25))
+        npt.assert_allclose(sampler.sample(a), sampler.sample(b), atol=1e-6)
+
+    def test_invalid(self, sphere_scene):
+        with pytest.raises(ValueError):
+            FunctionMaterial(lambda *args: (0, 0, 0), channels=0)
+
+        s = sphere_scene(FunctionMaterial(lambda *args: (0, 0)).into()).into()
+        with pytest.raises(ValueError):
+            UniformSampler((-1, -1, -1), (1, 1, 1)).sample(s, 10)
+
+    @pytest.mark.parametrize("vectorized", [False, True])
+    def test_raise(self, sphere_scene, vectorized):
+        class Failed(Exception):
+            pass
+
+        calls = []
+
+        def fail(*args):
+            calls.append(args)
+            raise Failed("no color")
+
+        material = FunctionMaterial(fail, vectorized=vectorized)
+        s = sphere_scene(material.into()).into()
+        with pytest.raises(Failed, match="no color"):
+            s.hit((0.5, 0, 0))
+
+        calls.clear()
+        sampler = RangeSampler((-1, -1, -1), (1, 1, 1), (0.1, 0.1, 0.1))
+        with pytest.raises(Failed):
+            sampler.sample(s)
+        assert len(calls) == 1  # the rest of the query is skipped
+
+        with pytest.raises(Failed):
+            UniformSampler((-1, -1, -1), (1, 1, 1)).sample(s, 100)
+
+        # the error does not leak into later queries
+        npt.assert_allclose(s.hit((2, 0, 0)), (0, 0, 0))
@@ -0,0 +1,94 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import numpy as np
+import numpy.testing as npt
+import pytest
+
+from sdflit import (
+    ColoredMaterial,
+    FunctionSDF,
+    ObjectsScene,
+    RangeSampler,
+    SDFObject,
+    Sphere,
+    merge,
+)
+
+BOX = ((-1, -1, -1), (1, 1, 1))
+
+
+def sphere(p):
+    return np.linalg.norm(p) - 1
+
+
+def spheres(p):
+    return np.linalg.norm(p, axis=1) - 1
+
+
+class TestFunctionSDF:
+    points = np.random.default_rng(0).uniform(-2, 2, (100, 3))
+
+    @pytest.mark.parametrize("func, vectorized", [(sphere, False), (spheres, True)])
+    def test_distance(self, func, vectorized):
+        sdf = FunctionSDF(func, BOX, vectorized=vectorized)
+        expected = Sphere((0, 0, 0), 1)
+        for p in map(tuple, self.points):
+            npt.assert_allclose(sdf.distance(p), expected.distance(p), atol=1e-6)
+            assert sdf.inside(p) == expected.inside(p)
+        assert sdf.bounding_box() == BOX
+
+    def test_composition(self):
+        sdf = merge(FunctionSDF(sphere, BOX).into(), Sphere((2, 0, 0), 1).into())
+        assert sdf.inside((0, 0, 0))
+        assert sdf.inside((2, 0, 0))
+        assert not sdf.inside((1, 1, 0))
+
+    def test_scene(self):
+        calls = []
+
+        def f(p):
+            calls.append(len(p))
+            return spheres(p)
+
+        def make_scene(sdf):
+            scene = ObjectsScene()
+            material = ColoredMaterial((1, 1, 1)).into()
+            scene.add_object(SDFObject(sdf, material).into())
+            return scene.into()
+
+        sampler = RangeSampler((-1, -1, -1), (1, 1, 1), (0.1, 0.1, 0.1))
+        samples = sampler.sample(make_scene(FunctionSDF(f, BOX, True).into()))
+        expected = sampler.sample(make_scene(Sphere((0, 0, 0), 1).into()))
+        assert samples.shape == expected.shape
+        assert np.mean(samples != expected) < 1e-2  # may differ on the surface
+        assert len(calls) < 10  # evaluated in batches, not per point
+
+    def test_invalid(self):
+        with pytest.raises(ValueError):
+            FunctionSDF(sphere, ((1, 1, 1), (-1, -1, -1)))
+
+    @pytest.mark.parametrize("vectorized", [False, True])
+    def test_raise(self, vectorized):
+        calls = []
+
+        def fail(p):
+            calls.append(p)
+            raise ValueError("no distance")
+
+        sdf = FunctionSDF(fail, BOX, vectorized=vectorized)
+        with pytest.raises(ValueError, match="no distance"):
+            sdf.distance((0, 0, 0))
+
+        with pytest.raises(ValueError):
+            merge(sdf.into(), Sphere((2, 0, 0), 1).into()).inside((0, 0, 0))
+
+        scene = ObjectsScene()
+        material = ColoredMaterial((1, 1, 1)).into()
+        scene.add_object(SDFObject(sdf.into(), material).into())
+        calls.clear()
+        sampler = RangeSampler((-1, -1, -1), (1, 1, 1), (0.1, 0.1, 0.1))
+        with pytest.raises(ValueError, match="no distance"):
+            sampler.sample(scene.into())
+        assert len(calls) == 1  # the rest of the query is skipped
@@ -0,0 +1,77 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import numpy.testing as npt
+import pytest
+
+import sdflit
+from sdflit import Sphere, elongate, shell
+
+
+class TestRound:
+    @pytest.mark.parametrize(
+        "p, expected",
+        [
+            # fmt: off
+            ((0, 0, 0), -1.5),
+            ((1.5, 0, 0), 0),
+            ((3, 0, 0), 1.5),
+            # fmt: on
+        ],
+    )
+    def test_distance(self, p, expected):
+        sdf = sdflit.round(Sphere((0, 0, 0), 1).into(), 0.5)
+        npt.assert_allclose(sdf.distance(p), expected)
+
+    def test_bounding_box(self):
+        sdf = sdflit.round(Sphere((0, 0, 0), 1).into(), 0.5)
+        npt.assert_allclose(sdf.bounding_box(), ((-1.5, -1.5, -1.5), (1.5, 1.5, 1.5)))
+
+
+class TestShell:
+    @pytest.mark.parametrize(
+        "p, expected",
+        [
+            # fmt: off
+            ((0, 0, 0), 1.9),
+            ((1, 0, 0), 0.9),
+            ((2, 0, 0), -0.1),
+            ((3, 0, 0), 0.9),
+            # fmt: on
+        ],
+    )
+    def test_distance(self, p, expected):
+        sdf = shell(Sphere((0, 0, 0), 2).into(), 0.2)
+        npt.assert_allclose(sdf.distance(p), expected, rtol=1e-6)
+
+    def test_inside(self):
+        sdf = shell(Sphere((0, 0, 0), 2).into(), 0.2)
+        assert not sdf.inside((0, 0, 0))
+        assert sdf.inside((2.05, 0, 0))
+        npt.assert_allclose(sdf.bounding_box(), ((-2.1, -2.1, -2.1), (2.1, 2.1, 2.1)))
+
+    def test_invalid(self):
+        with pytest.raises(ValueError):
+            shell(Sphere((0, 0, 0), 2).into(), 0)
+
+
+class TestElongate:
+    @pytest.mark.parametrize(
+        "p, expected",
+        [
+            # fmt: off
+            ((0, 0, 0), -1),
+            ((2, 0, 0), -1),
+            ((4, 0, 0), 1),
+            ((0, 2, 0), 1),
+            # fmt: on
+        ],
+    )
+    def test_distance(self, p, expected):
+        sdf = elongate(Sphere((0, 0, 0), 1).into(), (2, 0, 0))
+        npt.assert_allclose(sdf.distance(p), expected)
+
+    def test_bounding_box(self):
+        sdf = elongate(Sphere((0, 0, 0), 1).into(), (2, 0, 0))
+        npt.assert_allclose(sdf.bounding_box(), ((-3, -1, -1), (3, 1, 1)))
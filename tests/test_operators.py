@@ -0,0 +1,73 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import numpy as np
+import pytest
+
+from sdflit import (
+    ColoredMaterial,
+    SDFObject,
+    Sphere,
+    Tube,
+    intersect,
+    merge,
+    round,
+    subtract,
+)
+
+
+class TestOperators:
+    a = Sphere((0, 0, 0), 1)
+    b = Sphere((1, 0, 0), 1)
+    points = list(map(tuple, np.random.default_rng(0).uniform(-2, 3, (100, 3))))
+
+    @pytest.mark.parametrize(
+        "op, func",
+        [
+            (lambda a, b: a | b, merge),
+            (lambda a, b: a & b, intersect),
+            (lambda a, b: a - b, subtract),
+        ],
+    )
+    def test_primitives(self, op, func):
+        sdf = op(self.a, self.b)
+        expected = func(self.a.into(), self.b.into())
+        for p in self.points:
+            assert sdf.distance(p) == expected.distance(p)
+
+    def test_chained(self):
+        c = Tube((0, 0, 0), (0, 0, 2), 0.5)
+        sdf = (self.a | self.b) - c
+        expected = subtract(merge(self.a.into(), self.b.into()), c.into())
+        for p in self.points:
+            assert sdf.distance(p) == expected.distance(p)
+
+    def test_mixed(self):
+        sdf = self.a.into() | self.b
+        expected = merge(self.a.into(), self.b.into())
+        for p in self.points:
+            assert sdf.distance(p) == expected.distance(p)
+
+
+class TestPrimitivesAsSDF:
+    a = Sphere((0, 0, 0), 1)
+    b = Sphere((1, 0, 0), 1)
+
+    def test_composition(self):
+        p = (0.5, 0, 0)
+        assert merge(self.a, self.b).distance(p) == merge(
+            self.a.into(), self.b.into()
+        ).distance(p)
+
+    def test_modification(self):
+        p = (1.5, 0, 0)
+        assert round(self.a, 0.5).distance(p) == round(self.a.into(), 0.5).distance(p)
+
+    def test_object(self):
+        obj = SDFObject(self.a, ColoredMaterial((1, 0, 0)).into())
+        assert obj.into() is not None
+
+    def test_invalid(self):
+        with pytest.raises(TypeError):
+            merge(self.a, 1)
@@ -0,0 +1,169 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import copy
+import pickle
+
+import numpy as np
+import numpy.testing as npt
+import pytest
+
+from sdflit import (
+    ChannelsMaterial,
+    CheckerMaterial,
+    ColoredMaterial,
+    ColormapMaterial,
+    ElasticScene,
+    FalloffMaterial,
+    FunctionSDF,
+    LinearGradientMaterial,
+    Metaballs,
+    NoiseMaterial,
+    ObjectsScene,
+    OpacityMaterial,
+    RangeSampler,
+    RoundCone,
+    SDFObject,
+    Sphere,
+    Spheres,
+    SplineTube,
+    StripesMaterial,
+    Tube,
+    bend,
+    displace,
+    elongate,
+    mirror,
+    repeat,
+    repeat_radial,
+    round,
+    shell,
+    twist,
+    warp,
+)
+
+POINTS = list(map(tuple, np.random.default_rng(0).uniform(-2, 2, (200, 3))))
+NODES = np.array([[0, 0, 0], [1, 0, 0], [1, 1, 0], [2, 1, 1]])
+RADII = np.array([0.3, 0.2, 0.4, 0.1])
+
+
+def roundtrip(x):
+    return pickle.loads(pickle.dumps(x))
+
+
+def sphere(p):
+    return np.linalg.norm(p) - 1
+
+
+SDFS = [
+    Sphere((0, 0, 0), 1),
+    RoundCone((0, 0, 0), (1, 1, 0), 0.5, 0.2),
+    Spheres(NODES, RADII),
+    Metaballs(NODES, RADII, 0.2),
+    Tube(NODES, RADII),
+    SplineTube(NODES, RADII),
+    SplineTube(NODES, RADII, kind="bezier"),
+    FunctionSDF(sphere, ((-1, -1, -1), (1, 1, 1))),
+    Sphere((0, 0, 0), 1) | Sphere((1, 0, 0), 0.5),
+    Sphere((0, 0, 0), 1) & Sphere((1, 0, 0), 0.5),
+    Sphere((0, 0, 0), 1) - Sphere((1, 0, 0), 0.5),
+    round(Tube(NODES, RADII), 0.1),
+    shell(Sphere((0, 0, 0), 1), 0.2),
+    elongate(Sphere((0, 0, 0), 1), (1, 0, 0)),
+    repeat(Sphere((0, 0, 0), 0.5), (2, 2, 0), (2, 3, 1)),
+    repeat_radial(RoundCone((1, 0, 0), (1, 0, 1), 0.2, 0.1), 6),
+    mirror(Tube(NODES, RADII), (1, 0, 0), (0.5, 0, 0)),
+    twist(Tube(NODES, RADII), 0.5),
+    bend(Tube(NODES, RADII), 0.3),
+    warp(Sphere((0, 0, 0), 1), np.full((2, 2, 2, 3), 0.1), (-2, -2, -2), (2, 2, 2)),
+    displace(Sphere((0, 0, 0), 1), 0.1, 2.0, 3, seed=7, kind="simplex"),
+]
+
+MATERIALS = [
+    ColoredMaterial((1, 0, 0)),
+    LinearGradientMaterial((1, 0, 0), (0, 0, 1), "v"),
+    NoiseMaterial((1, 0, 0), (0, 0, 1), 2.0, 3, 5, "simplex", 0.1),
+    CheckerMaterial((1, 0, 0), (0, 1, 0), (0.5, 0.5, 0.5)),
+    StripesMaterial((1, 0, 0), (0, 1, 0), 0.3, "y"),
+    ColormapMaterial("viridis", axis="distance", vmin=-1, vmax=0),
+    ColormapMaterial([[0, 0, 0], [1, 1, 1]], stops=[0.2, 0.8]),
+    FalloffMaterial((1, 1, 1), 0.5, "gaussian", "centerline", 1.0),
+    OpacityMaterial(ColoredMaterial((1, 0, 0)).into(), 0.5, 2.0),
+]
+
+
+def colored(sdf, color):
+    return SDFObject(sdf, ColoredMaterial(color).into()).into()
+
+
+def scene(sdf, material, **kwargs):
+    scene = ObjectsScene(**kwargs)
+    scene.add_object(SDFObject(sdf, material.into()).into())
+    return scene
+
+
+class TestPickle:
+    @pytest.mark.parametrize("sdf", SDFS)
+    def test_sdf(self, sdf):
+        for x in (sdf, sdf.into()):
+            y = roundtrip(x)
+            assert y.bounding_box() == x.bounding_box()
+            for p in POINTS:
+                assert y.distance(p) == x.distance(p)
+
+    @pytest.mark.parametrize("material", MATERIALS)
+    def test_material(self, material):
+        for m in (material, material.into()):
+            a = scene(Tube(NODES, RADII), m).into()
+            b = roundtrip(a)
+            for p in POINTS:
+                assert b.hit(p) == a.hit(p)
+
+    def test_channels(self):
+        material = ChannelsMaterial([1, 2, 3, 4], ColoredMaterial((1, 1, 1)).into())
+        a = scene(Sphere((0, 0, 0), 1), material, channels=4).into()
+        assert roundtrip(a).hit((0, 0, 0)) == (1, 2, 3, 4)
+
+    @pytest.mark.parametrize("bvh", [False, True])
+    def test_objects_scene(self, bvh):
+        a = ObjectsScene(channels=3)
+        a.add_object(colored(Sphere((0, 0, 0), 1), (1, 0, 0)))
+        a.add_object(colored(Sphere((1, 0, 0), 1), (0, 1, 0)), 1)
+        a.set_background([0.1, 0.2, 0.3])
+        a.set_blend("additive")
+        if bvh:
+            a.build_bvh()
+
+        b = roundtrip(a)
+        assert b.__getstate__()[1:] == a.__getstate__()[1:]
+
+        sampler = RangeSampler((-2, -2, -2), (2, 2, 2), (0.25, 0.25, 0.25))
+        npt.assert_array_equal(sampler.sample(b.into()), sampler.sample(a.into()))
+
+        if bvh:
+            with pytest.raises(ValueError):
+                b.add_object(colored(Sphere((0, 0, 0), 1), (1, 0, 0)))
+
+    def test_elastic_scene(self):
+        inner = scene(Tube(NODES, RADII), ColoredMaterial((1, 1, 1))).into()
+        a = ElasticScene(inner, (0.5, 0.5, 0.5), 0.2, seed=3)
+        b = roundtrip(a)
+        for p in POINTS:
+            assert b.into().hit(p) == a.into().hit(p)
+
+    def test_sampler(self):
+        a = RangeSampler((-1, -1, -1), (1, 1, 1), (0.5, 0.5, 0.5))
+        s = scene(Sphere((0, 0, 0), 1), ColoredMaterial((1, 1, 1))).into()
+        npt.assert_array_equal(roundtrip(a).sample(s), a.sample(s))
+
+    def test_copy(self):
+        a = scene(Tube(NODES, RADII), ColoredMaterial((1, 1, 1)))
+        b = copy.deepcopy(a)
+        b.set_background([1, 1, 1])
+        assert a.into().hit((5, 5, 5)) == (0, 0, 0)
+        assert b.into().hit((5, 5, 5)) == (1, 1, 1)
+
+    def test_lambda(self):
+        sdf = FunctionSDF(lambda p: np.linalg.norm(p) - 1, ((-1, -1, -1), (1, 1, 1)))
+        with pytest.raises((pickle.PicklingError, AttributeError)):
+            pickle.dumps(sdf)
@@ -0,0 +1,63 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import numpy as np
+import numpy.testing as npt
+import pytest
+
+from sdflit import ColoredMaterial, ObjectsScene, SDFObject, Sphere, UniformSampler
+
+
+def make_object(center, color):
+    material = ColoredMaterial(color).into()
+    return SDFObject(Sphere(center, 1).into(), material).into()
+
+
+class TestPriority:
+    @pytest.mark.parametrize("bvh", [False, True])
+    def test_priority(self, bvh):
+        scene = ObjectsScene()
+        scene.add_object(make_object((0, 0, 0), (1, 0, 0)))
+        scene.add_object(make_object((0.5, 0, 0), (0, 1, 0)), priority=1)
+        scene.add_object(make_object((1, 0, 0), (0, 0, 1)), priority=1)
+        if bvh:
+            scene.build_bvh()
+
+        s = scene.into()
+        assert s.hit((0.25, 0, 0)) == (0, 1, 0)
+        assert s.hit((0.75, 0, 0)) == (0, 1, 0)  # same priority, added first
+        assert s.hit((1.75, 0, 0)) == (0, 0, 1)
+        assert s.hit((-0.75, 0, 0)) == (1, 0, 0)
+
+    def test_identical_with_bvh(self):
+        rng = np.random.default_rng(0)
+        linear, bvh = ObjectsScene(), ObjectsScene()
+        for i, center in enumerate(rng.uniform(0, 5, (100, 3))):
+            priority = int(rng.integers(-3, 3))
+            obj = make_object(tuple(center), (i, 0, 0))
+            linear.add_object(obj, priority)
+            bvh.add_object(obj, priority)
+        bvh.build_bvh()
+
+        points = rng.uniform(0, 5, (1000, 3))
+        npt.assert_array_equal(
+            [linear.into().hit(tuple(p)) for p in points],
+            [bvh.into().hit(tuple(p)) for p in points],
+        )
+
+    def test_alpha_over_order(self):
+        scene = ObjectsScene()
+        scene.add_object(make_object((0, 0, 0), (1, 0, 0)))
+        scene.add_object(make_object((0, 0, 0), (0, 1, 0)), priority=1)
+        scene.set_blend("alpha_over")
+        assert scene.into().hit((0, 0, 0)) == (0, 1, 0)
+
+    def test_sampler(self):
+        scene = ObjectsScene()
+        scene.add_object(make_object((0, 0, 0), (1, 0, 0)))
+        scene.add_object(make_object((0, 0, 0), (0, 1, 0)), priority=-1)
+        samples = UniformSampler((-0.5, -0.5, -0.5), (0.5, 0.5, 0.5)).sample(
+            scene.into(), 100
+        )
+        npt.assert_array_equal(samples[:, 1], 0)
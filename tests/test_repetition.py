@@ -0,0 +1,86 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import math
+
+import numpy.testing as npt
+import pytest
+
+from sdflit import Sphere, mirror, repeat, repeat_radial
+
+
+class TestRepeat:
+    @pytest.mark.parametrize(
+        "p, expected",
+        [
+            # fmt: off
+            ((0, 0, 0), -0.5),
+            ((4, 0, 0), -0.5),
+            ((-40, 22, 0), -0.5),
+            ((1, 0, 0), 0.5),
+            ((0, 0, 2), 1.5),
+            # fmt: on
+        ],
+    )
+    def test_infinite(self, p, expected):
+        sdf = repeat(Sphere((0, 0, 0), 0.5).into(), (2, 2, 0))
+        npt.assert_allclose(sdf.distance(p), expected, atol=1e-5)
+
+    @pytest.mark.parametrize(
+        "p, expected",
+        [
+            # fmt: off
+            ((0, 0, 0), -0.5),
+            ((4, 0, 0), -0.5),
+            ((6, 0, 0), 1.5),
+            ((-1, 0, 0), 0.5),
+            # fmt: on
+        ],
+    )
+    def test_finite(self, p, expected):
+        sdf = repeat(Sphere((0, 0, 0), 0.5).into(), (2, 0, 0), (3, 1, 1))
+        npt.assert_allclose(sdf.distance(p), expected, atol=1e-5)
+
+    def test_bounding_box(self):
+        sphere = Sphere((0, 0, 0), 0.5).into()
+        finite = repeat(sphere, (2, 0, 0), (3, 1, 1))
+        npt.assert_allclose(finite.bounding_box(), ((-0.5, -0.5, -0.5), (4.5, 0.5, 0.5)))
+
+        (min, max) = repeat(sphere, (2, 0, 0)).bounding_box()
+        assert min[0] == -math.inf and max[0] == math.inf
+        npt.assert_allclose((min[1:], max[1:]), ((-0.5, -0.5), (0.5, 0.5)))
+
+    def test_invalid(self):
+        with pytest.raises(ValueError):
+            repeat(Sphere((0, 0, 0), 0.5).into(), (2, 0, 0), (0, 1, 1))
+
+
+class TestRepeatRadial:
+    @pytest.mark.parametrize(
+        "p, expected",
+        [
+            # fmt: off
+            ((3, 0, 0), -1),
+            ((0, 3, 0), -1),
+            ((-3, 0, 0), -1),
+            ((0, -3, 0), -1),
+            ((0, 0, 0), 2),
+            # fmt: on
+        ],
+    )
+    def test_distance(self, p, expected):
+        sdf = repeat_radial(Sphere((3, 0, 0), 1).into(), 4)
+        npt.assert_allclose(sdf.distance(p), expected, atol=1e-5)
+
+    def test_bounding_box(self):
+        sdf = repeat_radial(Sphere((3, 0, 0), 1).into(), 4)
+        npt.assert_allclose(sdf.bounding_box(), ((-4, -4, -1), (4, 4, 1)), atol=1e-5)
+
+
+class TestMirror:
+    def test_distance(self):
+        sdf = mirror(Sphere((2, 0, 0), 1).into(), (1, 0, 0))
+        npt.assert_allclose(sdf.distance((2, 0, 0)), -1)
+        npt.assert_allclose(sdf.distance((-2, 0, 0)), -1)
+        npt.assert_allclose(sdf.bounding_box(), ((-3, -1, -1), (3, 1, 1)))
@@ -0,0 +1,139 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import json
+
+import numpy as np
+import numpy.testing as npt
+import pytest
+
+from sdflit import (
+    ChannelsMaterial,
+    ColoredMaterial,
+    ColormapMaterial,
+    ElasticScene,
+    FalloffMaterial,
+    FunctionSDF,
+    NoiseMaterial,
+    ObjectsScene,
+    OpacityMaterial,
+    RangeSampler,
+    SDFObject,
+    Sphere,
+    SplineTube,
+    Tube,
+    displace,
+    load_scene,
+    repeat,
+    twist,
+    warp,
+)
+
+NODES = np.array([[0, 0, 0], [1, 0, 0], [1, 1, 0], [2, 1, 1]])
+RADII = np.array([0.3, 0.2, 0.4, 0.1])
+SAMPLER = RangeSampler((-2, -2, -2), (3, 3, 3), (0.25, 0.25, 0.25))
+
+
+def obj(sdf, material):
+    return SDFObject(sdf, material.into()).into()
+
+
+def objects_scene():
+    scene = ObjectsScene(channels=3)
+    sdf = Sphere((0, 0, 0), 1) - Tube(NODES, RADII)
+    scene.add_object(obj(sdf, ColoredMaterial((1, 0, 0))))
+    sdf = twist(SplineTube(NODES, RADII), 0.5)
+    scene.add_object(obj(sdf, ColormapMaterial("magma")), 1)
+    scene.add_object(
+        obj(
+            repeat(Sphere((0, 0, 0), 0.3), (1, 1, 0), (2, 2, 1)),
+            NoiseMaterial((1, 0, 0), (0, 0, 1), 2.0, 3, seed=5),
+        )
+    )
+    sdf = displace(Sphere((2, 2, 2), 0.5), 0.1, seed=3)
+    sdf = warp(sdf, np.full((2, 2, 2, 3), 0.1), (1, 1, 1), (3, 3, 3))
+    material = OpacityMaterial(FalloffMaterial((1, 1, 1), 0.2).into(), 0.5)
+    scene.add_object(obj(sdf, material), -1)
+    scene.set_background([0.1, 0.2, 0.3])
+    scene.set_blend("alpha_over")
+    return scene
+
+
+class TestSceneFile:
+    @pytest.mark.parametrize("ext", ["json", "toml"])
+    @pytest.mark.parametrize("bvh", [False, True])
+    def test_objects_scene(self, tmp_path, ext, bvh):
+        scene = objects_scene()
+        if bvh:
+            scene.build_bvh()
+
+        path = tmp_path / f"scene.{ext}"
+        scene.into().save(path)
+        loaded = load_scene(path)
+        assert loaded.channels() == 3
+        npt.assert_array_equal(SAMPLER.sample(loaded), SAMPLER.sample(scene.into()))
+
+    @pytest.mark.parametrize("ext", ["json", "toml"])
+    def test_elastic_scene(self, tmp_path, ext):
+        inner = objects_scene().into()
+        scene = ElasticScene(inner, (0.5, 0.5, 0.5), 0.2, seed=3).into()
+        path = tmp_path / f"scene.{ext}"
+        scene.save(str(path))
+        loaded = load_scene(str(path))
+        npt.assert_array_equal(SAMPLER.sample(loaded), SAMPLER.sample(scene))
+
+    def test_channels(self, tmp_path):
+        scene = ObjectsScene(channels=4)
+        material = ChannelsMaterial([1, 2, 3, 4], ColoredMaterial((1, 1, 1)).into())
+        scene.add_object(obj(Sphere((0, 0, 0), 1), material))
+        scene.into().save(tmp_path / "scene.toml")
+        assert load_scene(tmp_path / "scene.toml").hit((0, 0, 0)) == (1, 2, 3, 4)
+
+    def test_hand_written(self, tmp_path):
+        path = tmp_path / "scene.toml"
+        path.write_text(
+            """
+version = 1
+
+[scene]
+type = "objects"
+channels = 3
+background = [0, 0, 0]
+blend = "first"
+
+[[scene.objects]]
+object.type = "sdf"
+object.sdf = { type = "sphere", center = [0, 0, 0], radius = 1 }
+object.material = { type = "colored", color = [1, 0, 0] }
+"""
+        )
+        scene = load_scene(path)
+        assert scene.hit((0, 0, 0)) == (1, 0, 0)
+        assert scene.hit((2, 0, 0)) == (0, 0, 0)
+
+    def test_version(self, tmp_path):
+        path = tmp_path / "scene.json"
+        objects_scene().into().save(path)
+        data = json.loads(path.read_text())
+        assert data["version"] == 1
+
+        data["scene"]["unknown"] = True
+        path.write_text(json.dumps(data))
+        load_scene(path)
+
+        data["version"] = 2
+        path.write_text(json.dumps(data))
+        with pytest.raises(ValueError, match="newer"):
+            load_scene(path)
+
+    def test_function_sdf(self, tmp_path):
+        scene = ObjectsScene()
+        sdf = FunctionSDF(lambda p: np.linalg.norm(p) - 1, ((-1, -1, -1), (1, 1, 1)))
+        scene.add_object(obj(sdf, ColoredMaterial((1, 1, 1))))
+        with pytest.raises(ValueError, match="cannot be saved"):
+            scene.into().save(tmp_path / "scene.json")
+
+    def test_format(self, tmp_path):
+        with pytest.raises(ValueError):
+            objects_scene().into().save(tmp_path / "scene.yaml")
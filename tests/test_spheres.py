@@ -0,0 +1,65 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import numpy as np
+import numpy.testing as npt
+import pytest
+
+from sdflit import Metaballs, Sphere, Spheres
+
+
+class TestSpheres:
+    centers = np.array([[0, 0, 0], [3, 0, 0], [0, 5, 0]])
+    radii = np.array([1, 1.5, 0.5])
+
+    @pytest.mark.parametrize(
+        "p",
+        [
+            # fmt: off
+            (0, 0, 0), (1.5, 0, 0), (3, 0, 0), (0, 4, 0), (10, 10, 10),
+            # fmt: on
+        ],
+    )
+    def test_distance(self, p):
+        spheres = Spheres(self.centers, self.radii)
+        expected = min(Sphere(tuple(c), r).distance(p) for c, r in zip(self.centers, self.radii))
+        npt.assert_allclose(spheres.distance(p), expected, rtol=1e-6)
+
+    def test_bounding_box(self):
+        spheres = Spheres(self.centers, self.radii)
+        npt.assert_allclose(spheres.bounding_box(), ((-1, -1.5, -1.5), (4.5, 5.5, 1.5)))
+
+    def test_invalid(self):
+        with pytest.raises(ValueError):
+            Spheres(self.centers, self.radii[:2])
+
+    @pytest.mark.parametrize("radius", [-1, np.nan])
+    def test_invalid_radii(self, radius):
+        radii = self.radii.copy()
+        radii[1] = radius
+        with pytest.raises(ValueError):
+            Spheres(self.centers, radii)
+
+
+class TestMetaballs:
+    def test_blend(self):
+        centers = np.array([[0, 0, 0], [2.4, 0, 0]])
+        radii = np.array([1, 1])
+        spheres = Spheres(centers, radii)
+        metaballs = Metaballs(centers, radii, 1)
+
+        # the gap between two spheres is filled when blending
+        assert not spheres.inside((1.2, 0, 0))
+        assert metaballs.inside((1.2, 0, 0))
+
+        # far away spheres are not affected
+        npt.assert_allclose(metaballs.distance((-3, 0, 0)), spheres.distance((-3, 0, 0)))
+
+    def test_bounding_box(self):
+        metaballs = Metaballs(np.zeros((1, 3)), np.ones(1), 0.5)
+        npt.assert_allclose(metaballs.bounding_box(), ((-1.5, -1.5, -1.5), (1.5, 1.5, 1.5)))
+
+    def test_invalid(self):
+        with pytest.raises(ValueError):
+            Metaballs(np.zeros((1, 3)), np.ones(1), 0)
@@ -0,0 +1,62 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import numpy as np
+import numpy.testing as npt
+import pytest
+
+from sdflit import SplineTube
+
+
+class TestSplineTube:
+    @pytest.mark.parametrize(
+        "p, expected",
+        [
+            # fmt: off
+            ((0, 0, 2), -1),
+            ((2, 0, 2), 1),
+            ((0, 3, 1), 2),
+            ((0, 0, -3), 2),
+            ((0, 0, 6), 1),
+            # fmt: on
+        ],
+    )
+    def test_straight_catmull_rom(self, p, expected):
+        points = np.array([[0, 0, 0], [0, 0, 2], [0, 0, 4]])
+        tube = SplineTube(points, np.ones(3))
+        npt.assert_allclose(tube.distance(p), expected, atol=1e-4)
+
+    @pytest.mark.parametrize(
+        "p, expected",
+        [
+            # fmt: off
+            ((0, 0, 1.5), -1),
+            ((3, 0, 1), 2),
+            ((0, 0, 5), 1),
+            # fmt: on
+        ],
+    )
+    def test_straight_bezier(self, p, expected):
+        points = np.array([[0, 0, 0], [0, 0, 1], [0, 0, 2], [0, 0, 3]])
+        tube = SplineTube(points, np.ones(4), "bezier")
+        npt.assert_allclose(tube.distance(p), expected, atol=1e-4)
+
+    def test_smooth(self):
+        # the curve passes through every point and bends smoothly in between
+        points = np.array([[0, 0, 0], [2, 0, 2], [0, 0, 4]])
+        tube = SplineTube(points, np.full(3, 0.5))
+        for p in points:
+            npt.assert_allclose(tube.distance(tuple(p)), -0.5, atol=1e-4)
+
+        assert tube.inside((1.8, 0, 2))
+
+    def test_invalid(self):
+        with pytest.raises(ValueError):
+            SplineTube(np.zeros((3, 3)), np.ones(2))
+
+        with pytest.raises(ValueError):
+            SplineTube(np.zeros((3, 3)), np.ones(3), "bezier")
+
+        with pytest.raises(ValueError):
+            SplineTube(np.zeros((3, 3)), np.ones(3), "bspline")
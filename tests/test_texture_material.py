@@ -0,0 +1,73 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import numpy.testing as npt
+import pytest
+
+from sdflit import CheckerMaterial, NoiseMaterial, StripesMaterial
+
+
+class TestCheckerMaterial:
+    def test_hit(self, sphere_scene):
+        material = CheckerMaterial((1, 0, 0), (0, 0, 1), (1, 1, 1)).into()
+        scene = sphere_scene(material, radius=10).into()
+        assert scene.hit((0.5, 0.5, 0.5)) == (1, 0, 0)
+        assert scene.hit((1.5, 0.5, 0.5)) == (0, 0, 1)
+        assert scene.hit((1.5, 1.5, 0.5)) == (1, 0, 0)
+        assert scene.hit((-0.5, 0.5, 0.5)) == (0, 0, 1)
+
+    def test_invalid(self):
+        with pytest.raises(ValueError):
+            CheckerMaterial((1, 0, 0), (0, 0, 1), (1, 0, 1))
+
+
+class TestStripesMaterial:
+    def test_hit(self, sphere_scene):
+        material = StripesMaterial((1, 0, 0), (0, 0, 1), 2, axis="y").into()
+        scene = sphere_scene(material, radius=10).into()
+        assert scene.hit((5, 1, 0)) == (1, 0, 0)
+        assert scene.hit((5, 3, 0)) == (0, 0, 1)
+        assert scene.hit((5, -1, 0)) == (0, 0, 1)
+
+    def test_invalid(self):
+        with pytest.raises(ValueError):
+            StripesMaterial((1, 0, 0), (0, 0, 1), 1, axis="u")
+        with pytest.raises(ValueError):
+            StripesMaterial((1, 0, 0), (0, 0, 1), 0)
+
+
+class TestNoiseMaterial:
+    points = [(0.1 * i, 0.37 * i, -0.21 * i) for i in range(20)]
+
+    def test_range(self, sphere_scene):
+        material = NoiseMaterial((0, 0, 0), (1, 1, 1), 2, octaves=3).into()
+        scene = sphere_scene(material, radius=10).into()
+        for p in self.points:
+            color = scene.hit(p)
+            assert all(0 <= c <= 1 for c in color)
+
+    def test_threshold(self, sphere_scene):
+        material = NoiseMaterial((0, 0, 0), (1, 1, 1), 2, threshold=0).into()
+        scene = sphere_scene(material, radius=10).into()
+        for p in self.points:
+            assert scene.hit(p) in ((0, 0, 0), (1, 1, 1))
+
+    def test_seed(self, sphere_scene):
+        def make():
+            material = NoiseMaterial((0, 0, 0), (1, 1, 1), 2, seed=1).into()
+            return sphere_scene(material, radius=10).into()
+
+        a, b = make(), make()
+        npt.assert_array_equal(
+            [a.hit(p) for p in self.points], [b.hit(p) for p in self.points]
+        )
+
+    def test_invalid(self):
+        with pytest.raises(ValueError):
+            NoiseMaterial((0, 0, 0), (1, 1, 1), kind="worley")
+
+    @pytest.mark.parametrize("octaves", [0, 17, 40])
+    def test_invalid_octaves(self, octaves):
+        with pytest.raises(ValueError):
+            NoiseMaterial((0, 0, 0), (1, 1, 1), octaves=octaves)
@@ -0,0 +1,62 @@
+# SPDX-FileCopyrightText: 2023-2026 Zexin Yuan <aim@yzx9.xyz>
+#
+# SPDX-License-Identifier: Apache-2.0
+
+import numpy as np
+import numpy.testing as npt
+import pytest
+
+from sdflit import LinearGradientMaterial, ObjectsScene, RoundCone, SDFObject, Tube
+
+
+class TestTube:
+    nodes = np.array([[0, 0, 0], [0, 0, 2], [0, 2, 2], [0, 2, 6]], dtype=np.float32)
+    radii = np.array([2, 1, 1, 0.5], dtype=np.float32)
+
+    @pytest.mark.parametrize(
+        "p",
+        [
+            # fmt: off
+            (0, 0, -3), (0, 0, 1), (1, 1, 2), (0, 2, 4), (3, 3, 3), (0, 2, 7),
+            # fmt: on
+        ],
+    )
+    def test_distance(self, p):
+        tube = Tube(self.nodes, self.radii)
+        expected = min(
+            RoundCone(a, b, ra, rb).distance(p)
+            for a, b, ra, rb in zip(
+                map(tuple, self.nodes[:-1]),
+                map(tuple, self.nodes[1:]),
+                self.radii[:-1],
+                self.radii[1:],
+            )
+        )
+        npt.assert_allclose(tube.distance(p), expected, rtol=1e-5, atol=1e-6)
+
+    def test_bounding_box(self):
+        tube = Tube(self.nodes, self.radii)
+        npt.assert_allclose(tube.bounding_box(), ((-2, -2, -2), (2, 3, 6.5)))
+
+    def test_arc_length(self):
+        tube = Tube(self.nodes, self.radii)
+        material = LinearGradientMaterial((0, 0, 0), (1, 1, 1), "v").into()
+        scene = ObjectsScene()
+        scene.add_object(SDFObject(tube.into(), material).into())
+
+        # total length is 8, so the node (0, 2, 2) sits at 4 / 8
+        npt.assert_allclose(scene.into().hit((0, 2, 2)), (0.5, 0.5, 0.5), atol=1e-6)
+
+    def test_invalid(self):
+        with pytest.raises(ValueError):
+            Tube(self.nodes, self.radii[:-1])
+
+        with pytest.raises(ValueError):
+            Tube(self.nodes[:1], self.radii[:1])
+
+    @pytest.mark.parametrize("radius", [-1, np.nan])
+    def test_invalid_radii(self, radius):
+        radii = self.radii.copy()
+        radii[1] = radius
+        with pytest.raises(ValueError):
+            Tube(self.nodes, radii)